@@ -0,0 +1,475 @@
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{format_ident, quote, quote_spanned};
+use syn::parse::{Parse, ParseStream, Result};
+use syn::{bracketed, parenthesized, Error, Ident, Lifetime, Lit, Pat, Path, Token, Type, Visibility};
+
+pub struct Grammar {
+    token : Type,
+    error : Type,
+    graph : Option<Ident>,
+    rules : Vec<Rule>,
+}
+
+struct Rule {
+    vis : Visibility,
+    name : Ident,
+    output : Type,
+    body : Alts,
+}
+
+struct Alts(Vec<Seq>, Span);
+
+struct Seq {
+    elems : Vec<Elem>,
+    action : Option<TokenStream>,
+}
+
+struct Elem {
+    binding : Option<Ident>,
+    lookahead : Option<Lookahead>,
+    atom : Atom,
+    repeats : Vec<Repeat>,
+}
+
+enum Lookahead {
+    Positive,
+    Negative,
+}
+
+enum Repeat {
+    Many,
+    Many1,
+    Optional,
+}
+
+enum Atom {
+    Any,
+    Rule(Ident),
+    Token(TokenStream),
+    Pattern(Pat),
+    Group(Alts),
+}
+
+impl Parse for Grammar {
+    fn parse(input : ParseStream) -> Result<Self> {
+        let mut token = None;
+        let mut error = None;
+        let mut graph = None;
+        while input.peek(Token![type]) {
+            input.parse::<Token![type]>()?;
+            let name : Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            match name.to_string().as_str() {
+                "Token" => { token = Some(input.parse()?); },
+                "Error" => { error = Some(input.parse()?); },
+                "Graph" => { graph = Some(input.parse()?); },
+                _ => { return Err(Error::new(name.span(), "expected `Token`, `Error` or `Graph`")); },
+            }
+            input.parse::<Token![;]>()?;
+        }
+
+        let token = token.ok_or_else(|| input.error("missing `type Token = ...;`"))?;
+        let error = error.ok_or_else(|| input.error("missing `type Error = ...;`"))?;
+
+        let mut rules = vec![];
+        while !input.is_empty() {
+            rules.push(input.parse()?);
+        }
+
+        Ok(Grammar { token, error, graph, rules })
+    }
+}
+
+impl Parse for Rule {
+    fn parse(input : ParseStream) -> Result<Self> {
+        let vis = input.parse()?;
+        let name = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let output = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let body = input.parse()?;
+        input.parse::<Token![;]>()?;
+        Ok(Rule { vis, name, output, body })
+    }
+}
+
+impl Parse for Alts {
+    fn parse(input : ParseStream) -> Result<Self> {
+        let span = input.span();
+        let mut seqs = vec![input.parse()?];
+        while input.peek(Token![|]) {
+            input.parse::<Token![|]>()?;
+            seqs.push(input.parse()?);
+        }
+        Ok(Alts(seqs, span))
+    }
+}
+
+fn at_seq_end(input : ParseStream) -> bool {
+    input.is_empty() || input.peek(Token![|]) || input.peek(Token![;]) || input.peek(Token![=>])
+}
+
+impl Parse for Seq {
+    fn parse(input : ParseStream) -> Result<Self> {
+        let mut elems = vec![];
+        while !at_seq_end(input) {
+            elems.push(input.parse()?);
+        }
+        if elems.is_empty() {
+            return Err(input.error("expected at least one element"));
+        }
+
+        let action = if input.peek(Token![=>]) {
+            input.parse::<Token![=>]>()?;
+            let mut tokens = TokenStream::new();
+            while !input.is_empty() && !input.peek(Token![|]) && !input.peek(Token![;]) {
+                tokens.extend([input.parse::<TokenTree>()?]);
+            }
+            if tokens.is_empty() {
+                return Err(input.error("expected an action expression after `=>`"));
+            }
+            Some(tokens)
+        }
+        else {
+            None
+        };
+
+        Ok(Seq { elems, action })
+    }
+}
+
+impl Parse for Elem {
+    fn parse(input : ParseStream) -> Result<Self> {
+        let binding = if input.peek(Ident) && input.peek2(Token![:]) && !input.peek2(Token![::]) {
+            let name = input.parse()?;
+            input.parse::<Token![:]>()?;
+            Some(name)
+        }
+        else {
+            None
+        };
+
+        let lookahead = if input.peek(Token![&]) {
+            input.parse::<Token![&]>()?;
+            Some(Lookahead::Positive)
+        }
+        else if input.peek(Token![!]) {
+            input.parse::<Token![!]>()?;
+            Some(Lookahead::Negative)
+        }
+        else {
+            None
+        };
+
+        let atom = input.parse()?;
+
+        let mut repeats = vec![];
+        loop {
+            if input.peek(Token![*]) {
+                input.parse::<Token![*]>()?;
+                repeats.push(Repeat::Many);
+            }
+            else if input.peek(Token![+]) {
+                input.parse::<Token![+]>()?;
+                repeats.push(Repeat::Many1);
+            }
+            else if input.peek(Token![?]) {
+                input.parse::<Token![?]>()?;
+                repeats.push(Repeat::Optional);
+            }
+            else {
+                break;
+            }
+        }
+
+        Ok(Elem { binding, lookahead, atom, repeats })
+    }
+}
+
+impl Parse for Atom {
+    fn parse(input : ParseStream) -> Result<Self> {
+        if input.peek(Token![_]) {
+            input.parse::<Token![_]>()?;
+            Ok(Atom::Any)
+        }
+        else if input.peek(Lit) {
+            let lit : Lit = input.parse()?;
+            Ok(Atom::Token(quote!(#lit)))
+        }
+        else if input.peek(syn::token::Paren) {
+            let content;
+            parenthesized!(content in input);
+            Ok(Atom::Group(content.parse()?))
+        }
+        else if input.peek(syn::token::Bracket) {
+            let content;
+            bracketed!(content in input);
+            Ok(Atom::Pattern(Pat::parse_multi(&content)?))
+        }
+        else {
+            let path : Path = input.parse()?;
+            match path.get_ident() {
+                Some(name) => Ok(Atom::Rule(name.clone())),
+                None => Ok(Atom::Token(quote!(#path))),
+            }
+        }
+    }
+}
+
+struct Context<'g> {
+    error : &'g Type,
+    labels : usize,
+}
+
+const INPUT : &str = "__jln_input";
+
+fn input() -> Ident {
+    Ident::new(INPUT, Span::call_site())
+}
+
+impl Grammar {
+    pub fn expand(&self) -> TokenStream {
+        let token = &self.token;
+        let error = &self.error;
+        let mut context = Context { error, labels: 0 };
+        let input = input();
+
+        let rules = self.rules.iter().map(|rule| {
+            let Rule { vis, name, output, body } = rule;
+            let body = context.alts(body);
+            quote! {
+                #vis fn #name(#input : &mut ::jlnexus::Parser<'_, #token>) -> ::std::result::Result<#output, #error> {
+                    #input.context(stringify!(#name), |#input| -> ::std::result::Result<#output, #error> { #body })
+                }
+            }
+        }).collect::<Vec<_>>();
+
+        let graph = self.graph.as_ref().map(|graph| {
+            let rules = self.rules.iter().map(|rule| {
+                let Rule { name, output, body, .. } = rule;
+                let body = body.describe();
+                let output = crate::render(quote!(#output));
+                quote!(::jlnexus::GraphRule { name: stringify!(#name), output: #output, body: #body })
+            });
+            quote! {
+                pub fn #graph() -> ::jlnexus::Graph {
+                    ::jlnexus::Graph { rules: vec![#(#rules),*] }
+                }
+            }
+        });
+
+        quote!(#(#rules)* #graph)
+    }
+}
+
+impl Alts {
+    fn describe(&self) -> TokenStream {
+        if self.0.len() == 1 {
+            return self.0[0].describe();
+        }
+        let seqs = self.0.iter().map(Seq::describe);
+        quote!(::jlnexus::GraphNode::Alt(vec![#(#seqs),*]))
+    }
+}
+
+impl Seq {
+    fn describe(&self) -> TokenStream {
+        if self.elems.len() == 1 {
+            return self.elems[0].describe();
+        }
+        let elems = self.elems.iter().map(Elem::describe);
+        quote!(::jlnexus::GraphNode::Seq(vec![#(#elems),*]))
+    }
+}
+
+impl Elem {
+    fn describe(&self) -> TokenStream {
+        let mut node = self.atom.describe();
+        for repeat in &self.repeats {
+            node = match repeat {
+                Repeat::Many => quote!(::jlnexus::GraphNode::Many(::std::boxed::Box::new(#node))),
+                Repeat::Many1 => quote!(::jlnexus::GraphNode::Many1(::std::boxed::Box::new(#node))),
+                Repeat::Optional => quote!(::jlnexus::GraphNode::Optional(::std::boxed::Box::new(#node))),
+            };
+        }
+        match self.lookahead {
+            None => node,
+            Some(ref lookahead) => {
+                let positive = matches!(lookahead, Lookahead::Positive);
+                quote!(::jlnexus::GraphNode::Lookahead { positive: #positive, node: ::std::boxed::Box::new(#node) })
+            },
+        }
+    }
+}
+
+impl Atom {
+    fn describe(&self) -> TokenStream {
+        match self {
+            Atom::Any => quote!(::jlnexus::GraphNode::Any),
+            Atom::Rule(name) => quote!(::jlnexus::GraphNode::Rule(stringify!(#name))),
+            Atom::Token(value) => {
+                let text = value.to_string();
+                quote!(::jlnexus::GraphNode::Token(#text))
+            },
+            Atom::Pattern(pattern) => {
+                let text = quote!(#pattern).to_string();
+                quote!(::jlnexus::GraphNode::Pattern(#text))
+            },
+            Atom::Group(alts) => alts.describe(),
+        }
+    }
+}
+
+impl<'g> Context<'g> {
+    fn closure(&self, body : TokenStream) -> TokenStream {
+        let error = self.error;
+        let input = input();
+        quote!(|#input| -> ::std::result::Result<_, #error> { #body })
+    }
+
+    fn unexpected(&self, index : &Ident) -> TokenStream {
+        let error = self.error;
+        quote!(<#error as ::jlnexus::JlnError>::from_kind(::jlnexus::ErrorKind::Unexpected, #index))
+    }
+
+    fn alts(&mut self, alts : &Alts) -> TokenStream {
+        if alts.0.len() == 1 {
+            return self.seq(&alts.0[0]);
+        }
+
+        let error = self.error;
+        let input = input();
+        self.labels += 1;
+        let label = Lifetime::new(&format!("'__jln_alts_{}", self.labels), Span::call_site());
+        let count = alts.0.len();
+        let location = quote_spanned!(alts.1=> ::std::panic::Location::caller());
+        let attempts = alts.0.iter().map(|seq| {
+            let seq = self.seq(seq);
+            let closure = self.closure(seq);
+            quote! {
+                match #input.alternative_at::<_, #error, _, #count>(&mut __jln_errors, __jln_location, #closure) {
+                    Ok(Some(v)) => { break #label Ok(v); },
+                    Ok(None) => { },
+                    Err(e) => { break #label Err(e); },
+                }
+            }
+        }).collect::<Vec<_>>();
+
+        quote! {
+            #label: {
+                let __jln_location = #location;
+                let mut __jln_errors = ::jlnexus::Furthest::<#error, #count>::new();
+                #(#attempts)*
+                Err(__jln_errors.into_error())
+            }
+        }
+    }
+
+    fn seq(&mut self, seq : &Seq) -> TokenStream {
+        if seq.elems.len() == 1 && seq.action.is_none() {
+            return self.elem(&seq.elems[0]);
+        }
+
+        let input = input();
+        let mut names = vec![];
+        let steps = seq.elems.iter().enumerate().map(|(i, elem)| {
+            let name = elem.binding.clone().unwrap_or_else(|| format_ident!("__jln_{}", i));
+            let value = self.elem(elem);
+            let step = if elem.binding.is_none() && seq.action.is_some() {
+                quote!(let _ = #value?;)
+            }
+            else {
+                quote!(let #name = #value?;)
+            };
+            names.push(name);
+            step
+        }).collect::<Vec<_>>();
+
+        let result = match &seq.action {
+            Some(action) => action.clone(),
+            None => quote!((#(#names),*)),
+        };
+
+        let closure = self.closure(quote! {
+            #(#steps)*
+            Ok(#result)
+        });
+        quote!(#input.with_rollback(#closure))
+    }
+
+    fn elem(&mut self, elem : &Elem) -> TokenStream {
+        let input = input();
+        let mut value = self.atom(&elem.atom);
+
+        for repeat in &elem.repeats {
+            value = match repeat {
+                Repeat::Many => {
+                    let closure = self.closure(value);
+                    quote!(#input.list(#closure))
+                },
+                Repeat::Optional => {
+                    let closure = self.closure(value);
+                    quote!(#input.option(#closure))
+                },
+                Repeat::Many1 => {
+                    let rest = self.closure(value.clone());
+                    let closure = self.closure(quote! {
+                        let mut __jln_items = vec![#value?];
+                        __jln_items.extend(#input.list(#rest)?);
+                        Ok(__jln_items)
+                    });
+                    quote!(#input.with_rollback(#closure))
+                },
+            };
+        }
+
+        match elem.lookahead {
+            None => value,
+            Some(ref lookahead) => {
+                let index = format_ident!("__jln_index");
+                let unexpected = self.unexpected(&index);
+                let expected = matches!(lookahead, Lookahead::Positive);
+                let closure = self.closure(value);
+                quote! {
+                    {
+                        let #index = #input.index();
+                        match #input.check(#closure) {
+                            Ok(matched) if matched == #expected => Ok(()),
+                            Ok(_) => Err(#unexpected),
+                            Err(e) => Err(e),
+                        }
+                    }
+                }
+            },
+        }
+    }
+
+    fn atom(&mut self, atom : &Atom) -> TokenStream {
+        let error = self.error;
+        let input = input();
+        let index = format_ident!("__jln_index");
+        let unexpected = self.unexpected(&index);
+        match atom {
+            Atom::Any => quote!(#input.get::<#error>().map(::std::clone::Clone::clone)),
+            Atom::Rule(name) => quote!(#name(#input)),
+            Atom::Token(value) => {
+                let description = crate::describe(value);
+                quote!(#input.expecting(#description, |#input| #input.expect::<#error>(&(#value)).map(::std::clone::Clone::clone)))
+            },
+            Atom::Pattern(pattern) => {
+                let closure = self.closure(quote! {
+                    let #index = #input.index();
+                    let __jln_token = #input.get::<#error>()?;
+                    #[allow(unused_variables)]
+                    match __jln_token {
+                        #pattern => Ok(::std::clone::Clone::clone(__jln_token)),
+                        _ => Err(#unexpected),
+                    }
+                });
+                quote!(#input.with_rollback(#closure))
+            },
+            Atom::Group(alts) => self.alts(alts),
+        }
+    }
+}
@@ -0,0 +1,71 @@
+
+mod grammar;
+mod parse;
+
+use proc_macro::TokenStream;
+
+fn describe(token : &proc_macro2::TokenStream) -> String {
+    match syn::parse2::<syn::Lit>(token.clone()) {
+        Ok(syn::Lit::Char(c)) => format!("`{}`", c.value()),
+        Ok(syn::Lit::Str(s)) => format!("`{}`", s.value()),
+        Ok(syn::Lit::Byte(b)) => format!("`{}`", b.value() as char),
+        _ => format!("`{}`", render(token.clone())),
+    }
+}
+
+fn render(tokens : proc_macro2::TokenStream) -> String {
+    use proc_macro2::{Delimiter, Spacing, TokenTree};
+
+    let mut text = String::new();
+    let mut word = false;
+    let mut joint = false;
+    for tree in tokens {
+        match tree {
+            TokenTree::Group(group) => {
+                let (open, close) = match group.delimiter() {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::Brace => ("{ ", " }"),
+                    Delimiter::None => ("", ""),
+                };
+                text.push_str(open);
+                text.push_str(&render(group.stream()));
+                text.push_str(close);
+                word = true;
+                joint = false;
+            },
+            TokenTree::Punct(punct) => {
+                match punct.as_char() {
+                    ',' | ';' => { text.push(punct.as_char()); text.push(' '); },
+                    '=' | '+' | '|' if !joint && punct.spacing() == Spacing::Alone => { text.push(' '); text.push(punct.as_char()); text.push(' '); },
+                    '-' if punct.spacing() == Spacing::Joint => { text.push_str(" -"); },
+                    '>' if joint && text.ends_with('-') => { text.push_str("> "); },
+                    c => { text.push(c); },
+                }
+                word = false;
+                joint = punct.spacing() == Spacing::Joint && punct.as_char() != '\'';
+            },
+            tree => {
+                if word {
+                    text.push(' ');
+                }
+                text.push_str(&tree.to_string());
+                word = true;
+                joint = false;
+            },
+        }
+    }
+    text.trim_end().to_string()
+}
+
+#[proc_macro]
+pub fn grammar(input : TokenStream) -> TokenStream {
+    let grammar = syn::parse_macro_input!(input as grammar::Grammar);
+    grammar.expand().into()
+}
+
+#[proc_macro_derive(Parse, attributes(jlnexus))]
+pub fn derive_parse(input : TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    parse::expand(input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
@@ -0,0 +1,240 @@
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, quote_spanned};
+use syn::{parenthesized, Attribute, Data, DeriveInput, Error, Expr, Fields, GenericArgument, Ident, Lifetime, Path, PathArguments, Result, Token, Type};
+
+#[derive(Default)]
+struct Options {
+    token : Option<Type>,
+    error : Option<Type>,
+    literals : Vec<Expr>,
+    after : Vec<Expr>,
+    delimited : Option<(Expr, Expr)>,
+    separated : Option<Expr>,
+    with : Option<Path>,
+}
+
+impl Options {
+    fn from_attrs(attrs : &[Attribute]) -> Result<Self> {
+        let mut options = Options::default();
+        for attr in attrs.iter().filter(|x| x.path().is_ident("jlnexus")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("token") {
+                    options.token = Some(meta.value()?.parse()?);
+                }
+                else if meta.path.is_ident("error") {
+                    options.error = Some(meta.value()?.parse()?);
+                }
+                else if meta.path.is_ident("literal") {
+                    options.literals.push(meta.value()?.parse()?);
+                }
+                else if meta.path.is_ident("after") {
+                    options.after.push(meta.value()?.parse()?);
+                }
+                else if meta.path.is_ident("separated") {
+                    options.separated = Some(meta.value()?.parse()?);
+                }
+                else if meta.path.is_ident("with") {
+                    options.with = Some(meta.value()?.parse()?);
+                }
+                else if meta.path.is_ident("delimited") {
+                    let content;
+                    parenthesized!(content in meta.input);
+                    let open = content.parse()?;
+                    content.parse::<Token![,]>()?;
+                    let close = content.parse()?;
+                    options.delimited = Some((open, close));
+                }
+                else {
+                    return Err(meta.error("unsupported jlnexus attribute"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(options)
+    }
+}
+
+const INPUT : &str = "__jln_input";
+
+fn input() -> Ident {
+    Ident::new(INPUT, Span::call_site())
+}
+
+struct Context {
+    token : Type,
+    error : Type,
+}
+
+impl Context {
+    fn closure(&self, body : TokenStream) -> TokenStream {
+        let error = &self.error;
+        let input = input();
+        quote!(|#input| -> ::std::result::Result<_, #error> { #body })
+    }
+
+    fn expect(&self, token : &Expr) -> TokenStream {
+        let error = &self.error;
+        let input = input();
+        let description = crate::describe(&quote!(#token));
+        quote!(#input.expecting(#description, |#input| #input.expect::<#error>(&(#token)).map(|_| ()))?;)
+    }
+
+    fn wrap(&self, options : &Options, value : TokenStream) -> TokenStream {
+        let literals = options.literals.iter().map(|x| self.expect(x));
+        let after = options.after.iter().map(|x| self.expect(x));
+        let (open, close) = match &options.delimited {
+            Some((open, close)) => (Some(self.expect(open)), Some(self.expect(close))),
+            None => (None, None),
+        };
+        quote! {
+            {
+                #(#literals)*
+                #open
+                let __jln_value = #value;
+                #close
+                #(#after)*
+                __jln_value
+            }
+        }
+    }
+
+    fn parse(&self, ty : &Type) -> TokenStream {
+        let token = &self.token;
+        let error = &self.error;
+        let input = input();
+        quote!(<#ty as ::jlnexus::Parse<#token, #error>>::parse(#input))
+    }
+
+    fn field(&self, ty : &Type, options : &Options) -> TokenStream {
+        let input = input();
+        let value = match (&options.with, wrapper(ty)) {
+            (Some(with), _) => quote!(#with(#input)?),
+            (None, Some(("Vec", inner))) => {
+                let item = self.parse(inner);
+                match &options.separated {
+                    Some(separator) => {
+                        let separator = self.expect(separator);
+                        let first = self.closure(item.clone());
+                        let rest = self.closure(quote! { #separator #item });
+                        quote! {
+                            {
+                                let mut __jln_items = ::std::vec::Vec::new();
+                                if let Some(first) = #input.option(#first)? {
+                                    __jln_items.push(first);
+                                    __jln_items.extend(#input.list(#rest)?);
+                                }
+                                __jln_items
+                            }
+                        }
+                    },
+                    None => {
+                        let closure = self.closure(item);
+                        quote!(#input.list(#closure)?)
+                    },
+                }
+            },
+            (None, Some(("Option", inner))) => {
+                let closure = self.closure(self.parse(inner));
+                quote!(#input.option(#closure)?)
+            },
+            (None, Some(("Box", inner))) => {
+                let item = self.parse(inner);
+                quote!(::std::boxed::Box::new(#item?))
+            },
+            _ => {
+                let item = self.parse(ty);
+                quote!(#item?)
+            },
+        };
+        self.wrap(options, value)
+    }
+
+    fn fields(&self, path : TokenStream, fields : &Fields) -> Result<TokenStream> {
+        let error = &self.error;
+        let mut steps = vec![];
+        let mut names = vec![];
+        for (i, field) in fields.iter().enumerate() {
+            let options = Options::from_attrs(&field.attrs)?;
+            let name = field.ident.clone().unwrap_or_else(|| format_ident!("__jln_{}", i));
+            let value = self.field(&field.ty, &options);
+            steps.push(quote!(let #name = #value;));
+            names.push(name);
+        }
+        let construct = match fields {
+            Fields::Named(_) => quote!(#path { #(#names),* }),
+            Fields::Unnamed(_) => quote!(#path(#(#names),*)),
+            Fields::Unit => quote!(#path),
+        };
+        Ok(quote! {
+            #(#steps)*
+            Ok::<_, #error>(#construct)
+        })
+    }
+}
+
+fn wrapper(ty : &Type) -> Option<(&'static str, &Type)> {
+    let Type::Path(path) = ty else { return None; };
+    let segment = path.path.segments.last()?;
+    let PathArguments::AngleBracketed(args) = &segment.arguments else { return None; };
+    let Some(GenericArgument::Type(inner)) = args.args.first() else { return None; };
+    ["Vec", "Option", "Box"].into_iter().find(|x| segment.ident == x).map(|x| (x, inner))
+}
+
+pub fn expand(item : DeriveInput) -> Result<TokenStream> {
+    let options = Options::from_attrs(&item.attrs)?;
+    let token = options.token.clone().ok_or_else(|| Error::new_spanned(&item.ident, "missing #[jlnexus(token = ...)]"))?;
+    let error = options.error.clone().ok_or_else(|| Error::new_spanned(&item.ident, "missing #[jlnexus(error = ...)]"))?;
+    let context = Context { token: token.clone(), error: error.clone() };
+    let name = &item.ident;
+    let parser = input();
+
+    let body = match &item.data {
+        Data::Struct(data) => {
+            let body = context.fields(quote!(Self), &data.fields)?;
+            let closure = context.closure(body);
+            context.wrap(&options, quote!(#parser.with_rollback(#closure)?))
+        },
+        Data::Enum(data) => {
+            let label = Lifetime::new("'__jln_variants", Span::call_site());
+            let count = data.variants.len();
+            let location = quote_spanned!(name.span()=> ::std::panic::Location::caller());
+            let mut attempts = vec![];
+            for variant in &data.variants {
+                let variant_options = Options::from_attrs(&variant.attrs)?;
+                let variant_name = &variant.ident;
+                let body = context.fields(quote!(Self::#variant_name), &variant.fields)?;
+                let body = context.wrap(&variant_options, quote!({ #body }?));
+                let closure = context.closure(quote!(Ok(#body)));
+                attempts.push(quote! {
+                    match #parser.alternative_at::<_, #error, _, #count>(&mut __jln_errors, __jln_location, #closure) {
+                        Ok(Some(v)) => { break #label Ok(v); },
+                        Ok(None) => { },
+                        Err(e) => { break #label Err(e); },
+                    }
+                });
+            }
+            let variants = quote! {
+                #label: {
+                    let __jln_location = #location;
+                    let mut __jln_errors = ::jlnexus::Furthest::<#error, #count>::new();
+                    #(#attempts)*
+                    Err(__jln_errors.into_error())
+                }
+            };
+            context.wrap(&options, quote!(#variants?))
+        },
+        Data::Union(_) => { return Err(Error::new_spanned(name, "unions are not supported")); },
+    };
+
+    let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::jlnexus::Parse<#token, #error> for #name #ty_generics #where_clause {
+            fn parse(#parser : &mut ::jlnexus::Parser<'_, #token>) -> ::std::result::Result<Self, #error> {
+                #parser.context(stringify!(#name), |#parser| -> ::std::result::Result<Self, #error> {
+                    #parser.with_rollback(|#parser| -> ::std::result::Result<Self, #error> { Ok(#body) })
+                })
+            }
+        }
+    })
+}
@@ -0,0 +1,158 @@
+
+use jlnexus::{grammar, ErrorKind, GraphNode, JlnError, Parser};
+
+#[derive(Debug, PartialEq)]
+enum TestError {
+    Eof,
+    Unexpected(usize),
+    Fatal,
+    Aggregate(Vec<TestError>),
+}
+
+impl JlnError for TestError {
+    fn is_fatal(&self) -> bool { matches!(self, TestError::Fatal) }
+    fn eof() -> Self { TestError::Eof }
+    fn aggregate(errors : Vec<Self>) -> Self { TestError::Aggregate(errors) }
+    fn from_kind(kind : ErrorKind, index : usize) -> Self {
+        if kind.is_fatal() { TestError::Fatal } else { TestError::Unexpected(index) }
+    }
+}
+
+grammar! {
+    type Token = char;
+    type Error = TestError;
+    type Graph = arithmetic;
+
+    digit : u32 = c:[ '0'..='9' ] => c.to_digit(10).unwrap();
+    pub number : u32 = ds:digit+ => ds.into_iter().fold(0, |a, d| a * 10 + d);
+    atom : u32 = number | '(' e:sum ')' => e;
+    product : u32 = a:atom rest:('*' b:atom => b)* => rest.into_iter().fold(a, |x, y| x * y);
+    pub sum : u32 = a:product rest:('+' b:product => b)* => a + rest.into_iter().sum::<u32>();
+    pub signed : (Option<char>, u32) = '-'? number;
+    pub keyword : () = 'i' 'f' !_ => ();
+}
+
+fn chars(input : &str) -> Vec<char> {
+    input.chars().collect()
+}
+
+#[test]
+fn should_parse_sequences_and_repetition() {
+    let input = chars("123");
+    let mut parser = Parser::new(&input);
+
+    assert_eq!(number(&mut parser), Ok(123));
+    assert!(parser.end());
+}
+
+#[test]
+fn should_parse_alternatives_and_groups() {
+    let input = chars("2*(3+4)+1");
+    let mut parser = Parser::new(&input);
+
+    assert_eq!(sum(&mut parser), Ok(15));
+    assert!(parser.end());
+}
+
+#[test]
+fn should_return_tuple_without_action() {
+    let input = chars("-7");
+    let mut parser = Parser::new(&input);
+
+    assert_eq!(signed(&mut parser), Ok((Some('-'), 7)));
+}
+
+#[test]
+fn should_support_negative_lookahead() {
+    let input = chars("if");
+    let mut parser = Parser::new(&input);
+    assert_eq!(keyword(&mut parser), Ok(()));
+
+    let input = chars("ifx");
+    let mut parser = Parser::new(&input);
+    assert!(keyword(&mut parser).is_err());
+    assert_eq!(parser.index(), 0);
+}
+
+#[test]
+fn should_roll_back_failed_rules() {
+    let input = chars("(1+");
+    let mut parser = Parser::new(&input);
+
+    assert!(sum(&mut parser).is_err());
+    assert_eq!(parser.index(), 0);
+}
+
+#[test]
+fn should_describe_rule_graph() {
+    let graph = arithmetic();
+
+    assert_eq!(graph.rules.len(), 7);
+    assert_eq!(graph.rule("atom").unwrap().references(), vec!["number", "sum"]);
+    assert_eq!(graph.rule("keyword").unwrap().body, GraphNode::Seq(vec![
+        GraphNode::Token("'i'"),
+        GraphNode::Token("'f'"),
+        GraphNode::Lookahead { positive: false, node: Box::new(GraphNode::Any) },
+    ]));
+    assert_eq!(graph.rule("signed").unwrap().output, "(Option<char>, u32)");
+    assert!(graph.to_dot().contains("\"sum\" -> \"product\";"));
+}
+
+mod expected {
+    use jlnexus::{grammar, Deferred, Diagnostic, Parser};
+
+    grammar! {
+        type Token = char;
+        type Error = Deferred<Diagnostic>;
+
+        pub op : char = '+' | '-' | ('*' '*' => '^') | '*';
+        pub pow : char = '+' | ('*' '*' => '^');
+    }
+
+    #[test]
+    fn should_merge_expected_tokens_through_deferred() {
+        let input = "x".chars().collect::<Vec<_>>();
+
+        let error = op(&mut Parser::new(&input)).map_err(Deferred::into_error).unwrap_err();
+
+        assert_eq!(error.snapshot(), "in op at 0:\n  at 0: expected one of `*`, `+`, `-`\n");
+    }
+
+    #[test]
+    fn should_keep_only_furthest_alternatives() {
+        let input = "*x".chars().collect::<Vec<_>>();
+
+        let error = pow(&mut Parser::new(&input)).map_err(Deferred::into_error).unwrap_err();
+
+        assert_eq!(error.snapshot(), "in pow at 0:\n  at 1: expected `*`\n");
+    }
+}
+
+mod coverage {
+    use jlnexus::{grammar, Parser};
+
+    use super::TestError;
+
+    grammar! {
+        type Token = char;
+        type Error = TestError;
+
+        sign : char = '+' | '-';
+        pub signed_letter : (char, char) = sign ('a' | 'b');
+    }
+
+    #[test]
+    fn should_cover_each_alternation_separately() {
+        let input = "-b".chars().collect::<Vec<_>>();
+        let mut parser = Parser::new(&input);
+        parser.enable_coverage();
+
+        assert_eq!(signed_letter(&mut parser), Ok(('-', 'b')));
+
+        let coverage = parser.coverage().unwrap();
+        assert_eq!(coverage.alternatives.values().collect::<Vec<_>>(), vec![&vec![0, 1], &vec![0, 1]]);
+        let unreached = coverage.unreached_alternatives();
+        assert_eq!(unreached.iter().map(|x| x.1).collect::<Vec<_>>(), vec![0, 0]);
+        assert_ne!(unreached[0].0, unreached[1].0);
+    }
+}
@@ -0,0 +1,169 @@
+
+use jlnexus::{ErrorKind, JlnError, Parse, Parser};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    Let,
+    Eq,
+    Comma,
+    Semi,
+    LParen,
+    RParen,
+    Ident(String),
+    Num(i64),
+}
+
+#[derive(Debug, PartialEq)]
+enum TestError {
+    Unexpected,
+    Fatal,
+}
+
+impl JlnError for TestError {
+    fn is_fatal(&self) -> bool { matches!(self, TestError::Fatal) }
+    fn eof() -> Self { TestError::Unexpected }
+    fn aggregate(_errors : Vec<Self>) -> Self { TestError::Unexpected }
+    fn from_kind(kind : ErrorKind, _index : usize) -> Self {
+        if kind.is_fatal() { TestError::Fatal } else { TestError::Unexpected }
+    }
+}
+
+fn ident(input : &mut Parser<Tok>) -> Result<String, TestError> {
+    match input.get()? {
+        Tok::Ident(x) => Ok(x.clone()),
+        _ => Err(TestError::Unexpected),
+    }
+}
+
+#[derive(Debug, PartialEq, Parse)]
+#[jlnexus(token = Tok, error = TestError)]
+struct Number(#[jlnexus(with = number)] i64);
+
+fn number(input : &mut Parser<Tok>) -> Result<i64, TestError> {
+    match input.get()? {
+        Tok::Num(x) => Ok(*x),
+        _ => Err(TestError::Unexpected),
+    }
+}
+
+#[derive(Debug, PartialEq, Parse)]
+#[jlnexus(token = Tok, error = TestError)]
+enum Expr {
+    Num(Number),
+    Call(#[jlnexus(with = ident)] String, #[jlnexus(delimited(Tok::LParen, Tok::RParen), separated = Tok::Comma)] Vec<Expr>),
+    Var(#[jlnexus(with = ident)] String),
+}
+
+#[derive(Debug, PartialEq, Parse)]
+#[jlnexus(token = Tok, error = TestError, literal = Tok::Let, after = Tok::Semi)]
+struct Let {
+    #[jlnexus(with = ident)]
+    name : String,
+    #[jlnexus(literal = Tok::Eq)]
+    value : Box<Expr>,
+}
+
+fn ident_tok(x : &str) -> Tok {
+    Tok::Ident(x.into())
+}
+
+#[test]
+fn should_parse_struct_fields_in_order() {
+    let input = vec![Tok::Let, ident_tok("x"), Tok::Eq, Tok::Num(5), Tok::Semi];
+    let mut parser = Parser::new(&input);
+
+    let result = Let::parse(&mut parser);
+
+    assert_eq!(result, Ok(Let { name: "x".into(), value: Box::new(Expr::Num(Number(5))) }));
+    assert!(parser.end());
+}
+
+#[test]
+fn should_parse_enum_variants_with_delimiters_and_separators() {
+    let input = vec![ident_tok("f"), Tok::LParen, Tok::Num(1), Tok::Comma, ident_tok("y"), Tok::RParen];
+    let mut parser = Parser::new(&input);
+
+    let result = Expr::parse(&mut parser);
+
+    assert_eq!(result, Ok(Expr::Call("f".into(), vec![Expr::Num(Number(1)), Expr::Var("y".into())])));
+    assert!(parser.end());
+}
+
+#[test]
+fn should_fall_back_to_later_variant() {
+    let input = vec![ident_tok("y"), Tok::Semi];
+    let mut parser = Parser::new(&input);
+
+    let result = Expr::parse(&mut parser);
+
+    assert_eq!(result, Ok(Expr::Var("y".into())));
+    assert_eq!(parser.index(), 1);
+}
+
+#[test]
+fn should_roll_back_on_failure() {
+    let input = vec![Tok::Let, ident_tok("x"), Tok::Eq, Tok::Num(5)];
+    let mut parser = Parser::new(&input);
+
+    assert!(Let::parse(&mut parser).is_err());
+    assert_eq!(parser.index(), 0);
+}
+
+#[test]
+fn should_parse_through_parser_helper() {
+    let input = vec![Tok::Num(3)];
+    let mut parser = Parser::new(&input);
+
+    assert_eq!(parser.parse::<Option<Number>, TestError>(), Ok(Some(Number(3))));
+    assert!(parser.end());
+}
+
+#[derive(Debug, PartialEq, Parse)]
+#[jlnexus(token = Tok, error = jlnexus::Deferred<jlnexus::Diagnostic>)]
+enum Punct {
+    #[jlnexus(literal = Tok::Comma)]
+    Comma,
+    #[jlnexus(literal = Tok::Semi)]
+    Semi,
+}
+
+#[test]
+fn should_merge_expected_literals_across_variants() {
+    let input = vec![Tok::Eq];
+    let mut parser = Parser::new(&input);
+
+    let error = Punct::parse(&mut parser).map_err(jlnexus::Deferred::into_error).unwrap_err();
+
+    assert_eq!(error.snapshot(), "in Punct at 0:\n  at 0: expected one of `Tok::Comma`, `Tok::Semi`\n");
+}
+
+#[derive(Debug, PartialEq, Parse)]
+#[jlnexus(token = Tok, error = jlnexus::Deferred<jlnexus::Diagnostic>)]
+enum Sep {
+    #[jlnexus(literal = Tok::Comma, after = Tok::Semi)]
+    Pair,
+    #[jlnexus(literal = Tok::Eq)]
+    Eq,
+}
+
+#[test]
+fn should_keep_only_furthest_variants() {
+    let input = vec![Tok::Comma, Tok::Eq];
+    let mut parser = Parser::new(&input);
+
+    let error = Sep::parse(&mut parser).map_err(jlnexus::Deferred::into_error).unwrap_err();
+
+    assert_eq!(error.snapshot(), "in Sep at 0:\n  at 1: expected `Tok::Semi`\n");
+}
+
+#[test]
+fn should_cover_enum_variants() {
+    let input = vec![ident_tok("y"), Tok::Semi];
+    let mut parser = Parser::new(&input);
+    parser.enable_coverage();
+
+    assert_eq!(Expr::parse(&mut parser), Ok(Expr::Var("y".into())));
+
+    let coverage = parser.coverage().unwrap();
+    assert_eq!(coverage.alternatives.values().collect::<Vec<_>>(), vec![&vec![0, 0, 1]]);
+}
@@ -0,0 +1,129 @@
+
+use crate::{JlnError, Parser, Span};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Annotations<A> {
+    entries : Vec<(Span, A)>,
+}
+
+impl<A> Default for Annotations<A> {
+    fn default() -> Self {
+        Annotations::new()
+    }
+}
+
+impl<A> Annotations<A> {
+    pub fn new() -> Self {
+        Annotations { entries: vec![] }
+    }
+
+    pub fn insert(&mut self, span : Span, value : A) {
+        let at = self.entries.partition_point(|(x, _)| x.start <= span.start);
+        self.entries.insert(at, (span, value));
+    }
+
+    pub fn starting_at(&self, index : usize) -> impl Iterator<Item = &(Span, A)> {
+        let from = self.entries.partition_point(|(x, _)| x.start < index);
+        self.entries[from..].iter().take_while(move |(x, _)| x.start == index)
+    }
+
+    pub fn covering(&self, index : usize) -> impl Iterator<Item = &(Span, A)> {
+        self.entries.iter().filter(move |(x, _)| x.start <= index && index < x.end)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &(Span, A)> {
+        self.entries.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl<'a, T> Parser<'a, T> {
+    pub fn annotate<A : Clone + 'static>(&mut self, span : Span, value : A) {
+        if let Some(annotations) = self.state_mut::<Annotations<A>>() {
+            annotations.insert(span, value);
+        }
+    }
+
+    pub fn annotations<A : 'static>(&self) -> Option<&Annotations<A>> {
+        self.state::<Annotations<A>>()
+    }
+
+    pub fn two_pass<A, P, S, E, F, G>(&mut self, first : F, second : G) -> Result<S, E>
+        where A : Clone + 'static,
+              E : JlnError,
+              F : FnOnce(&mut Parser<'a, T>) -> Result<P, E>,
+              G : FnOnce(&mut Parser<'a, T>) -> Result<S, E>,
+    {
+        let mut pass = self.fork();
+        pass.set_state(Annotations::<A>::new());
+        first(&mut pass)?;
+        let annotations = pass.annotations::<A>().cloned().unwrap_or_default();
+        let previous = self.state.take();
+        self.set_state(annotations);
+        let result = self.attempt(second);
+        self.state = previous;
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn label(input : &mut Parser<char>) -> Result<String, ()> {
+        Ok(input.take_while::<(), _>(|c| c.is_ascii_alphabetic())?.iter().collect())
+    }
+
+    fn line(input : &mut Parser<char>) -> Result<Option<String>, ()> {
+        let result = input.option(|input| {
+            let (name, span) = input.spanned(label)?;
+            input.expect::<()>(&':')?;
+            input.annotate(span, name.clone());
+            Ok(None)
+        })?;
+        let result = match result {
+            Some(result) => result,
+            None => {
+                input.tag_str::<()>("jmp ")?;
+                let target = label(input)?;
+                let known = input.annotations::<String>().is_some_and(|x| x.iter().any(|(_, name)| *name == target));
+                (!known).then_some(target)
+            },
+        };
+        input.expect::<()>(&'\n')?;
+        Ok(result)
+    }
+
+    #[test]
+    fn should_resolve_forward_references_in_second_pass() {
+        let input = "jmp end\nstart:\njmp start\njmp nowhere\nend:\n".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        buffer.set_state(7u8);
+
+        let unresolved = buffer.two_pass::<String, _, _, _, _, _>(|input| input.list(line), |input| input.list(line)).unwrap();
+
+        assert_eq!(unresolved.into_iter().flatten().collect::<Vec<_>>(), vec!["nowhere"]);
+        assert!(buffer.end());
+        assert_eq!(buffer.state::<u8>(), Some(&7));
+    }
+
+    #[test]
+    fn should_order_and_query_annotations() {
+        let mut annotations = Annotations::new();
+        annotations.insert(Span::new(4, 6), 'b');
+        annotations.insert(Span::new(0, 9), 'a');
+        annotations.insert(Span::new(4, 5), 'c');
+
+        assert_eq!(annotations.iter().map(|x| x.1).collect::<String>(), "abc");
+        assert_eq!(annotations.starting_at(4).map(|x| x.1).collect::<String>(), "bc");
+        assert_eq!(annotations.covering(5).map(|x| x.1).collect::<String>(), "ab");
+        assert_eq!(annotations.len(), 3);
+    }
+}
@@ -0,0 +1,78 @@
+
+use bumpalo::collections::Vec as BumpVec;
+use bumpalo::Bump;
+
+use crate::{JlnError, Parser};
+
+impl<'a, T> Parser<'a, T> {
+    pub fn alloc_in<'b, S, E : JlnError, F : FnOnce(&mut Parser<'a, T>) -> Result<S, E>>(&mut self, bump : &'b Bump, f : F) -> Result<&'b mut S, E> {
+        let value = f(self)?;
+        Ok(bump.alloc(value))
+    }
+
+    pub fn list_in<'b, S, E : JlnError, F : FnMut(&mut Parser<'a, T>) -> Result<S, E>>(&mut self, bump : &'b Bump, mut f : F) -> Result<&'b [S], E> {
+        let mut rets = BumpVec::new_in(bump);
+        loop {
+            match self.attempt(&mut f) {
+                Ok(v) => { rets.push(v); },
+                Err(e) if self.fatal(&e) => { return Err(e); },
+                Err(_) => { break; },
+            }
+        }
+        Ok(rets.into_bump_slice())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::TError;
+
+    enum Tree<'b> {
+        Leaf(u8),
+        Node(&'b [Tree<'b>]),
+    }
+
+    fn tree<'b>(input : &mut Parser<u8>, bump : &'b Bump) -> Result<Tree<'b>, TError> {
+        match *input.get()? {
+            b'(' => {
+                let children = input.list_in(bump, |input| tree(input, bump))?;
+                input.expect(&b')')?;
+                Ok(Tree::Node(children))
+            },
+            b')' => Err(TError(false)),
+            b => Ok(Tree::Leaf(b)),
+        }
+    }
+
+    fn leaves(tree : &Tree) -> Vec<u8> {
+        match tree {
+            Tree::Leaf(b) => vec![*b],
+            Tree::Node(children) => children.iter().flat_map(leaves).collect(),
+        }
+    }
+
+    #[test]
+    fn should_collect_lists_into_arena() {
+        let bump = Bump::new();
+        let input = b"(a(bc)d)".to_vec();
+        let mut buffer = Parser::new(&input);
+
+        let tree = tree(&mut buffer, &bump).map_err(|_| ()).unwrap();
+
+        assert_eq!(leaves(&tree), b"abcd".to_vec());
+        assert!(buffer.end());
+    }
+
+    #[test]
+    fn should_allocate_values_in_arena() {
+        let bump = Bump::new();
+        let input = vec![7u8];
+        let mut buffer = Parser::new(&input);
+
+        let value = buffer.alloc_in(&bump, |input| input.get::<TError>().copied()).map_err(|_| ()).unwrap();
+
+        assert_eq!(*value, 7);
+        assert!(buffer.alloc_in(&bump, |input| input.get::<TError>().copied()).is_err());
+    }
+}
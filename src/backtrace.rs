@@ -0,0 +1,93 @@
+
+use crate::{JlnError, Parser};
+
+impl<'a, T> Parser<'a, T> {
+    pub fn enable_backtrace(&mut self) {
+        if self.rules.is_none() {
+            self.rules = Some(vec![]);
+        }
+    }
+
+    pub fn disable_backtrace(&mut self) {
+        self.rules = None;
+    }
+
+    pub fn rule_stack(&self) -> &[(&'static str, usize)] {
+        self.rules.as_deref().unwrap_or_default()
+    }
+
+    pub(crate) fn push_rule(&mut self, rule : &'static str, index : usize) {
+        if let Some(rules) = &mut self.rules {
+            rules.push((rule, index));
+        }
+    }
+
+    pub(crate) fn pop_rule(&mut self) {
+        if let Some(rules) = &mut self.rules {
+            rules.pop();
+        }
+    }
+
+    pub(crate) fn capture_backtrace<E : JlnError>(&self, error : E) -> E {
+        match &self.rules {
+            Some(rules) => error.backtrace(rules),
+            None => error,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Diagnostic;
+
+    fn value(input : &mut Parser<char>) -> Result<char, Diagnostic> {
+        input.context("value", |input| input.or([
+            |input : &mut Parser<char>| input.context("digit", |input| input.satisfy(|c : &char| c.is_ascii_digit()).copied()),
+            |input : &mut Parser<char>| input.context("list", |input| {
+                input.expect::<Diagnostic>(&'[')?;
+                let item = value(input)?;
+                input.expect::<Diagnostic>(&']')?;
+                Ok(item)
+            }),
+        ]))
+    }
+
+    #[test]
+    fn should_capture_enclosing_rules_of_innermost_failure() {
+        let input = "[[x]]".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::builder(&input[..]).backtrace().build();
+
+        let error = buffer.context("document", value).unwrap_err();
+
+        assert_eq!(error.snapshot(), concat!(
+            "in document at 0:\n",
+            "  in value at 0:\n",
+            "    in list at 0:\n",
+            "      in value at 1:\n",
+            "        in list at 1:\n",
+            "          in value at 2:\n",
+            "            any of:\n",
+            "              in digit at 2:\n",
+            "                via document@0 > value@0 > list@0 > value@1 > list@1 > value@2 > digit@2:\n",
+            "                  at 2: Unexpected\n",
+            "              in list at 2:\n",
+            "                via document@0 > value@0 > list@0 > value@1 > list@1 > value@2 > list@2:\n",
+            "                  at 2: Unexpected\n",
+        ));
+        assert!(buffer.rule_stack().is_empty());
+    }
+
+    #[test]
+    fn should_leave_errors_untouched_when_disabled() {
+        let input = "[1".chars().collect::<Vec<_>>();
+
+        let error = Parser::new(&input).context("document", value).unwrap_err();
+
+        assert!(!error.snapshot().contains("via"));
+        let mut buffer = Parser::new(&input);
+        buffer.enable_backtrace();
+        buffer.get::<()>().unwrap();
+        assert_eq!(buffer.context("document", |input| Ok::<_, ()>(input.rule_stack().to_vec())), Ok(vec![("document", 1)]));
+    }
+}
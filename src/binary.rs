@@ -0,0 +1,124 @@
+
+use crate::{ErrorKind, JlnError, Parser};
+
+pub fn zigzag(value : u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+impl<'a> Parser<'a, u8> {
+    pub fn uleb128<E : JlnError>(&mut self) -> Result<u64, E> {
+        let start = self.index;
+        self.with_rollback(|input| {
+            let mut value = 0u64;
+            let mut shift = 0;
+            loop {
+                let byte = input.varint_byte(start)?;
+                let low = (byte & 0x7f) as u64;
+                if shift >= 64 || (shift == 63 && low > 1) {
+                    return Err(E::from_kind(ErrorKind::Overflow, start));
+                }
+                value |= low << shift;
+                if byte & 0x80 == 0 {
+                    return Ok(value);
+                }
+                shift += 7;
+            }
+        })
+    }
+
+    pub fn sleb128<E : JlnError>(&mut self) -> Result<i64, E> {
+        let start = self.index;
+        self.with_rollback(|input| {
+            let mut value = 0i128;
+            let mut shift = 0;
+            loop {
+                let byte = input.varint_byte(start)?;
+                if shift >= 70 {
+                    return Err(E::from_kind(ErrorKind::Overflow, start));
+                }
+                value |= ((byte & 0x7f) as i128) << shift;
+                shift += 7;
+                if byte & 0x80 == 0 {
+                    if byte & 0x40 != 0 {
+                        value |= -1i128 << shift;
+                    }
+                    return i64::try_from(value).map_err(|_| E::from_kind(ErrorKind::Overflow, start));
+                }
+            }
+        })
+    }
+
+    pub fn varint<E : JlnError>(&mut self) -> Result<u64, E> {
+        self.uleb128()
+    }
+
+    pub fn varint_zigzag<E : JlnError>(&mut self) -> Result<i64, E> {
+        self.uleb128().map(zigzag)
+    }
+
+    fn varint_byte<E : JlnError>(&mut self, start : usize) -> Result<u8, E> {
+        if self.end() {
+            return Err(E::from_kind(ErrorKind::Truncated, start));
+        }
+        self.get().copied()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct KindError(Option<ErrorKind>);
+
+    impl JlnError for KindError {
+        fn is_fatal(&self) -> bool { false }
+        fn eof() -> Self { KindError(None) }
+        fn aggregate(_errors : Vec<Self>) -> Self { KindError(None) }
+        fn from_kind(kind : ErrorKind, _index : usize) -> Self { KindError(Some(kind)) }
+    }
+
+    #[test]
+    fn should_decode_unsigned_leb128() {
+        let input = vec![0xe5, 0x8e, 0x26, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01];
+        let mut buffer = Parser::new(&input);
+
+        assert_eq!(buffer.uleb128::<()>(), Ok(624485));
+        assert_eq!(buffer.uleb128::<()>(), Ok(0));
+        assert_eq!(buffer.varint::<()>(), Ok(u64::MAX));
+        assert!(buffer.end());
+    }
+
+    #[test]
+    fn should_decode_signed_leb128() {
+        let input = vec![0xc0, 0xbb, 0x78, 0x7f, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x7f];
+        let mut buffer = Parser::new(&input);
+
+        assert_eq!(buffer.sleb128::<()>(), Ok(-123456));
+        assert_eq!(buffer.sleb128::<()>(), Ok(-1));
+        assert_eq!(buffer.sleb128::<()>(), Ok(i64::MIN));
+    }
+
+    #[test]
+    fn should_decode_zigzag_varints() {
+        let input = vec![0x00, 0x01, 0x02, 0x03, 0xac, 0x02];
+        let mut buffer = Parser::new(&input);
+
+        let values = buffer.list(|input| input.varint_zigzag::<()>()).unwrap();
+
+        assert_eq!(values, vec![0, -1, 1, -2, 150]);
+    }
+
+    #[test]
+    fn should_report_overflow_and_truncation() {
+        let input = vec![0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02];
+        let mut buffer = Parser::new(&input);
+        assert_eq!(buffer.uleb128::<KindError>(), Err(KindError(Some(ErrorKind::Overflow))));
+        assert_eq!(buffer.index(), 0);
+
+        let input = vec![0x80, 0x80];
+        let mut buffer = Parser::new(&input);
+        assert_eq!(buffer.sleb128::<KindError>(), Err(KindError(Some(ErrorKind::Truncated))));
+        assert_eq!(buffer.index(), 0);
+    }
+}
@@ -0,0 +1,91 @@
+
+use nom::error::{Error, ParseError};
+use nom::IResult;
+
+use crate::{ErrorKind, JlnError, Parser};
+
+impl<'a, T> Parser<'a, T> {
+    pub fn nom<O, E : JlnError, P : FnOnce(&[T]) -> IResult<&[T], O>>(&mut self, parser : P) -> Result<O, E> {
+        self.with_rollback(|input| {
+            input.consume_trivia();
+            let start = input.index;
+            let bound = input.bound();
+            let (consumed, value) = match parser(&input.input[start..bound]) {
+                Ok((rest, value)) => (bound - start - rest.len(), value),
+                Err(nom::Err::Error(e)) => { return Err(E::from_kind(ErrorKind::Unexpected, bound - e.input.len())); },
+                Err(nom::Err::Failure(e)) => { return Err(input.raise(ErrorKind::External { fatal: true }, bound - e.input.len())); },
+                Err(nom::Err::Incomplete(_)) => { return Err(E::from_kind(ErrorKind::Truncated, start)); },
+            };
+            input.no_trivia(|input| {
+                for _ in 0..consumed {
+                    input.get::<E>()?;
+                }
+                Ok(value)
+            })
+        })
+    }
+}
+
+pub fn nom_rule<T, S, E : JlnError, F : FnMut(&mut Parser<'_, T>) -> Result<S, E>>(mut rule : F) -> impl FnMut(&[T]) -> IResult<&[T], S> {
+    move |input : &[T]| {
+        let mut parser = Parser::new(input);
+        match rule(&mut parser) {
+            Ok(value) => Ok((&input[parser.index()..], value)),
+            Err(e) if parser.fatal(&e) => Err(nom::Err::Failure(Error::from_error_kind(input, nom::error::ErrorKind::Verify))),
+            Err(_) => Err(nom::Err::Error(Error::from_error_kind(input, nom::error::ErrorKind::Verify))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use nom::bytes::complete::tag;
+    use nom::character::complete::digit1;
+    use nom::sequence::preceded;
+    use nom::Parser as _;
+
+    use super::*;
+
+    fn key(input : &mut Parser<u8>) -> Result<u8, ()> {
+        input.satisfy(|b : &u8| b.is_ascii_alphabetic()).copied()
+    }
+
+    #[test]
+    fn should_run_nom_parser_as_rule() {
+        let input = b"k=42;".to_vec();
+        let mut buffer = Parser::new(&input);
+
+        assert_eq!(key(&mut buffer), Ok(b'k'));
+        assert_eq!(buffer.nom::<_, (), _>(|i| preceded(tag(&b"="[..]), digit1).map(|x : &[u8]| x.len()).parse_complete(i)), Ok(2));
+        assert_eq!(buffer.index(), 4);
+        assert_eq!(buffer.expect::<()>(&b';'), Ok(&b';'));
+    }
+
+    #[test]
+    fn should_translate_nom_errors() {
+        #[derive(Debug, PartialEq)]
+        struct At(usize);
+
+        impl JlnError for At {
+            fn is_fatal(&self) -> bool { false }
+            fn eof() -> Self { At(usize::MAX) }
+            fn aggregate(_errors : Vec<Self>) -> Self { At(usize::MAX) }
+            fn from_kind(_kind : ErrorKind, index : usize) -> Self { At(index) }
+        }
+
+        let input = b"k=x".to_vec();
+        let mut buffer = Parser::new(&input);
+        buffer.get::<At>().unwrap();
+
+        assert_eq!(buffer.nom::<_, At, _>(|i| preceded(tag(&b"="[..]), digit1).map(|_| ()).parse_complete(i)), Err(At(2)));
+        assert_eq!(buffer.index(), 1);
+    }
+
+    #[test]
+    fn should_expose_rule_to_nom() {
+        let mut parser = (nom_rule(key), tag(&b"!"[..]));
+
+        assert_eq!(parser.parse(&b"a!?"[..]), Ok((&b"?"[..], (b'a', &b"!"[..]))));
+        assert!(parser.parse(&b"1!"[..]).is_err());
+    }
+}
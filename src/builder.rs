@@ -0,0 +1,134 @@
+
+use crate::{EventSink, Parser};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub max_depth : Option<usize>,
+    pub fuel : Option<u64>,
+    pub trivia : bool,
+    pub memo : bool,
+    pub events : bool,
+    pub state : bool,
+}
+
+pub struct ParserBuilder<'a, T> {
+    parser : Parser<'a, T>,
+}
+
+impl<'a, T> ParserBuilder<'a, T> {
+    pub fn new<I : Into<Parser<'a, T>>>(input : I) -> Self {
+        ParserBuilder { parser: input.into() }
+    }
+
+    pub fn max_depth(mut self, limit : usize) -> Self {
+        self.parser.set_max_depth(Some(limit));
+        self
+    }
+
+    pub fn fuel(mut self, fuel : u64) -> Self {
+        self.parser.set_fuel(Some(fuel));
+        self
+    }
+
+    pub fn trivia(mut self, trivia : fn(&T) -> bool) -> Self {
+        self.parser.set_trivia(Some(trivia));
+        self
+    }
+
+    pub fn memo(mut self) -> Self {
+        self.parser.enable_memo();
+        self
+    }
+
+    pub fn stats(mut self) -> Self {
+        self.parser.enable_stats();
+        self
+    }
+
+    pub fn coverage(mut self) -> Self {
+        self.parser.enable_coverage();
+        self
+    }
+
+    pub fn profile(mut self) -> Self {
+        self.parser.enable_profile();
+        self
+    }
+
+    pub fn warnings(mut self) -> Self {
+        self.parser.enable_warnings();
+        self
+    }
+
+    pub fn errors(mut self) -> Self {
+        self.parser.enable_errors();
+        self
+    }
+
+    pub fn backtrace(mut self) -> Self {
+        self.parser.enable_backtrace();
+        self
+    }
+
+    pub fn event_sink<S : EventSink + 'static>(mut self, sink : S) -> Self {
+        self.parser.set_event_sink(sink);
+        self
+    }
+
+    pub fn state<U : Clone + 'static>(mut self, state : U) -> Self {
+        self.parser.set_state(state);
+        self
+    }
+
+    pub fn build(self) -> Parser<'a, T> {
+        self.parser
+    }
+}
+
+impl<'a, T> Parser<'a, T> {
+    pub fn builder<I : Into<Parser<'a, T>>>(input : I) -> ParserBuilder<'a, T> {
+        ParserBuilder::new(input)
+    }
+
+    pub fn config(&self) -> Config {
+        Config {
+            max_depth: self.max_depth,
+            fuel: self.fuel(),
+            trivia: self.trivia.is_some(),
+            memo: self.memo.is_some(),
+            events: self.events.is_some(),
+            state: self.state.is_some(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_build_configured_parser() {
+        let buffer = Parser::builder("a b")
+            .max_depth(8)
+            .fuel(100)
+            .trivia(|c : &char| *c == ' ')
+            .memo()
+            .build();
+
+        assert_eq!(buffer.config(), Config { max_depth: Some(8), fuel: Some(100), trivia: true, memo: true, events: false, state: false });
+    }
+
+    #[test]
+    fn should_apply_builder_configuration() {
+        let input = vec![' ', 'a', ' ', 'b'];
+        let mut buffer = ParserBuilder::new(&input[..])
+            .trivia(|c : &char| *c == ' ')
+            .state(0u32)
+            .build();
+
+        assert_eq!(buffer.get::<()>(), Ok(&'a'));
+        assert_eq!(buffer.get::<()>(), Ok(&'b'));
+        assert_eq!(buffer.state::<u32>(), Some(&0));
+        assert_eq!(Parser::new(&input).config(), Config { max_depth: None, fuel: None, trivia: false, memo: false, events: false, state: false });
+    }
+}
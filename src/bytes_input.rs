@@ -0,0 +1,72 @@
+
+use std::rc::Rc;
+
+use bytes::{Bytes, BytesMut};
+
+use crate::{Input, JlnError, Parser};
+
+impl<'a> From<Bytes> for Parser<'a, u8> {
+    fn from(item : Bytes) -> Self {
+        Parser::from_input(Input::Owned(Rc::new(item)))
+    }
+}
+
+impl<'a> From<&Bytes> for Parser<'a, u8> {
+    fn from(item : &Bytes) -> Self {
+        item.clone().into()
+    }
+}
+
+impl<'a> From<BytesMut> for Parser<'a, u8> {
+    fn from(item : BytesMut) -> Self {
+        item.freeze().into()
+    }
+}
+
+impl<'a> Parser<'a, u8> {
+    pub fn recognize_bytes<S, E : JlnError, F : FnOnce(&mut Parser<'a, u8>) -> Result<S, E>>(&mut self, source : &Bytes, f : F) -> Result<(S, Bytes), E> {
+        let (value, span) = self.spanned(f)?;
+        let slice = &self.input[span.start..span.end];
+        let range = source.as_ptr_range();
+        if range.contains(&slice.as_ptr()) && slice.as_ptr_range().end <= range.end {
+            Ok((value, source.slice_ref(slice)))
+        }
+        else {
+            Ok((value, Bytes::copy_from_slice(slice)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn name(input : &mut Parser<u8>) -> Result<usize, ()> {
+        input.take_while::<(), _>(|b| b.is_ascii_alphabetic()).map(|x| x.len())
+    }
+
+    #[test]
+    fn should_slice_source_without_copying() {
+        let source = Bytes::from_static(b"key=value");
+        let mut buffer = Parser::from(&source);
+
+        let (len, key) = buffer.recognize_bytes(&source, name).unwrap();
+        buffer.expect::<()>(&b'=').unwrap();
+        let (_, value) = buffer.recognize_bytes(&source, name).unwrap();
+
+        assert_eq!((len, &key[..], &value[..]), (3, &b"key"[..], &b"value"[..]));
+        assert_eq!(value.as_ptr(), source[4..].as_ptr());
+        assert!(buffer.end());
+    }
+
+    #[test]
+    fn should_accept_mutable_buffers() {
+        let mut frame = BytesMut::from(&b"abc1"[..]);
+        frame.extend_from_slice(b"2");
+        let mut buffer = Parser::from(frame);
+
+        assert_eq!(name(&mut buffer), Ok(3));
+        let (_, digits) = buffer.recognize_bytes(&Bytes::from_static(b"12"), |input| input.take_while::<(), _>(|b| b.is_ascii_digit()).map(|_| ())).unwrap();
+        assert_eq!(&digits[..], b"12");
+    }
+}
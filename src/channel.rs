@@ -0,0 +1,129 @@
+
+use std::sync::mpsc::{Receiver, TryRecvError};
+
+use crate::{JlnError, Parser, StreamBuffer};
+
+pub struct ChannelInput<T> {
+    receiver : Receiver<T>,
+    stream : StreamBuffer<T>,
+    closed : bool,
+}
+
+impl<T> ChannelInput<T> {
+    pub fn new(receiver : Receiver<T>) -> Self {
+        ChannelInput { receiver, stream: StreamBuffer::new(), closed: false }
+    }
+
+    pub fn position(&self) -> usize {
+        self.stream.position()
+    }
+
+    pub fn closed(&self) -> bool {
+        self.closed
+    }
+
+    pub fn done(&self) -> bool {
+        self.closed && self.stream.buffered() == 0
+    }
+
+    pub fn next<S, E : JlnError, F : FnMut(&mut Parser<'_, T>) -> Result<S, E>>(&mut self, mut f : F) -> Result<Option<S>, E> {
+        loop {
+            if let Some(value) = self.step(&mut f)? {
+                return Ok(Some(value));
+            }
+            if self.closed {
+                return Ok(None);
+            }
+            match self.receiver.recv() {
+                Ok(token) => { self.stream.feed([token]); },
+                Err(_) => { self.closed = true; },
+            }
+            self.drain();
+        }
+    }
+
+    pub fn try_next<S, E : JlnError, F : FnMut(&mut Parser<'_, T>) -> Result<S, E>>(&mut self, mut f : F) -> Result<Option<S>, E> {
+        self.drain();
+        self.step(&mut f)
+    }
+
+    fn step<S, E : JlnError, F : FnMut(&mut Parser<'_, T>) -> Result<S, E>>(&mut self, f : F) -> Result<Option<S>, E> {
+        let result = if self.closed {
+            self.stream.finish(f)
+        }
+        else {
+            self.stream.next(f)
+        };
+        self.stream.commit();
+        result
+    }
+
+    fn drain(&mut self) {
+        loop {
+            match self.receiver.try_recv() {
+                Ok(token) => { self.stream.feed([token]); },
+                Err(TryRecvError::Empty) => { return; },
+                Err(TryRecvError::Disconnected) => {
+                    self.closed = true;
+                    return;
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::mpsc;
+    use std::thread;
+
+    use super::*;
+    use crate::Diagnostic;
+
+    fn word(input : &mut Parser<char>) -> Result<String, Diagnostic> {
+        let word = input.take_while::<Diagnostic, _>(|c| *c != ' ')?.iter().collect::<String>();
+        input.peek::<Diagnostic>()?;
+        input.get::<Diagnostic>()?;
+        Ok(word)
+    }
+
+    fn last(input : &mut Parser<char>) -> Result<String, Diagnostic> {
+        Ok(input.take_while::<Diagnostic, _>(|c| *c != ' ')?.iter().collect())
+    }
+
+    #[test]
+    fn should_parse_tokens_from_producer_thread() {
+        let (sender, receiver) = mpsc::channel();
+        let producer = thread::spawn(move || {
+            for c in "one two three".chars() {
+                sender.send(c).unwrap();
+            }
+        });
+        let mut input = ChannelInput::new(receiver);
+
+        let mut words = vec![];
+        while let Some(word) = input.next(|input| input.or([word, last])).unwrap() {
+            words.push(word);
+        }
+
+        producer.join().unwrap();
+        assert_eq!(words, vec!["one", "two", "three"]);
+        assert!(input.done());
+        assert_eq!(input.position(), 13);
+    }
+
+    #[test]
+    fn should_report_need_for_more_input_without_blocking() {
+        let (sender, receiver) = mpsc::channel();
+        let mut input = ChannelInput::new(receiver);
+        "ab".chars().for_each(|c| sender.send(c).unwrap());
+
+        assert_eq!(input.try_next(word), Ok(None));
+        assert!(!input.closed());
+        sender.send(' ').unwrap();
+        assert_eq!(input.try_next(word), Ok(Some(String::from("ab"))));
+        drop(sender);
+        assert_eq!(input.try_next(word), Ok(None));
+        assert!(input.done());
+    }
+}
@@ -0,0 +1,170 @@
+
+use std::fmt::{self, Debug, Display, Formatter};
+use std::io;
+
+use bytes::{Buf, BytesMut};
+use tokio_util::codec::Decoder;
+
+use crate::{JlnError, Parser, ParserBuilder};
+
+#[derive(Debug)]
+pub enum CodecError<E> {
+    Io(io::Error),
+    Parse(E),
+}
+
+impl<E> From<io::Error> for CodecError<E> {
+    fn from(e : io::Error) -> Self {
+        CodecError::Io(e)
+    }
+}
+
+impl<E : Debug> Display for CodecError<E> {
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CodecError::Io(e) => write!(f, "io error: {}", e),
+            CodecError::Parse(e) => write!(f, "parse error: {:?}", e),
+        }
+    }
+}
+
+impl<E : Debug> std::error::Error for CodecError<E> { }
+
+type Configure = Box<dyn for<'b> Fn(ParserBuilder<'b, u8>) -> ParserBuilder<'b, u8>>;
+
+pub struct FrameCodec<F> {
+    grammar : F,
+    configure : Configure,
+}
+
+impl<F> FrameCodec<F> {
+    pub fn new(grammar : F) -> Self {
+        FrameCodec::with_config(grammar, |builder| builder)
+    }
+
+    pub fn with_config<C : for<'b> Fn(ParserBuilder<'b, u8>) -> ParserBuilder<'b, u8> + 'static>(grammar : F, configure : C) -> Self {
+        FrameCodec { grammar, configure: Box::new(configure) }
+    }
+}
+
+impl<S, E : JlnError, F : FnMut(&mut Parser<'_, u8>) -> Result<S, E>> FrameCodec<F> {
+    fn frame(&mut self, src : &mut BytesMut, streaming : bool) -> Result<Option<S>, CodecError<E>> {
+        let mut parser = (self.configure)(Parser::builder(&src[..])).build();
+        parser.set_streaming(streaming);
+        let (value, consumed) = match (self.grammar)(&mut parser) {
+            Ok(_) if parser.index() == 0 => { return Err(CodecError::Parse(E::custom(Some("empty frame"), 0))); },
+            Ok(value) => (value, parser.index()),
+            Err(_) if parser.incomplete() => { return Ok(None); },
+            Err(e) => { return Err(CodecError::Parse(e)); },
+        };
+        src.advance(consumed);
+        Ok(Some(value))
+    }
+}
+
+impl<S, E : JlnError, F : FnMut(&mut Parser<'_, u8>) -> Result<S, E>> Decoder for FrameCodec<F> {
+    type Item = S;
+    type Error = CodecError<E>;
+
+    fn decode(&mut self, src : &mut BytesMut) -> Result<Option<S>, CodecError<E>> {
+        self.frame(src, true)
+    }
+
+    fn decode_eof(&mut self, src : &mut BytesMut) -> Result<Option<S>, CodecError<E>> {
+        if src.is_empty() {
+            return Ok(None);
+        }
+        self.frame(src, false)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::TError;
+
+    fn line(input : &mut Parser<u8>) -> Result<Vec<u8>, TError> {
+        let line = input.list(|input| input.satisfy(|b : &u8| *b != b'\n').copied())?;
+        input.expect(&b'\n')?;
+        Ok(line)
+    }
+
+    #[test]
+    fn should_decode_frames_as_bytes_arrive() {
+        let mut codec = FrameCodec::new(line);
+        let mut buffer = BytesMut::from(&b"ab"[..]);
+
+        assert_eq!(codec.decode(&mut buffer).unwrap(), None);
+        buffer.extend_from_slice(b"c\nd\nef");
+        assert_eq!(codec.decode(&mut buffer).unwrap(), Some(b"abc".to_vec()));
+        assert_eq!(codec.decode(&mut buffer).unwrap(), Some(b"d".to_vec()));
+        assert_eq!(codec.decode(&mut buffer).unwrap(), None);
+        assert_eq!(&buffer[..], b"ef");
+    }
+
+    #[test]
+    fn should_report_real_errors() {
+        let mut codec = FrameCodec::new(|input : &mut Parser<u8>| -> Result<u8, TError> {
+            match *input.get()? {
+                b'!' => Ok(b'!'),
+                _ => Err(TError(false)),
+            }
+        });
+        let mut buffer = BytesMut::from(&b"?"[..]);
+
+        assert!(matches!(codec.decode(&mut buffer), Err(CodecError::Parse(_))));
+    }
+
+    #[test]
+    fn should_fail_on_truncated_frame_at_eof() {
+        let mut codec = FrameCodec::new(line);
+        let mut buffer = BytesMut::from(&b"abc"[..]);
+
+        assert!(matches!(codec.decode_eof(&mut buffer), Err(CodecError::Parse(_))));
+        assert_eq!(codec.decode_eof(&mut BytesMut::new()).unwrap(), None);
+    }
+
+    #[test]
+    fn should_reject_frames_that_consume_nothing() {
+        let mut codec = FrameCodec::new(|input : &mut Parser<u8>| input.list(|input| input.expect::<TError>(&b'x').copied()));
+        let mut buffer = BytesMut::from(&b"y"[..]);
+
+        assert!(matches!(codec.decode(&mut buffer), Err(CodecError::Parse(_))));
+        assert_eq!(&buffer[..], b"y");
+    }
+
+    #[test]
+    fn should_wait_for_bytes_with_error_types_that_are_never_fatal() {
+        let pair = |input : &mut Parser<u8>| -> Result<(u8, u8), ()> { Ok((*input.get()?, *input.get()?)) };
+        let mut codec = FrameCodec::new(pair);
+        let mut buffer = BytesMut::from(&b"x"[..]);
+
+        assert_eq!(codec.decode(&mut buffer).unwrap(), None);
+        buffer.extend_from_slice(b"y");
+        assert_eq!(codec.decode(&mut buffer).unwrap(), Some((b'x', b'y')));
+        assert!(matches!(codec.decode_eof(&mut BytesMut::from(&b"z"[..])), Err(CodecError::Parse(()))));
+    }
+
+    #[test]
+    fn should_wait_for_bytes_when_grammar_maps_incomplete_error() {
+        let mut codec = FrameCodec::new(|input : &mut Parser<u8>| -> Result<u8, TError> {
+            match input.with_rollback(|input| input.tag::<TError>(b"ab").map(|_| b'!')) {
+                Ok(value) => Ok(value),
+                Err(_) => Err(TError(false)),
+            }
+        });
+        let mut buffer = BytesMut::from(&b"a"[..]);
+
+        assert_eq!(codec.decode(&mut buffer).unwrap(), None);
+    }
+
+    #[test]
+    fn should_keep_builder_settings_for_each_frame() {
+        let pair = |input : &mut Parser<u8>| -> Result<(u8, u8), TError> { Ok((*input.get()?, *input.get()?)) };
+        let mut codec = FrameCodec::with_config(pair, |builder| builder.trivia(|b : &u8| *b == b' '));
+        let mut buffer = BytesMut::from(&b" a b c d"[..]);
+
+        assert_eq!(codec.decode(&mut buffer).unwrap(), Some((b'a', b'b')));
+        assert_eq!(codec.decode(&mut buffer).unwrap(), Some((b'c', b'd')));
+    }
+}
@@ -0,0 +1,149 @@
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+use std::panic::Location;
+use std::rc::Rc;
+
+use crate::{Graph, Parser};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Coverage {
+    pub rules : HashMap<&'static str, usize>,
+    pub alternatives : HashMap<&'static Location<'static>, Vec<usize>>,
+}
+
+pub(crate) type CoverageLog = Rc<RefCell<Coverage>>;
+
+impl Coverage {
+    pub fn merge(&mut self, other : &Coverage) {
+        for (rule, count) in &other.rules {
+            *self.rules.entry(rule).or_insert(0) += count;
+        }
+        for (location, counts) in &other.alternatives {
+            let totals = self.alternatives.entry(location).or_insert_with(|| vec![0; counts.len()]);
+            for (total, count) in totals.iter_mut().zip(counts) {
+                *total += count;
+            }
+        }
+    }
+
+    pub fn unreached_alternatives(&self) -> Vec<(&'static Location<'static>, usize)> {
+        let mut unreached = self.alternatives.iter()
+            .flat_map(|(location, counts)| counts.iter().enumerate().filter(|(_, x)| **x == 0).map(|(i, _)| (*location, i)))
+            .collect::<Vec<_>>();
+        unreached.sort_by_key(|(location, i)| (location.file(), location.line(), location.column(), *i));
+        unreached
+    }
+
+    pub fn unreached_rules(&self, graph : &Graph) -> Vec<&'static str> {
+        graph.rules.iter().map(|x| x.name).filter(|x| !self.rules.contains_key(x)).collect()
+    }
+}
+
+impl Display for Coverage {
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result {
+        let mut rules = self.rules.iter().collect::<Vec<_>>();
+        rules.sort();
+        writeln!(f, "rules:")?;
+        for (rule, count) in rules {
+            writeln!(f, "  {}: {}", rule, count)?;
+        }
+        let mut alternatives = self.alternatives.iter().collect::<Vec<_>>();
+        alternatives.sort_by_key(|(location, _)| (location.file(), location.line(), location.column()));
+        writeln!(f, "alternatives:")?;
+        for (location, counts) in alternatives {
+            writeln!(f, "  {}: {:?}", location, counts)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, T> Parser<'a, T> {
+    pub fn enable_coverage(&mut self) {
+        if self.coverage.is_none() {
+            self.coverage = Some(Rc::new(RefCell::new(Coverage::default())));
+        }
+    }
+
+    pub fn disable_coverage(&mut self) {
+        self.coverage = None;
+    }
+
+    pub fn coverage(&self) -> Option<Coverage> {
+        self.coverage.as_ref().map(|x| x.borrow().clone())
+    }
+
+    pub(crate) fn cover_rule(&self, rule : &'static str) {
+        if let Some(coverage) = &self.coverage {
+            *coverage.borrow_mut().rules.entry(rule).or_insert(0) += 1;
+        }
+    }
+
+    pub(crate) fn cover_alternative(&self, location : &'static Location<'static>, branch : usize, branches : usize) {
+        if let Some(coverage) = &self.coverage {
+            let mut coverage = coverage.borrow_mut();
+            let counts = coverage.alternatives.entry(location).or_insert_with(|| vec![0; branches]);
+            if let Some(count) = counts.get_mut(branch) {
+                *count += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::TError;
+    use crate::{GraphNode, GraphRule};
+
+    fn letter(input : &mut Parser<char>) -> Result<char, TError> {
+        input.context("letter", |input| input.satisfy(|c : &char| c.is_alphabetic()).copied())
+    }
+
+    fn digit(input : &mut Parser<char>) -> Result<char, TError> {
+        input.context("digit", |input| input.satisfy(|c : &char| c.is_ascii_digit()).copied())
+    }
+
+    fn space(input : &mut Parser<char>) -> Result<char, TError> {
+        input.context("space", |input| input.expect(&' ').copied())
+    }
+
+    fn item(input : &mut Parser<char>) -> Result<char, TError> {
+        input.or([letter, digit, space])
+    }
+
+    #[test]
+    fn should_record_exercised_branches_and_rules() {
+        let input = "ab1".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        buffer.enable_coverage();
+
+        buffer.list(item).map_err(|_| ()).unwrap();
+
+        let coverage = buffer.coverage().unwrap();
+        assert_eq!(coverage.rules.get("letter"), Some(&2));
+        assert_eq!(coverage.rules.get("digit"), Some(&1));
+        assert_eq!(coverage.rules.get("space"), None);
+        let unreached = coverage.unreached_alternatives();
+        assert_eq!(unreached.len(), 1);
+        assert_eq!(unreached[0].1, 2);
+    }
+
+    #[test]
+    fn should_merge_runs_and_report_unreached_rules() {
+        let mut total = Coverage::default();
+        for text in ["a", " "] {
+            let input = text.chars().collect::<Vec<_>>();
+            let mut buffer = Parser::new(&input);
+            buffer.enable_coverage();
+            item(&mut buffer).map_err(|_| ()).unwrap();
+            total.merge(&buffer.coverage().unwrap());
+        }
+
+        let graph = Graph { rules: ["letter", "digit", "space"].into_iter().map(|name| GraphRule { name, output: "char", body: GraphNode::Any }).collect() };
+        assert_eq!(total.unreached_rules(&graph), vec!["digit"]);
+        assert_eq!(total.unreached_alternatives().iter().map(|x| x.1).collect::<Vec<_>>(), vec![1]);
+        assert!(total.to_string().starts_with("rules:\n  letter: 1\n  space: 1\nalternatives:\n"));
+    }
+}
@@ -0,0 +1,142 @@
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::{Event, Parser};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CstElement<T> {
+    Node(CstNode<T>),
+    Token(T),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CstNode<T> {
+    pub kind : &'static str,
+    pub children : Vec<CstElement<T>>,
+}
+
+impl<T> CstNode<T> {
+    pub fn tokens(&self) -> Vec<&T> {
+        let mut tokens = vec![];
+        self.collect_tokens(&mut tokens);
+        tokens
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = &CstNode<T>> {
+        self.children.iter().filter_map(|x| match x {
+            CstElement::Node(node) => Some(node),
+            CstElement::Token(_) => None,
+        })
+    }
+
+    fn collect_tokens<'t>(&'t self, tokens : &mut Vec<&'t T>) {
+        for child in &self.children {
+            match child {
+                CstElement::Node(node) => node.collect_tokens(tokens),
+                CstElement::Token(token) => tokens.push(token),
+            }
+        }
+    }
+}
+
+pub struct CstBuilder {
+    events : Rc<RefCell<Vec<Event>>>,
+}
+
+impl<'a, T : Clone> Parser<'a, T> {
+    pub fn capture_cst(&mut self) -> CstBuilder {
+        let events = Rc::new(RefCell::new(vec![]));
+        let sink = Rc::clone(&events);
+        self.set_event_sink(move |event| sink.borrow_mut().push(event));
+        CstBuilder { events }
+    }
+
+    pub fn finish_cst(&mut self, builder : CstBuilder, kind : &'static str) -> CstNode<T> {
+        self.clear_event_sink();
+        let mut stack = vec![CstNode { kind, children: vec![] }];
+        for event in builder.events.take() {
+            match event {
+                Event::Start { kind, .. } => { stack.push(CstNode { kind, children: vec![] }); },
+                Event::Token { index } => {
+                    stack.last_mut().unwrap().children.push(CstElement::Token(self.input[index].clone()));
+                },
+                Event::End { .. } if stack.len() > 1 => {
+                    let node = stack.pop().unwrap();
+                    stack.last_mut().unwrap().children.push(CstElement::Node(node));
+                },
+                Event::End { .. } | Event::Error { .. } => { },
+            }
+        }
+        while stack.len() > 1 {
+            let node = stack.pop().unwrap();
+            stack.last_mut().unwrap().children.push(CstElement::Node(node));
+        }
+        let mut root = stack.pop().unwrap();
+        root.children.extend(self.input[self.index..].iter().cloned().map(CstElement::Token));
+        root
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn spaces(input : &mut Parser<char>) -> Result<(), ()> {
+        input.list(|input| input.expect(&' ').map(|_| ()))?;
+        Ok(())
+    }
+
+    fn name(input : &mut Parser<char>) -> Result<char, ()> {
+        input.node("name", |input| {
+            spaces(input)?;
+            match *input.get()? {
+                c if c.is_alphabetic() => Ok(c),
+                _ => Err(()),
+            }
+        })
+    }
+
+    fn assign(input : &mut Parser<char>) -> Result<(char, char), ()> {
+        input.node("assign", |input| {
+            let a = name(input)?;
+            spaces(input)?;
+            input.expect(&'=')?;
+            let b = name(input)?;
+            Ok((a, b))
+        })
+    }
+
+    #[test]
+    fn should_capture_lossless_tree() {
+        let text = " a  =  b ";
+        let input = text.chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        let builder = buffer.capture_cst();
+
+        assert_eq!(assign(&mut buffer), Ok(('a', 'b')));
+        let tree = buffer.finish_cst(builder, "root");
+
+        assert_eq!(tree.tokens().into_iter().collect::<String>(), text);
+        let assign = tree.nodes().next().unwrap();
+        assert_eq!(assign.kind, "assign");
+        assert_eq!(assign.nodes().map(|x| x.kind).collect::<Vec<_>>(), vec!["name", "name"]);
+        assert_eq!(assign.nodes().last().unwrap().tokens(), vec![&' ', &' ', &'b']);
+    }
+
+    #[test]
+    fn should_exclude_backtracked_nodes() {
+        let input = "ab".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        let builder = buffer.capture_cst();
+
+        assert!(buffer.option(assign).unwrap().is_none());
+        name(&mut buffer).unwrap();
+        let tree = buffer.finish_cst(builder, "root");
+
+        assert_eq!(tree, CstNode { kind: "root", children: vec![
+            CstElement::Node(CstNode { kind: "name", children: vec![CstElement::Token('a')] }),
+            CstElement::Token('b'),
+        ]});
+    }
+}
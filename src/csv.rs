@@ -0,0 +1,168 @@
+
+use crate::{ErrorKind, JlnError, Lexemes, Parser, TextToken};
+
+#[derive(Debug, Clone)]
+pub struct CsvStyle {
+    delimiter : char,
+    quote : Option<char>,
+}
+
+impl Default for CsvStyle {
+    fn default() -> Self {
+        CsvStyle::new(',')
+    }
+}
+
+impl CsvStyle {
+    pub fn new(delimiter : char) -> Self {
+        CsvStyle { delimiter, quote: Some('"') }
+    }
+
+    pub fn tsv() -> Self {
+        CsvStyle::new('\t')
+    }
+
+    pub fn set_delimiter(&mut self, delimiter : char) {
+        self.delimiter = delimiter;
+    }
+
+    pub fn set_quote(&mut self, quote : Option<char>) {
+        self.quote = quote;
+    }
+}
+
+impl<'a, T : TextToken> Parser<'a, T> {
+    pub fn csv_record<E : JlnError>(&mut self, style : &CsvStyle) -> Result<Vec<String>, E> {
+        self.with_rollback(|input| input.no_trivia(|input| {
+            let mut fields = vec![input.csv_field(style)?];
+            loop {
+                match input.csv_peek() {
+                    None => { return Ok(fields); },
+                    Some('\n') => {
+                        input.get::<E>()?;
+                        return Ok(fields);
+                    },
+                    Some('\r') if input.csv_crlf() => {
+                        input.get::<E>()?;
+                        input.get::<E>()?;
+                        return Ok(fields);
+                    },
+                    Some(c) if c == style.delimiter => {
+                        input.get::<E>()?;
+                        fields.push(input.csv_field(style)?);
+                    },
+                    Some(_) => { return Err(E::from_kind(ErrorKind::Unexpected, input.index)); },
+                }
+            }
+        }))
+    }
+
+    pub fn csv_records<'s, E : JlnError>(&mut self, style : &'s CsvStyle) -> Lexemes<'_, 'a, T, impl FnMut(&mut Parser<'a, T>) -> Result<Vec<String>, E> + 's> {
+        self.lexemes(move |input : &mut Parser<'a, T>| input.csv_record(style))
+    }
+
+    fn csv_field<E : JlnError>(&mut self, style : &CsvStyle) -> Result<String, E> {
+        let mut field = String::new();
+        match (self.csv_peek(), style.quote) {
+            (Some(c), Some(quote)) if c == quote => {
+                let start = self.index;
+                self.get::<E>()?;
+                loop {
+                    if self.end() {
+                        return Err(self.raise(ErrorKind::Unterminated, start));
+                    }
+                    let c = self.get::<E>()?.to_char();
+                    if c != quote {
+                        field.push(c);
+                    }
+                    else if self.csv_peek() == Some(quote) {
+                        self.get::<E>()?;
+                        field.push(quote);
+                    }
+                    else {
+                        return Ok(field);
+                    }
+                }
+            },
+            _ => {
+                while let Some(c) = self.csv_peek() {
+                    if c == style.delimiter || c == '\n' || (c == '\r' && self.csv_crlf()) {
+                        break;
+                    }
+                    field.push(c);
+                    self.get::<E>()?;
+                }
+                Ok(field)
+            },
+        }
+    }
+
+    fn csv_peek(&self) -> Option<char> {
+        if self.index < self.bound() {
+            Some(self.input[self.index].to_char())
+        }
+        else {
+            None
+        }
+    }
+
+    fn csv_crlf(&self) -> bool {
+        self.index + 1 < self.bound() && self.input[self.index + 1].to_char() == '\n'
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Diagnostic, Span};
+
+    fn records(text : &str, style : &CsvStyle) -> Vec<Result<Vec<String>, ()>> {
+        let input = text.chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        buffer.csv_records(style).map(|x| x.map(|(record, _)| record)).collect()
+    }
+
+    #[test]
+    fn should_parse_quoted_fields_and_line_endings() {
+        let rows = records("a,\"b,\"\"c\"\"\",\r\n\"x\ny\",2\n,\n", &CsvStyle::default());
+
+        assert_eq!(rows, vec![
+            Ok(vec!["a".into(), "b,\"c\"".into(), "".into()]),
+            Ok(vec!["x\ny".into(), "2".into()]),
+            Ok(vec!["".into(), "".into()]),
+        ]);
+    }
+
+    #[test]
+    fn should_use_configured_delimiter() {
+        let mut style = CsvStyle::tsv();
+        assert_eq!(records("a,b\t'c\td'", &style), vec![Ok(vec!["a,b".into(), "'c".into(), "d'".into()])]);
+
+        style.set_delimiter(';');
+        style.set_quote(Some('\''));
+        assert_eq!(records("a\r;'c;d'", &style), vec![Ok(vec!["a\r".into(), "c;d".into()])]);
+    }
+
+    #[test]
+    fn should_parse_byte_records_one_at_a_time() {
+        let input = b"id,name\r\n1,\"x\"\r\n".to_vec();
+        let mut buffer = Parser::new(&input);
+        let style = CsvStyle::default();
+
+        assert_eq!(buffer.csv_record::<()>(&style), Ok(vec!["id".into(), "name".into()]));
+        assert_eq!(buffer.index(), 9);
+        let rows = buffer.csv_records::<()>(&style).collect::<Vec<_>>();
+        assert_eq!(rows, vec![Ok((vec!["1".into(), "x".into()], Span::new(9, 16)))]);
+    }
+
+    #[test]
+    fn should_stop_stream_at_malformed_record() {
+        let text = "a,b\n\"c\"d\ne\n";
+        let rows = records(text, &CsvStyle::default());
+        assert_eq!(rows, vec![Ok(vec!["a".into(), "b".into()]), Err(())]);
+
+        let input = "\"open,a".chars().collect::<Vec<_>>();
+        let error = Parser::new(&input).csv_record::<Diagnostic>(&CsvStyle::default()).unwrap_err();
+        assert_eq!(error.snapshot(), "at 0: Unterminated\n");
+    }
+}
@@ -0,0 +1,324 @@
+
+use crate::{ErrorKind, JlnError, Parser, TextToken};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Date {
+    pub year : u16,
+    pub month : u8,
+    pub day : u8,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Time {
+    pub hour : u8,
+    pub minute : u8,
+    pub second : u8,
+    pub nanosecond : u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Offset {
+    Utc,
+    Minutes(i16),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DateTime {
+    pub date : Date,
+    pub time : Time,
+    pub offset : Offset,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct IsoDuration {
+    pub years : u32,
+    pub months : u32,
+    pub weeks : u32,
+    pub days : u32,
+    pub hours : u32,
+    pub minutes : u32,
+    pub seconds : u32,
+    pub nanoseconds : u32,
+}
+
+impl Offset {
+    pub fn minutes(&self) -> i16 {
+        match self {
+            Offset::Utc => 0,
+            Offset::Minutes(minutes) => *minutes,
+        }
+    }
+}
+
+fn days_in_month(year : u16, month : u8) -> u8 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl<'a, T : TextToken> Parser<'a, T> {
+    pub fn date<E : JlnError>(&mut self) -> Result<Date, E> {
+        self.datetime_rule(|input| input.date_parts())
+    }
+
+    pub fn time<E : JlnError>(&mut self) -> Result<Time, E> {
+        self.datetime_rule(|input| input.time_parts())
+    }
+
+    pub fn utc_offset<E : JlnError>(&mut self) -> Result<Offset, E> {
+        self.datetime_rule(|input| input.offset_parts())
+    }
+
+    pub fn datetime<E : JlnError>(&mut self) -> Result<DateTime, E> {
+        self.datetime_rule(|input| {
+            let date = input.date_parts()?;
+            input.satisfy::<E, _>(|t| matches!(t.to_char(), 'T' | 't' | ' '))?;
+            let time = input.time_parts()?;
+            let offset = input.offset_parts()?;
+            Ok(DateTime { date, time, offset })
+        })
+    }
+
+    pub fn iso_duration<E : JlnError>(&mut self) -> Result<IsoDuration, E> {
+        self.datetime_rule(|input| {
+            input.expect_no_case::<E>('P')?;
+            let mut duration = IsoDuration::default();
+            let mut components = 0;
+            let mut designators = "YMWD";
+            let mut time = false;
+            loop {
+                if !time && input.option(|input| input.expect_no_case::<E>('T'))?.is_some() {
+                    time = true;
+                    designators = "HMS";
+                    let start = input.index;
+                    if !input.peek::<E>().is_ok_and(|t| t.to_char().is_ascii_digit()) {
+                        return Err(E::custom(Some("duration"), start));
+                    }
+                }
+                let start = input.index;
+                let Some((value, fraction)) = input.option(|input| input.duration_number())? else { break; };
+                let at = input.index;
+                let designator = input.get::<E>().map(|t| t.to_char().to_ascii_uppercase()).map_err(|_| E::custom(Some("duration"), at))?;
+                let Some(position) = designators.find(designator) else { return Err(E::from_kind(ErrorKind::Unexpected, at)); };
+                if fraction.is_some() && !(time && designator == 'S') {
+                    return Err(E::custom(Some("duration"), start));
+                }
+                designators = &designators[position + 1..];
+                components += 1;
+                let field = match (time, designator) {
+                    (false, 'Y') => &mut duration.years,
+                    (false, 'M') => &mut duration.months,
+                    (false, 'W') => &mut duration.weeks,
+                    (false, _) => &mut duration.days,
+                    (true, 'H') => &mut duration.hours,
+                    (true, 'M') => &mut duration.minutes,
+                    (true, _) => &mut duration.seconds,
+                };
+                *field = value;
+                duration.nanoseconds = fraction.unwrap_or(duration.nanoseconds);
+            }
+            if components == 0 {
+                return Err(E::custom(Some("duration"), input.index));
+            }
+            Ok(duration)
+        })
+    }
+
+    fn datetime_rule<S, E : JlnError, F : FnOnce(&mut Parser<'a, T>) -> Result<S, E>>(&mut self, f : F) -> Result<S, E> {
+        self.with_rollback(|input| {
+            input.consume_trivia();
+            input.no_trivia(f)
+        })
+    }
+
+    fn date_parts<E : JlnError>(&mut self) -> Result<Date, E> {
+        let year = self.component(4, "year", 0, 9999)? as u16;
+        self.expect_no_case::<E>('-')?;
+        let month = self.component(2, "month", 1, 12)? as u8;
+        self.expect_no_case::<E>('-')?;
+        let day = self.component(2, "day", 1, days_in_month(year, month) as u32)? as u8;
+        Ok(Date { year, month, day })
+    }
+
+    fn time_parts<E : JlnError>(&mut self) -> Result<Time, E> {
+        let hour = self.component(2, "hour", 0, 23)? as u8;
+        self.expect_no_case::<E>(':')?;
+        let minute = self.component(2, "minute", 0, 59)? as u8;
+        self.expect_no_case::<E>(':')?;
+        let second = self.component(2, "second", 0, 60)? as u8;
+        let nanosecond = match self.option(|input| input.expect_no_case::<E>('.'))? {
+            Some(_) => self.fraction("second")?,
+            None => 0,
+        };
+        Ok(Time { hour, minute, second, nanosecond })
+    }
+
+    fn offset_parts<E : JlnError>(&mut self) -> Result<Offset, E> {
+        let start = self.index;
+        let sign = match self.get::<E>()?.to_char() {
+            'Z' | 'z' => { return Ok(Offset::Utc); },
+            '+' => 1,
+            '-' => -1,
+            _ => { return Err(E::from_kind(ErrorKind::Unexpected, start)); },
+        };
+        let hours = self.component(2, "offset hour", 0, 23)? as i16;
+        self.expect_no_case::<E>(':')?;
+        let minutes = self.component(2, "offset minute", 0, 59)? as i16;
+        Ok(Offset::Minutes(sign * (hours * 60 + minutes)))
+    }
+
+    fn component<E : JlnError>(&mut self, width : usize, label : &'static str, min : u32, max : u32) -> Result<u32, E> {
+        let start = self.index;
+        let mut value = 0;
+        for _ in 0..width {
+            match self.peek::<E>().ok().and_then(|t| t.to_char().to_digit(10)) {
+                Some(digit) => {
+                    value = value * 10 + digit;
+                    self.get::<E>()?;
+                },
+                None => { return Err(E::custom(Some(label), start)); },
+            }
+        }
+        if value < min || value > max {
+            return Err(E::custom(Some(label), start));
+        }
+        Ok(value)
+    }
+
+    fn fraction<E : JlnError>(&mut self, label : &'static str) -> Result<u32, E> {
+        let start = self.index;
+        let mut value = 0;
+        let mut digits = 0;
+        while let Some(digit) = self.peek::<E>().ok().and_then(|t| t.to_char().to_digit(10)) {
+            if digits < 9 {
+                value = value * 10 + digit;
+            }
+            digits += 1;
+            self.get::<E>()?;
+        }
+        if digits == 0 {
+            return Err(E::custom(Some(label), start));
+        }
+        Ok(value * 10u32.pow(9u32.saturating_sub(digits)))
+    }
+
+    fn duration_number<E : JlnError>(&mut self) -> Result<(u32, Option<u32>), E> {
+        let start = self.index;
+        let mut value = 0u32;
+        let mut digits = 0;
+        while let Some(digit) = self.peek::<E>().ok().and_then(|t| t.to_char().to_digit(10)) {
+            value = value.checked_mul(10).and_then(|x| x.checked_add(digit)).ok_or_else(|| E::from_kind(ErrorKind::Overflow, start))?;
+            digits += 1;
+            self.get::<E>()?;
+        }
+        if digits == 0 {
+            return Err(E::from_kind(ErrorKind::InvalidNumber, start));
+        }
+        match self.option(|input| input.satisfy::<E, _>(|t| matches!(t.to_char(), '.' | ',')).map(|_| ()))? {
+            Some(_) => Ok((value, Some(self.fraction("duration")?))),
+            None => Ok((value, None)),
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl DateTime {
+    pub fn to_chrono(&self) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+        let date = chrono::NaiveDate::from_ymd_opt(self.date.year as i32, self.date.month as u32, self.date.day as u32)?;
+        let time = match self.time.second {
+            60 => chrono::NaiveTime::from_hms_nano_opt(self.time.hour as u32, self.time.minute as u32, 59, self.time.nanosecond + 1_000_000_000)?,
+            second => chrono::NaiveTime::from_hms_nano_opt(self.time.hour as u32, self.time.minute as u32, second as u32, self.time.nanosecond)?,
+        };
+        let offset = chrono::FixedOffset::east_opt(self.offset.minutes() as i32 * 60)?;
+        date.and_time(time).and_local_timezone(offset).single()
+    }
+}
+
+#[cfg(feature = "time")]
+impl DateTime {
+    pub fn to_time(&self) -> Option<time::OffsetDateTime> {
+        let month = time::Month::try_from(self.date.month).ok()?;
+        let date = time::Date::from_calendar_date(self.date.year as i32, month, self.date.day).ok()?;
+        let clock = time::Time::from_hms_nano(self.time.hour, self.time.minute, self.time.second, self.time.nanosecond).ok()?;
+        let offset = time::UtcOffset::from_whole_seconds(self.offset.minutes() as i32 * 60).ok()?;
+        Some(time::PrimitiveDateTime::new(date, clock).assume_offset(offset))
+    }
+}
+
+impl<'a, T : TextToken> Parser<'a, T> {
+    #[cfg(feature = "chrono")]
+    pub fn chrono_datetime<E : JlnError>(&mut self) -> Result<chrono::DateTime<chrono::FixedOffset>, E> {
+        let start = self.next_significant();
+        self.with_rollback(|input| input.datetime::<E>()?.to_chrono().ok_or_else(|| E::custom(Some("datetime"), start)))
+    }
+
+    #[cfg(feature = "time")]
+    pub fn time_datetime<E : JlnError>(&mut self) -> Result<time::OffsetDateTime, E> {
+        let start = self.next_significant();
+        self.with_rollback(|input| input.datetime::<E>()?.to_time().ok_or_else(|| E::custom(Some("datetime"), start)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Diagnostic;
+
+    fn parse<S>(text : &str, f : fn(&mut Parser<char>) -> Result<S, Diagnostic>) -> Result<S, String> {
+        let input = text.chars().collect::<Vec<_>>();
+        f(&mut Parser::new(&input)).map_err(|e| e.snapshot())
+    }
+
+    #[test]
+    fn should_parse_rfc3339_datetimes() {
+        let value = parse("2024-02-29T23:59:60.25-05:30", |input| input.datetime()).unwrap();
+
+        assert_eq!(value.date, Date { year: 2024, month: 2, day: 29 });
+        assert_eq!(value.time, Time { hour: 23, minute: 59, second: 60, nanosecond: 250_000_000 });
+        assert_eq!(value.offset, Offset::Minutes(-330));
+        assert_eq!(parse("1999-12-31 00:00:00z", |input| input.datetime()).unwrap().offset, Offset::Utc);
+    }
+
+    #[test]
+    fn should_report_invalid_component_position() {
+        assert_eq!(parse("2023-02-29", |input| input.date()), Err("at 8: Custom { label: Some(\"day\") }\n".into()));
+        assert_eq!(parse("2023-13-01", |input| input.date()), Err("at 5: Custom { label: Some(\"month\") }\n".into()));
+        assert_eq!(parse("12:3x:00", |input| input.time()), Err("at 3: Custom { label: Some(\"minute\") }\n".into()));
+        assert_eq!(parse("2023-01-01T10:00:00+24:00", |input| input.datetime()), Err("at 20: Custom { label: Some(\"offset hour\") }\n".into()));
+        assert_eq!(parse("2023-01-01T10:00:00", |input| input.datetime()), Err("at 19: end of input\n".into()));
+    }
+
+    #[test]
+    fn should_parse_iso_durations() {
+        let value = parse("P1Y2M10DT2H30M1.5S", |input| input.iso_duration()).unwrap();
+
+        assert_eq!(value, IsoDuration { years: 1, months: 2, days: 10, hours: 2, minutes: 30, seconds: 1, nanoseconds: 500_000_000, ..IsoDuration::default() });
+        assert_eq!(parse("P3W", |input| input.iso_duration()).unwrap().weeks, 3);
+        assert_eq!(parse("PT36H", |input| input.iso_duration()).unwrap().hours, 36);
+        assert_eq!(parse("P1D2Y", |input| input.iso_duration()), Err("at 4: Unexpected\n".into()));
+        assert_eq!(parse("P1.5D", |input| input.iso_duration()), Err("at 1: Custom { label: Some(\"duration\") }\n".into()));
+        assert_eq!(parse("P1DT", |input| input.iso_duration()), Err("at 4: Custom { label: Some(\"duration\") }\n".into()));
+        assert!(parse("P", |input| input.iso_duration()).is_err());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn should_convert_to_chrono() {
+        let value = parse("2024-06-01T12:30:00+02:00", |input| input.chrono_datetime()).unwrap();
+
+        assert_eq!(value.to_rfc3339(), "2024-06-01T12:30:00+02:00");
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn should_convert_to_time() {
+        let value = parse("2024-06-01T12:30:00.5Z", |input| input.time_datetime()).unwrap();
+
+        assert_eq!(value.unix_timestamp(), 1_717_245_000);
+        assert_eq!(value.nanosecond(), 500_000_000);
+        assert_eq!(parse("2024-06-01T12:30:60Z", |input| input.time_datetime()), Err("at 0: Custom { label: Some(\"datetime\") }\n".into()));
+    }
+}
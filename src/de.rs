@@ -0,0 +1,216 @@
+
+use std::fmt::{self, Display, Formatter};
+
+use serde::de::value::{MapAccessDeserializer, MapDeserializer, SeqDeserializer};
+use serde::de::{self, DeserializeOwned, IntoDeserializer, Visitor};
+use serde::forward_to_deserialize_any;
+
+use crate::{JlnError, Parser};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    String(String),
+    Seq(Vec<Value>),
+    Map(Vec<(Value, Value)>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeError(String);
+
+impl DeError {
+    pub fn message(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for DeError {
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for DeError { }
+
+impl de::Error for DeError {
+    fn custom<M : Display>(msg : M) -> Self {
+        DeError(msg.to_string())
+    }
+}
+
+impl<'de> IntoDeserializer<'de, DeError> for Value {
+    type Deserializer = Value;
+
+    fn into_deserializer(self) -> Value {
+        self
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Value {
+    type Error = DeError;
+
+    fn deserialize_any<V : Visitor<'de>>(self, visitor : V) -> Result<V::Value, DeError> {
+        match self {
+            Value::Null => visitor.visit_unit(),
+            Value::Bool(x) => visitor.visit_bool(x),
+            Value::Int(x) => visitor.visit_i64(x),
+            Value::UInt(x) => visitor.visit_u64(x),
+            Value::Float(x) => visitor.visit_f64(x),
+            Value::String(x) => visitor.visit_string(x),
+            Value::Seq(x) => {
+                let mut seq = SeqDeserializer::new(x.into_iter());
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+                Ok(value)
+            },
+            Value::Map(x) => {
+                let mut map = MapDeserializer::new(x.into_iter());
+                let value = visitor.visit_map(&mut map)?;
+                map.end()?;
+                Ok(value)
+            },
+        }
+    }
+
+    fn deserialize_option<V : Visitor<'de>>(self, visitor : V) -> Result<V::Value, DeError> {
+        match self {
+            Value::Null => visitor.visit_none(),
+            x => visitor.visit_some(x),
+        }
+    }
+
+    fn deserialize_newtype_struct<V : Visitor<'de>>(self, _name : &'static str, visitor : V) -> Result<V::Value, DeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V : Visitor<'de>>(self, _name : &'static str, _variants : &'static [&'static str], visitor : V) -> Result<V::Value, DeError> {
+        match self {
+            Value::String(x) => visitor.visit_enum(x.into_deserializer()),
+            Value::Map(x) if x.len() == 1 => visitor.visit_enum(MapAccessDeserializer::new(MapDeserializer::new(x.into_iter()))),
+            _ => Err(de::Error::custom("expected a string or single-entry map for enum")),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+pub fn from_value<D : DeserializeOwned>(value : Value) -> Result<D, DeError> {
+    D::deserialize(value)
+}
+
+impl<'a, T> Parser<'a, T> {
+    pub fn deserialize<D, E, F>(&mut self, grammar : F) -> Result<D, E>
+        where D : DeserializeOwned,
+              E : JlnError + From<DeError>,
+              F : FnOnce(&mut Parser<'a, T>) -> Result<Value, E>,
+    {
+        let value = self.with_rollback(grammar)?;
+        Ok(from_value(value)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde::Deserialize;
+
+    use crate::{ErrorKind, ident_continue, ident_start};
+
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    enum KvError {
+        Syntax,
+        Data(String),
+    }
+
+    impl JlnError for KvError {
+        fn is_fatal(&self) -> bool { false }
+        fn eof() -> Self { KvError::Syntax }
+        fn aggregate(_errors : Vec<Self>) -> Self { KvError::Syntax }
+        fn from_kind(_kind : ErrorKind, _index : usize) -> Self { KvError::Syntax }
+    }
+
+    impl From<DeError> for KvError {
+        fn from(e : DeError) -> Self {
+            KvError::Data(e.message().to_string())
+        }
+    }
+
+    fn scalar(input : &mut Parser<char>) -> Result<Value, KvError> {
+        if let Some(n) = input.option(|input| input.integer::<i64, KvError>())? {
+            return Ok(Value::Int(n));
+        }
+        match input.identifier::<KvError>(ident_start, ident_continue)?.as_str() {
+            "true" => Ok(Value::Bool(true)),
+            "false" => Ok(Value::Bool(false)),
+            "none" => Ok(Value::Null),
+            word => Ok(Value::String(word.to_string())),
+        }
+    }
+
+    fn entries(input : &mut Parser<char>) -> Result<Value, KvError> {
+        let pairs = input.list(|input| {
+            let key = input.identifier::<KvError>(ident_start, ident_continue)?;
+            input.expect::<KvError>(&'=')?;
+            let value = scalar(input)?;
+            input.option(|input| input.expect::<KvError>(&';').map(|_| ()))?;
+            Ok::<_, KvError>((Value::String(key), value))
+        })?;
+        Ok(Value::Map(pairs))
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    enum Mode {
+        Fast,
+        Safe,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Config {
+        name : String,
+        port : u16,
+        verbose : bool,
+        mode : Mode,
+        limit : Option<u32>,
+    }
+
+    #[test]
+    fn should_deserialize_struct_from_grammar() {
+        let input = "name=server;port=8080;verbose=true;mode=Safe;limit=none".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        let config = buffer.deserialize::<Config, _, _>(entries);
+
+        assert_eq!(config, Ok(Config { name: "server".into(), port: 8080, verbose: true, mode: Mode::Safe, limit: None }));
+        assert!(buffer.end());
+    }
+
+    #[test]
+    fn should_report_data_errors() {
+        let input = "name=x;port=70000;verbose=false;mode=Fast".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        let result = buffer.deserialize::<Config, _, _>(entries);
+
+        assert!(matches!(result, Err(KvError::Data(_))));
+    }
+
+    #[test]
+    fn should_deserialize_sequences_and_enum_maps() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        enum Shape {
+            Circle(u32),
+        }
+
+        let value = Value::Seq(vec![Value::Map(vec![(Value::String("Circle".into()), Value::UInt(3))])]);
+
+        assert_eq!(from_value::<Vec<Shape>>(value), Ok(vec![Shape::Circle(3)]));
+    }
+}
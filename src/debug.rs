@@ -0,0 +1,87 @@
+
+use std::fmt::{self, Debug, Formatter, Write};
+
+use crate::Parser;
+
+const WINDOW : usize = 4;
+
+impl<'a, T> Parser<'a, T> {
+    pub fn context_window(&self, n : usize) -> (&[T], &[T]) {
+        let index = self.index.min(self.input.len());
+        let start = index.saturating_sub(n);
+        let end = index.saturating_add(n).min(self.input.len());
+        (&self.input[start..index], &self.input[index..end])
+    }
+}
+
+impl<'a, T : Debug> Debug for Parser<'a, T> {
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result {
+        let (before, after) = self.context_window(WINDOW);
+        let mut window = String::new();
+        if before.len() < self.index {
+            window.push_str("..");
+        }
+        for (i, token) in before.iter().chain(after).enumerate() {
+            if !window.is_empty() {
+                window.push(' ');
+            }
+            if i == before.len() {
+                window.push_str(">>");
+            }
+            write!(window, "{:?}", token)?;
+        }
+        if after.is_empty() {
+            if !window.is_empty() {
+                window.push(' ');
+            }
+            window.push_str(">><end>");
+        }
+        else if self.index + after.len() < self.input.len() {
+            window.push_str(" ..");
+        }
+        f.debug_struct("Parser")
+            .field("index", &self.index)
+            .field("len", &self.input.len())
+            .field("window", &format_args!("[{}]", window))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_show_window_around_cursor() {
+        let input = "abcdefghij".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        for _ in 0..5 {
+            buffer.get::<()>().unwrap();
+        }
+
+        assert_eq!(format!("{:?}", buffer), "Parser { index: 5, len: 10, window: [.. 'b' 'c' 'd' 'e' >>'f' 'g' 'h' 'i' ..] }");
+    }
+
+    #[test]
+    fn should_clamp_context_window() {
+        let input = vec![1, 2, 3, 4, 5];
+        let mut buffer = Parser::new(&input);
+
+        assert_eq!(buffer.context_window(2), (&[][..], &[1, 2][..]));
+        buffer.get::<()>().unwrap();
+        buffer.get::<()>().unwrap();
+        buffer.get::<()>().unwrap();
+        assert_eq!(buffer.context_window(2), (&[2, 3][..], &[4, 5][..]));
+        assert_eq!(buffer.context_window(9), (&[1, 2, 3][..], &[4, 5][..]));
+    }
+
+    #[test]
+    fn should_mark_end_of_input() {
+        let input = vec![1, 2];
+        let mut buffer = Parser::new(&input);
+        buffer.get::<()>().unwrap();
+        buffer.get::<()>().unwrap();
+
+        assert_eq!(format!("{:?}", buffer), "Parser { index: 2, len: 2, window: [1 2 >><end>] }");
+    }
+}
@@ -0,0 +1,207 @@
+
+use crate::{JlnError, Parser, Span};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeMode {
+    Strict,
+    Lossy,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decoded {
+    chars : Vec<char>,
+    offsets : Vec<usize>,
+}
+
+impl Decoded {
+    pub fn utf16<E : JlnError>(units : &[u16], mode : DecodeMode) -> Result<Decoded, E> {
+        let mut decoded = Decoded { chars: vec![], offsets: vec![] };
+        let mut input = Parser::new(units);
+        while !input.end() {
+            decoded.offsets.push(input.index());
+            decoded.chars.push(input.utf16_char(mode)?);
+        }
+        decoded.offsets.push(units.len());
+        Ok(decoded)
+    }
+
+    pub fn utf16le<E : JlnError>(bytes : &[u8], mode : DecodeMode) -> Result<Decoded, E> {
+        let units = bytes.chunks_exact(2).map(|x| u16::from_le_bytes([x[0], x[1]])).collect::<Vec<_>>();
+        let mut decoded = Decoded::utf16(&units, mode)?;
+        if !bytes.len().is_multiple_of(2) {
+            if mode == DecodeMode::Strict {
+                return Err(E::custom(Some("utf16"), units.len()));
+            }
+            decoded.chars.push(char::REPLACEMENT_CHARACTER);
+            decoded.offsets.push(units.len() + 1);
+        }
+        Ok(decoded)
+    }
+
+    pub fn utf8<E : JlnError>(bytes : &[u8], mode : DecodeMode) -> Result<Decoded, E> {
+        let mut decoded = Decoded { chars: vec![], offsets: vec![] };
+        let mut input = Parser::new(bytes);
+        while !input.end() {
+            decoded.offsets.push(input.index());
+            decoded.chars.push(input.utf8_char(mode)?);
+        }
+        decoded.offsets.push(bytes.len());
+        Ok(decoded)
+    }
+
+    pub fn chars(&self) -> &[char] {
+        &self.chars
+    }
+
+    pub fn parser(&self) -> Parser<'_, char> {
+        Parser::new(&self.chars)
+    }
+
+    pub fn source_index(&self, index : usize) -> usize {
+        self.offsets[index.min(self.offsets.len() - 1)]
+    }
+
+    pub fn source_span(&self, span : Span) -> Span {
+        Span::new(self.source_index(span.start), self.source_index(span.end))
+    }
+}
+
+impl<'a> Parser<'a, u16> {
+    pub fn utf16_char<E : JlnError>(&mut self, mode : DecodeMode) -> Result<char, E> {
+        let start = self.index;
+        let high = *self.peek::<E>()?;
+        let split = (0xd800..0xdc00).contains(&high) && start + 1 == self.bound();
+        if split && self.awaiting_input() {
+            return Err(self.exhausted());
+        }
+        let units = &self.input[start..self.bound().min(start + 2)];
+        match char::decode_utf16(units.iter().copied()).next() {
+            Some(Ok(c)) => {
+                for _ in 0..c.len_utf16() {
+                    self.get::<E>()?;
+                }
+                Ok(c)
+            },
+            _ if mode == DecodeMode::Strict => Err(E::custom(Some("utf16"), start)),
+            _ => {
+                self.get::<E>()?;
+                Ok(char::REPLACEMENT_CHARACTER)
+            },
+        }
+    }
+}
+
+impl<'a> Parser<'a, u8> {
+    pub fn utf8_char<E : JlnError>(&mut self, mode : DecodeMode) -> Result<char, E> {
+        self.consume_trivia();
+        self.no_trivia(|input| {
+            let start = input.index;
+            let width = match *input.peek::<E>()? {
+                0x00..=0x7f => 1,
+                0xc2..=0xdf => 2,
+                0xe0..=0xef => 3,
+                0xf0..=0xf4 => 4,
+                _ => 1,
+            };
+            let end = input.bound().min(start + width);
+            let (c, used) = match std::str::from_utf8(&input.input[start..end]) {
+                Ok(text) => (text.chars().next().unwrap_or(char::REPLACEMENT_CHARACTER), end - start),
+                Err(e) if e.error_len().is_none() && input.awaiting_input() => { return Err(input.exhausted()); },
+                Err(_) if mode == DecodeMode::Strict => { return Err(E::custom(Some("utf8"), start)); },
+                Err(e) => (char::REPLACEMENT_CHARACTER, e.error_len().unwrap_or(end - start)),
+            };
+            for _ in 0..used {
+                input.get::<E>()?;
+            }
+            Ok(c)
+        })
+    }
+
+    pub fn utf8_take_while<E : JlnError, P : FnMut(char) -> bool>(&mut self, mode : DecodeMode, mut pred : P) -> Result<String, E> {
+        let mut text = String::new();
+        while !self.end() {
+            let mut probe = self.fork();
+            let c = probe.utf8_char(mode)?;
+            if !pred(c) {
+                break;
+            }
+            self.adopt(probe)?;
+            text.push(c);
+        }
+        Ok(text)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Diagnostic, StreamBuffer};
+
+    fn units(text : &str) -> Vec<u16> {
+        text.encode_utf16().collect()
+    }
+
+    #[test]
+    fn should_decode_surrogate_pairs_with_source_positions() {
+        let decoded = Decoded::utf16::<()>(&units("a😀b = 1"), DecodeMode::Strict).unwrap();
+
+        assert_eq!(decoded.chars().iter().collect::<String>(), "a😀b = 1");
+        let mut input = decoded.parser();
+        input.tag_str::<()>("a😀").unwrap();
+        let (_, span) = input.spanned(|input| input.expect::<()>(&'b').copied()).unwrap();
+        assert_eq!(decoded.source_span(span), Span::new(3, 4));
+
+        let bytes = "hé".encode_utf16().flat_map(|x| x.to_le_bytes()).collect::<Vec<_>>();
+        assert_eq!(Decoded::utf16le::<()>(&bytes, DecodeMode::Strict).unwrap().chars(), &['h', 'é']);
+    }
+
+    #[test]
+    fn should_reject_or_replace_lone_surrogates() {
+        let broken = vec![0x61, 0xd800, 0x62, 0xdc00];
+        let error = Decoded::utf16::<Diagnostic>(&broken, DecodeMode::Strict).unwrap_err();
+        assert_eq!(error.snapshot(), "at 1: Custom { label: Some(\"utf16\") }\n");
+
+        let lossy = Decoded::utf16::<()>(&broken, DecodeMode::Lossy).unwrap();
+        assert_eq!(lossy.chars(), &['a', '\u{fffd}', 'b', '\u{fffd}']);
+        assert_eq!(Decoded::utf16le::<()>(&[0x61, 0, 0x62], DecodeMode::Lossy).unwrap().chars(), &['a', '\u{fffd}']);
+        assert!(Decoded::utf16le::<()>(&[0x61, 0, 0x62], DecodeMode::Strict).is_err());
+    }
+
+    #[test]
+    fn should_wait_for_low_surrogate_when_streaming() {
+        let mut stream = StreamBuffer::new();
+        stream.feed(units("😀").into_iter().take(1));
+
+        assert_eq!(stream.next(|input| input.utf16_char::<Diagnostic>(DecodeMode::Strict)), Ok(None));
+        stream.feed(units("😀").into_iter().skip(1));
+        assert_eq!(stream.next(|input| input.utf16_char::<Diagnostic>(DecodeMode::Strict)), Ok(Some('😀')));
+    }
+
+    #[test]
+    fn should_decode_utf8_on_the_fly() {
+        let input = "naïve 東京 rest".as_bytes();
+        let mut buffer = Parser::new(input);
+
+        assert_eq!(buffer.utf8_take_while::<(), _>(DecodeMode::Strict, |c| c.is_alphabetic()), Ok(String::from("naïve")));
+        assert_eq!(buffer.index(), 6);
+        buffer.expect::<()>(&b' ').unwrap();
+        assert_eq!(buffer.utf8_char::<()>(DecodeMode::Strict), Ok('東'));
+        assert_eq!(buffer.index(), 10);
+        let decoded = Decoded::utf8::<()>(input, DecodeMode::Strict).unwrap();
+        assert_eq!(decoded.source_index(8), 13);
+    }
+
+    #[test]
+    fn should_report_or_replace_invalid_utf8() {
+        let broken = b"a\xe6\x9d\xb1\xff\xe6\x9dz";
+        let error = Decoded::utf8::<Diagnostic>(broken, DecodeMode::Strict).unwrap_err();
+        assert_eq!(error.snapshot(), "at 4: Custom { label: Some(\"utf8\") }\n");
+        assert_eq!(Decoded::utf8::<()>(broken, DecodeMode::Lossy).unwrap().chars().iter().collect::<String>(), String::from_utf8_lossy(broken));
+
+        let mut stream = StreamBuffer::new();
+        stream.feed("é".bytes().take(1));
+        assert_eq!(stream.next(|input| input.utf8_char::<Diagnostic>(DecodeMode::Strict)), Ok(None));
+        stream.feed("é".bytes().skip(1));
+        assert_eq!(stream.next(|input| input.utf8_char::<Diagnostic>(DecodeMode::Strict)), Ok(Some('é')));
+    }
+}
@@ -0,0 +1,184 @@
+
+use crate::{ErrorKind, JlnError};
+
+#[derive(Debug)]
+pub enum Deferred<E> {
+    Eof,
+    EofAt { index : usize },
+    Kind { kind : ErrorKind, index : usize },
+    Custom { label : Option<&'static str>, index : usize },
+    Expected { error : Box<Deferred<E>>, description : String },
+    Aggregate(Vec<Deferred<E>>),
+    Context { error : Box<Deferred<E>>, rule : &'static str, index : usize },
+    Backtrace { error : Box<Deferred<E>>, rules : Vec<(&'static str, usize)> },
+    Ready(E),
+}
+
+impl<E : JlnError> Deferred<E> {
+    pub fn into_error(self) -> E {
+        match self {
+            Deferred::Eof => E::eof(),
+            Deferred::EofAt { index } => E::eof_at(index),
+            Deferred::Kind { kind, index } => E::from_kind(kind, index),
+            Deferred::Custom { label, index } => E::custom(label, index),
+            Deferred::Expected { error, description } => error.into_error().expected(&description),
+            Deferred::Aggregate(errors) => E::aggregate(errors.into_iter().map(Deferred::into_error).collect()),
+            Deferred::Context { error, rule, index } => error.into_error().context(rule, index),
+            Deferred::Backtrace { error, rules } => error.into_error().backtrace(&rules),
+            Deferred::Ready(e) => e,
+        }
+    }
+}
+
+impl<E> From<E> for Deferred<E> {
+    fn from(e : E) -> Self {
+        Deferred::Ready(e)
+    }
+}
+
+impl<E : JlnError> JlnError for Deferred<E> {
+    fn is_fatal(&self) -> bool {
+        match self {
+            Deferred::Eof | Deferred::EofAt { .. } => false,
+            Deferred::Kind { kind, .. } => kind.is_fatal(),
+            Deferred::Custom { label, .. } => ErrorKind::Custom { label : *label }.is_fatal(),
+            Deferred::Expected { error, .. } => error.is_fatal(),
+            Deferred::Aggregate(errors) => errors.iter().any(JlnError::is_fatal),
+            Deferred::Context { error, .. } | Deferred::Backtrace { error, .. } => error.is_fatal(),
+            Deferred::Ready(e) => e.is_fatal(),
+        }
+    }
+
+    fn eof() -> Self {
+        Deferred::Eof
+    }
+
+    fn eof_at(index : usize) -> Self {
+        Deferred::EofAt { index }
+    }
+
+    fn aggregate(errors : Vec<Self>) -> Self {
+        Deferred::Aggregate(errors)
+    }
+
+    fn from_kind(kind : ErrorKind, index : usize) -> Self {
+        Deferred::Kind { kind, index }
+    }
+
+    fn context(self, rule : &'static str, index : usize) -> Self {
+        Deferred::Context { error: Box::new(self), rule, index }
+    }
+
+    fn custom(label : Option<&'static str>, index : usize) -> Self {
+        Deferred::Custom { label, index }
+    }
+
+    fn expected(self, description : &str) -> Self {
+        Deferred::Expected { error: Box::new(self), description: description.to_string() }
+    }
+
+    fn backtrace(self, rules : &[(&'static str, usize)]) -> Self {
+        Deferred::Backtrace { error: Box::new(self), rules: rules.to_vec() }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::Parser;
+
+    thread_local! {
+        static BUILT : Cell<usize> = const { Cell::new(0) };
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Heavy(Vec<String>);
+
+    impl Heavy {
+        fn new(message : String) -> Self {
+            BUILT.with(|x| x.set(x.get() + 1));
+            Heavy(vec![message])
+        }
+    }
+
+    impl JlnError for Heavy {
+        fn is_fatal(&self) -> bool { false }
+        fn eof() -> Self { Heavy::new("eof".into()) }
+        fn aggregate(errors : Vec<Self>) -> Self { Heavy(errors.into_iter().flat_map(|x| x.0).collect()) }
+        fn from_kind(kind : ErrorKind, index : usize) -> Self { Heavy::new(format!("{:?} at {}", kind, index)) }
+        fn context(self, rule : &'static str, _index : usize) -> Self { Heavy(self.0.into_iter().map(|x| format!("{}: {}", rule, x)).collect()) }
+    }
+
+    fn digit(input : &mut Parser<char>) -> Result<char, Deferred<Heavy>> {
+        input.satisfy(|c : &char| c.is_ascii_digit()).copied()
+    }
+
+    fn letter(input : &mut Parser<char>) -> Result<char, Deferred<Heavy>> {
+        input.satisfy(|c : &char| c.is_alphabetic()).copied()
+    }
+
+    #[test]
+    fn should_only_build_errors_that_escape() {
+        let input = "12a3!".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        let items = buffer.list(|input| input.or([digit, letter])).map_err(Deferred::into_error).unwrap();
+        assert_eq!(items, vec!['1', '2', 'a', '3']);
+        assert_eq!(BUILT.with(|x| x.get()), 0);
+
+        let error = buffer.context("item", |input| input.or([digit, letter])).map_err(Deferred::into_error).unwrap_err();
+        assert_eq!(error.0, vec!["item: Unexpected at 4", "item: Unexpected at 4"]);
+        assert_eq!(BUILT.with(|x| x.get()), 2);
+    }
+
+    #[test]
+    fn should_answer_fatality_and_replay_hooks_without_building() {
+        let input = "ab".chars().collect::<Vec<_>>();
+        let before = BUILT.with(|x| x.get());
+
+        let error = Parser::new(&input[..0]).or([digit, letter]).unwrap_err();
+        assert!(!error.is_fatal());
+        assert!(!Deferred::<Heavy>::eof_at(3).is_fatal());
+        assert_eq!(BUILT.with(|x| x.get()), before);
+
+        let replayed = Deferred::<crate::Diagnostic>::custom(Some("tag"), 1).into_error();
+        assert_eq!(replayed.snapshot(), "at 1: Custom { label: Some(\"tag\") }\n");
+        let expected = Parser::new(&input).expecting("digit", |input| input.satisfy::<Deferred<crate::Diagnostic>, _>(|c : &char| c.is_ascii_digit()).copied());
+        assert_eq!(expected.map_err(Deferred::into_error).unwrap_err().snapshot(), "at 0: expected digit\n");
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Plain;
+
+    impl JlnError for Plain {
+        fn is_fatal(&self) -> bool { false }
+        fn eof() -> Self { Plain }
+        fn aggregate(_errors : Vec<Self>) -> Self { Plain }
+    }
+
+    fn open<E : JlnError>(input : &mut Parser<char>) -> Result<usize, E> {
+        let mut input = input.enter_rule()?;
+        input.expect(&'(')?;
+        let inner = input.option(open)?;
+        Ok(inner.unwrap_or(0) + 1)
+    }
+
+    #[test]
+    fn should_backtrack_the_same_way_as_the_wrapped_error() {
+        let input = "((((".chars().collect::<Vec<_>>();
+        let run = |limit| {
+            let mut plain = Parser::new(&input);
+            plain.set_max_depth(Some(limit));
+            let mut deferred = Parser::new(&input);
+            deferred.set_max_depth(Some(limit));
+            let plain = plain.option(open::<Plain>).map_err(|_| ());
+            let deferred = deferred.option(open::<Deferred<Plain>>).map_err(|_| ());
+            (plain, deferred)
+        };
+
+        assert_eq!(run(8), (Ok(Some(4)), Ok(Some(4))));
+        assert_eq!(run(2), (Err(()), Err(())));
+    }
+}
@@ -0,0 +1,139 @@
+
+use std::ops::{Deref, DerefMut};
+
+use crate::{ErrorKind, JlnError, Parser};
+
+pub struct RuleGuard<'p, 'a, T> {
+    parser : &'p mut Parser<'a, T>,
+}
+
+impl<'p, 'a, T> Deref for RuleGuard<'p, 'a, T> {
+    type Target = Parser<'a, T>;
+
+    fn deref(&self) -> &Self::Target {
+        self.parser
+    }
+}
+
+impl<'p, 'a, T> DerefMut for RuleGuard<'p, 'a, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.parser
+    }
+}
+
+impl<'p, 'a, T> Drop for RuleGuard<'p, 'a, T> {
+    fn drop(&mut self) {
+        self.parser.depth -= 1;
+    }
+}
+
+impl<'a, T> Parser<'a, T> {
+    pub fn set_max_depth(&mut self, limit : Option<usize>) {
+        self.max_depth = limit;
+    }
+
+    pub fn max_depth(&self) -> Option<usize> {
+        self.max_depth
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn enter_rule<E : JlnError>(&mut self) -> Result<RuleGuard<'_, 'a, T>, E> {
+        match self.max_depth {
+            Some(limit) if self.depth >= limit => Err(self.raise(ErrorKind::NestingTooDeep { limit }, self.index)),
+            _ => {
+                self.depth += 1;
+                self.count_depth();
+                Ok(RuleGuard { parser: self })
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::TError;
+
+    fn nested(input : &mut Parser<char>) -> Result<usize, TError> {
+        let mut input = input.enter_rule()?;
+        match input.peek::<TError>() {
+            Ok('(') => {
+                input.get::<TError>()?;
+                let inner = nested(&mut input)?;
+                Ok(inner + 1)
+            },
+            _ => Ok(0),
+        }
+    }
+
+    #[test]
+    fn should_track_depth_with_guard() {
+        let input = vec!['a'];
+        let mut buffer = Parser::new(&input);
+
+        {
+            let guard = buffer.enter_rule::<()>().unwrap();
+            assert_eq!(guard.depth(), 1);
+        }
+
+        assert_eq!(buffer.depth(), 0);
+    }
+
+    #[test]
+    fn should_parse_nesting_within_limit() {
+        let input = vec!['(', '(', '(', 'a'];
+        let mut buffer = Parser::new(&input);
+        buffer.set_max_depth(Some(4));
+
+        let result = nested(&mut buffer);
+
+        assert!(matches!(result, Ok(3)));
+        assert_eq!(buffer.depth(), 0);
+    }
+
+    #[test]
+    fn should_fail_fatally_when_nesting_too_deep() {
+        let input = vec!['('; 10_000];
+        let mut buffer = Parser::new(&input);
+        buffer.set_max_depth(Some(64));
+
+        let result = nested(&mut buffer);
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().is_fatal());
+        assert_eq!(buffer.depth(), 0);
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Plain;
+
+    impl JlnError for Plain {
+        fn is_fatal(&self) -> bool { false }
+        fn eof() -> Self { Plain }
+        fn aggregate(_errors : Vec<Self>) -> Self { Plain }
+    }
+
+    fn open(input : &mut Parser<char>) -> Result<usize, Plain> {
+        let mut input = input.enter_rule()?;
+        input.expect(&'(')?;
+        let inner = input.option(open)?;
+        Ok(inner.unwrap_or(0) + 1)
+    }
+
+    #[test]
+    fn should_halt_error_types_that_keep_default_from_kind() {
+        let input = vec!['('; 10];
+        let mut buffer = Parser::new(&input);
+        buffer.set_max_depth(Some(5));
+
+        let result = buffer.or([open, |input : &mut Parser<char>| input.list(|input| input.expect::<Plain>(&'(').copied()).map(|x| x.len())]);
+
+        assert_eq!(result, Err(Plain));
+        assert!(buffer.halted());
+        assert_eq!((buffer.index(), buffer.depth()), (0, 0));
+        assert!(!Parser::new(&input).halted());
+    }
+}
@@ -0,0 +1,276 @@
+
+use std::fmt::{self, Display, Formatter, Write};
+
+use crate::{ErrorKind, JlnError};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Diagnostic {
+    Eof,
+    EofAt { index : usize },
+    Expected { expected : Vec<String>, index : usize },
+    Kind { kind : ErrorKind, index : usize },
+    Aggregate(Vec<Diagnostic>),
+    Context { rule : &'static str, index : usize, error : Box<Diagnostic> },
+    Backtrace { rules : Vec<(&'static str, usize)>, error : Box<Diagnostic> },
+}
+
+impl Diagnostic {
+    pub fn canonical(self) -> Diagnostic {
+        match self {
+            Diagnostic::Aggregate(errors) => {
+                let mut flat = vec![];
+                for error in errors {
+                    match error.canonical() {
+                        Diagnostic::Aggregate(inner) => { flat.extend(inner); },
+                        error => { flat.push(error); },
+                    }
+                }
+                let mut keyed = flat.into_iter().map(|x| (x.render(), x)).collect::<Vec<_>>();
+                keyed.sort_by(|a, b| a.0.cmp(&b.0));
+                keyed.dedup_by(|a, b| a.0 == b.0);
+                let mut errors = keyed.into_iter().map(|x| x.1).collect::<Vec<_>>();
+                match errors.len() {
+                    1 => errors.pop().unwrap_or(Diagnostic::Aggregate(vec![])),
+                    _ => Diagnostic::Aggregate(errors),
+                }
+            },
+            Diagnostic::Context { rule, index, error } => Diagnostic::Context { rule, index, error: Box::new(error.canonical()) },
+            Diagnostic::Backtrace { rules, error } => Diagnostic::Backtrace { rules, error: Box::new(error.canonical()) },
+            error => error,
+        }
+    }
+
+    fn has_backtrace(&self) -> bool {
+        match self {
+            Diagnostic::Backtrace { .. } => true,
+            Diagnostic::Aggregate(errors) => errors.iter().any(Diagnostic::has_backtrace),
+            Diagnostic::Context { error, .. } => error.has_backtrace(),
+            _ => false,
+        }
+    }
+
+    pub fn snapshot(&self) -> String {
+        self.clone().canonical().render()
+    }
+
+    fn render(&self) -> String {
+        let mut text = String::new();
+        self.write_tree(&mut text, 0);
+        text
+    }
+
+    fn write_tree(&self, text : &mut String, level : usize) {
+        let indent = "  ".repeat(level);
+        match self {
+            Diagnostic::Eof => { let _ = writeln!(text, "{}end of input", indent); },
+            Diagnostic::EofAt { index } => { let _ = writeln!(text, "{}at {}: end of input", indent, index); },
+            Diagnostic::Expected { expected, index } => {
+                let mut expected = expected.clone();
+                expected.sort();
+                expected.dedup();
+                match expected.as_slice() {
+                    [one] => { let _ = writeln!(text, "{}at {}: expected {}", indent, index, one); },
+                    _ => { let _ = writeln!(text, "{}at {}: expected one of {}", indent, index, expected.join(", ")); },
+                }
+            },
+            Diagnostic::Kind { kind, index } => { let _ = writeln!(text, "{}at {}: {:?}", indent, index, kind); },
+            Diagnostic::Aggregate(errors) => {
+                let _ = writeln!(text, "{}any of:", indent);
+                for error in errors {
+                    error.write_tree(text, level + 1);
+                }
+            },
+            Diagnostic::Context { rule, index, error } => {
+                let _ = writeln!(text, "{}in {} at {}:", indent, rule, index);
+                error.write_tree(text, level + 1);
+            },
+            Diagnostic::Backtrace { rules, error } => {
+                let frames = rules.iter().map(|(rule, index)| format!("{}@{}", rule, index)).collect::<Vec<_>>();
+                let _ = writeln!(text, "{}via {}:", indent, frames.join(" > "));
+                error.write_tree(text, level + 1);
+            },
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.snapshot())
+    }
+}
+
+impl JlnError for Diagnostic {
+    fn is_fatal(&self) -> bool {
+        match self {
+            Diagnostic::Eof | Diagnostic::EofAt { .. } | Diagnostic::Expected { .. } => false,
+            Diagnostic::Kind { kind, .. } => kind.is_fatal(),
+            Diagnostic::Aggregate(errors) => errors.iter().any(JlnError::is_fatal),
+            Diagnostic::Context { error, .. } | Diagnostic::Backtrace { error, .. } => error.is_fatal(),
+        }
+    }
+
+    fn eof() -> Self {
+        Diagnostic::Eof
+    }
+
+    fn eof_at(index : usize) -> Self {
+        Diagnostic::EofAt { index }
+    }
+
+    fn aggregate(errors : Vec<Self>) -> Self {
+        let mut merged : Vec<Diagnostic> = vec![];
+        for error in errors {
+            match error {
+                Diagnostic::Expected { expected, index } => {
+                    let existing = merged.iter_mut().find_map(|x| match x {
+                        Diagnostic::Expected { expected, index : at } if *at == index => Some(expected),
+                        _ => None,
+                    });
+                    match existing {
+                        Some(set) => { set.extend(expected); },
+                        None => { merged.push(Diagnostic::Expected { expected, index }); },
+                    }
+                },
+                error => { merged.push(error); },
+            }
+        }
+        match merged.as_slice() {
+            [Diagnostic::Expected { .. }] => merged.pop().unwrap_or(Diagnostic::Aggregate(vec![])),
+            _ => Diagnostic::Aggregate(merged),
+        }
+    }
+
+    fn from_kind(kind : ErrorKind, index : usize) -> Self {
+        Diagnostic::Kind { kind, index }
+    }
+
+    fn context(self, rule : &'static str, index : usize) -> Self {
+        Diagnostic::Context { rule, index, error: Box::new(self) }
+    }
+
+    fn backtrace(self, rules : &[(&'static str, usize)]) -> Self {
+        match self.has_backtrace() {
+            true => self,
+            false => Diagnostic::Backtrace { rules: rules.to_vec(), error: Box::new(self) },
+        }
+    }
+
+    fn expected(self, description : &str) -> Self {
+        match self {
+            Diagnostic::Kind { kind : ErrorKind::Unexpected, index } | Diagnostic::EofAt { index } => Diagnostic::Expected { expected: vec![description.to_string()], index },
+            Diagnostic::Expected { mut expected, index } => {
+                expected.push(description.to_string());
+                Diagnostic::Expected { expected, index }
+            },
+            error => error,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Parser;
+
+    fn digit(input : &mut Parser<char>) -> Result<char, Diagnostic> {
+        input.context("digit", |input| input.satisfy(|c : &char| c.is_ascii_digit()).copied())
+    }
+
+    fn letter(input : &mut Parser<char>) -> Result<char, Diagnostic> {
+        input.context("letter", |input| input.satisfy(|c : &char| c.is_alphabetic()).copied())
+    }
+
+    fn pair(input : &mut Parser<char>) -> Result<char, Diagnostic> {
+        input.context("pair", |input| {
+            input.expect(&'(')?;
+            input.or([digit, letter])
+        })
+    }
+
+    #[test]
+    fn should_render_same_snapshot_regardless_of_alternative_order() {
+        let input = "!".chars().collect::<Vec<_>>();
+
+        let a = Parser::new(&input).or([digit, pair, letter]).unwrap_err();
+        let b = Parser::new(&input).or([letter, digit, pair]).unwrap_err();
+
+        assert_ne!(a, b);
+        assert_eq!(a.snapshot(), b.snapshot());
+        assert_eq!(a.snapshot(), concat!(
+            "any of:\n",
+            "  in digit at 0:\n",
+            "    at 0: Unexpected\n",
+            "  in letter at 0:\n",
+            "    at 0: Unexpected\n",
+            "  in pair at 0:\n",
+            "    at 0: Unexpected\n",
+        ));
+    }
+
+    #[test]
+    fn should_report_only_furthest_failing_alternatives() {
+        let input = "(!".chars().collect::<Vec<_>>();
+
+        let error = Parser::new(&input).or([digit, pair, letter]).unwrap_err();
+
+        assert_eq!(error.snapshot(), concat!(
+            "in pair at 0:\n",
+            "  any of:\n",
+            "    in digit at 1:\n",
+            "      at 1: Unexpected\n",
+            "    in letter at 1:\n",
+            "      at 1: Unexpected\n",
+        ));
+        let mut buffer = Parser::new(&input);
+        assert!(buffer.or([pair, digit]).is_err());
+        assert_eq!(buffer.or([digit, letter]).unwrap_err().snapshot(), "any of:\n  in digit at 0:\n    at 0: Unexpected\n  in letter at 0:\n    at 0: Unexpected\n");
+    }
+
+    #[test]
+    fn should_flatten_and_deduplicate_aggregates() {
+        let eof = Diagnostic::aggregate(vec![
+            Diagnostic::eof(),
+            Diagnostic::aggregate(vec![Diagnostic::eof(), Diagnostic::from_kind(ErrorKind::Overflow, 2)]),
+        ]);
+
+        assert_eq!(eof.clone().canonical(), Diagnostic::Aggregate(vec![Diagnostic::Kind { kind: ErrorKind::Overflow, index: 2 }, Diagnostic::Eof]));
+        assert_eq!(Diagnostic::aggregate(vec![Diagnostic::eof()]).snapshot(), "end of input\n");
+        assert_eq!(eof.to_string(), "any of:\n  at 2: Overflow\n  end of input\n");
+    }
+
+    #[test]
+    fn should_report_where_input_ended() {
+        let input = "a  ".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        buffer.set_trivia(Some(|c : &char| *c == ' '));
+        buffer.get::<Diagnostic>().unwrap();
+
+        assert_eq!(buffer.get::<Diagnostic>().unwrap_err().snapshot(), "at 3: end of input\n");
+        assert_eq!(Parser::new(&input[..1]).limit(0, |input| input.peek::<Diagnostic>().copied()), Err(Diagnostic::EofAt { index: 0 }));
+        assert_eq!(buffer.get::<()>(), Err(()));
+    }
+
+    #[test]
+    fn should_merge_expected_sets_across_alternatives() {
+        fn comma(input : &mut Parser<char>) -> Result<char, Diagnostic> {
+            input.expect_no_case(',')
+        }
+
+        fn close(input : &mut Parser<char>) -> Result<char, Diagnostic> {
+            input.tag_str(")").map(|_| ')')
+        }
+
+        fn digit(input : &mut Parser<char>) -> Result<char, Diagnostic> {
+            input.expecting("digit", |input| input.satisfy(|c : &char| c.is_ascii_digit()).copied())
+        }
+
+        let input = "1;".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        buffer.get::<Diagnostic>().unwrap();
+
+        assert_eq!(buffer.or([comma, close]).unwrap_err().snapshot(), "at 1: expected one of `)`, `,`\n");
+        assert_eq!(buffer.or([digit, comma]).unwrap_err().snapshot(), "at 1: expected one of `,`, digit\n");
+        buffer.get::<Diagnostic>().unwrap();
+        assert_eq!(comma(&mut buffer).unwrap_err().snapshot(), "at 2: expected `,`\n");
+    }
+}
@@ -0,0 +1,109 @@
+
+use crate::{JlnError, Parser};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Either<A, B> {
+    Left(A),
+    Right(B),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Either3<A, B, C> {
+    First(A),
+    Second(B),
+    Third(C),
+}
+
+impl<A, B> Either<A, B> {
+    pub fn left(self) -> Option<A> {
+        match self {
+            Either::Left(a) => Some(a),
+            Either::Right(_) => None,
+        }
+    }
+
+    pub fn right(self) -> Option<B> {
+        match self {
+            Either::Left(_) => None,
+            Either::Right(b) => Some(b),
+        }
+    }
+}
+
+impl<'a, T> Parser<'a, T> {
+    pub fn either<A, B, E, F, G>(&mut self, a : F, b : G) -> Result<Either<A, B>, E>
+        where E : JlnError,
+              F : FnOnce(&mut Parser<'a, T>) -> Result<A, E>,
+              G : FnOnce(&mut Parser<'a, T>) -> Result<B, E>,
+    {
+        let first = match self.attempt(a) {
+            Ok(a) => { return Ok(Either::Left(a)); },
+            Err(e) if self.fatal(&e) => { return Err(e); },
+            Err(e) => e,
+        };
+        match self.attempt(b) {
+            Ok(b) => Ok(Either::Right(b)),
+            Err(e) if self.fatal(&e) => Err(e),
+            Err(e) => Err(E::aggregate(vec![first, e])),
+        }
+    }
+
+    pub fn either3<A, B, C, E, F, G, H>(&mut self, a : F, b : G, c : H) -> Result<Either3<A, B, C>, E>
+        where E : JlnError,
+              F : FnOnce(&mut Parser<'a, T>) -> Result<A, E>,
+              G : FnOnce(&mut Parser<'a, T>) -> Result<B, E>,
+              H : FnOnce(&mut Parser<'a, T>) -> Result<C, E>,
+    {
+        let mut errors = vec![];
+        match self.attempt(a) {
+            Ok(a) => { return Ok(Either3::First(a)); },
+            Err(e) if self.fatal(&e) => { return Err(e); },
+            Err(e) => { errors.push(e); },
+        }
+        match self.attempt(b) {
+            Ok(b) => { return Ok(Either3::Second(b)); },
+            Err(e) if self.fatal(&e) => { return Err(e); },
+            Err(e) => { errors.push(e); },
+        }
+        match self.attempt(c) {
+            Ok(c) => Ok(Either3::Third(c)),
+            Err(e) if self.fatal(&e) => Err(e),
+            Err(e) => {
+                errors.push(e);
+                Err(E::aggregate(errors))
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Diagnostic;
+    use crate::test::TError;
+
+    #[test]
+    fn should_return_heterogeneous_alternatives() {
+        let input = "12ab".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        let digit = |input : &mut Parser<char>| input.satisfy::<(), _>(|c| c.is_ascii_digit()).map(|c| c.to_digit(10).unwrap());
+        let word = |input : &mut Parser<char>| input.tag_str::<()>("ab").map(|x| x.iter().collect::<String>());
+
+        assert_eq!(buffer.either(digit, word), Ok(Either::Left(1)));
+        assert_eq!(buffer.either(word, digit).map(Either::right), Ok(Some(2)));
+        assert_eq!(buffer.either3(digit, |input| input.expect::<()>(&'x').copied(), word), Ok(Either3::Third(String::from("ab"))));
+        assert!(buffer.end());
+    }
+
+    #[test]
+    fn should_aggregate_or_stop_on_fatal_errors() {
+        let input = vec![1, 2];
+        let mut buffer = Parser::new(&input);
+
+        let error = buffer.either(|input| input.expect::<Diagnostic>(&5).copied(), |input| input.tag::<Diagnostic>(&[1, 3]).map(|x| x.len())).unwrap_err();
+        assert_eq!(error.snapshot(), "any of:\n  at 0: Unexpected\n  at 1: Unexpected\n");
+        let fatal = buffer.either(|_| Err::<(), _>(TError(true)), |input| input.get::<TError>().copied());
+        assert!(fatal.unwrap_err().is_fatal());
+        assert_eq!(buffer.index(), 0);
+    }
+}
@@ -0,0 +1,163 @@
+
+use crate::{JlnError, Parser, TextToken};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Padding {
+    Required,
+    Optional,
+    Forbidden,
+}
+
+#[derive(Debug, Clone)]
+pub struct Base64Style {
+    alphabet : [char; 64],
+    padding : Padding,
+}
+
+impl Default for Base64Style {
+    fn default() -> Self {
+        Base64Style::standard()
+    }
+}
+
+impl Base64Style {
+    pub fn new(alphabet : [char; 64], padding : Padding) -> Self {
+        Base64Style { alphabet, padding }
+    }
+
+    pub fn standard() -> Self {
+        Base64Style::with_tail('+', '/', Padding::Required)
+    }
+
+    pub fn url_safe() -> Self {
+        Base64Style::with_tail('-', '_', Padding::Optional)
+    }
+
+    pub fn set_padding(&mut self, padding : Padding) {
+        self.padding = padding;
+    }
+
+    fn with_tail(a : char, b : char, padding : Padding) -> Self {
+        let mut alphabet = ['A'; 64];
+        for (i, c) in ('A'..='Z').chain('a'..='z').chain('0'..='9').chain([a, b]).enumerate() {
+            alphabet[i] = c;
+        }
+        Base64Style { alphabet, padding }
+    }
+
+    fn value(&self, c : char) -> Option<u32> {
+        self.alphabet.iter().position(|x| *x == c).map(|x| x as u32)
+    }
+}
+
+impl<'a, T : TextToken> Parser<'a, T> {
+    pub fn base64_bytes<E : JlnError>(&mut self, style : &Base64Style) -> Result<Vec<u8>, E> {
+        self.with_rollback(|input| {
+            input.consume_trivia();
+            input.no_trivia(|input| {
+                let invalid = |index| E::custom(Some("base64"), index);
+                let mut bytes = vec![];
+                let mut buffer = 0u32;
+                let mut count = 0;
+                while let Some(value) = input.peek::<E>().ok().and_then(|t| style.value(t.to_char())) {
+                    buffer = buffer << 6 | value;
+                    count += 1;
+                    input.get::<E>()?;
+                    if count % 4 == 0 {
+                        bytes.extend_from_slice(&buffer.to_be_bytes()[1..]);
+                        buffer = 0;
+                    }
+                }
+                let last = input.index.saturating_sub(1);
+                let (extra, bits) = match count % 4 {
+                    0 => (0, 0),
+                    1 => { return Err(invalid(last)); },
+                    2 => (1, 4),
+                    _ => (2, 2),
+                };
+                if buffer & ((1 << bits) - 1) != 0 {
+                    return Err(invalid(last));
+                }
+                let tail = (buffer >> bits).to_be_bytes();
+                bytes.extend_from_slice(&tail[4 - extra..]);
+                let pad = if extra == 0 { 0 } else { 3 - extra };
+                let mut padded = 0;
+                while padded < pad && input.peek::<E>().is_ok_and(|t| t.to_char() == '=') {
+                    if style.padding == Padding::Forbidden {
+                        return Err(invalid(input.index));
+                    }
+                    input.get::<E>()?;
+                    padded += 1;
+                }
+                if input.peek::<E>().is_ok_and(|t| t.to_char() == '=') {
+                    return Err(invalid(input.index));
+                }
+                if padded != pad && (padded > 0 || style.padding == Padding::Required) {
+                    return Err(invalid(input.index));
+                }
+                Ok(bytes)
+            })
+        })
+    }
+
+    pub fn hex_bytes<E : JlnError>(&mut self) -> Result<Vec<u8>, E> {
+        self.with_rollback(|input| {
+            let start = input.next_significant();
+            let digits = input.take_while::<E, _>(|t| t.to_char().is_ascii_hexdigit())?;
+            if digits.len() % 2 != 0 {
+                return Err(E::custom(Some("hex"), start + digits.len() - 1));
+            }
+            Ok(digits.chunks(2).map(|x| (x[0].to_char().to_digit(16).unwrap() << 4 | x[1].to_char().to_digit(16).unwrap()) as u8).collect())
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Diagnostic;
+
+    fn base64(text : &str, style : &Base64Style) -> Result<Vec<u8>, String> {
+        let input = text.chars().collect::<Vec<_>>();
+        Parser::new(&input).base64_bytes::<Diagnostic>(style).map_err(|e| e.snapshot())
+    }
+
+    #[test]
+    fn should_decode_base64_variants() {
+        let standard = Base64Style::standard();
+        assert_eq!(base64("aGVsbG8gd29ybGQ=", &standard), Ok(b"hello world".to_vec()));
+        assert_eq!(base64("+/+/", &standard), Ok(vec![0xfb, 0xff, 0xbf]));
+        assert_eq!(base64("", &standard), Ok(vec![]));
+        assert_eq!(base64("-_8", &Base64Style::url_safe()), Ok(vec![0xfb, 0xff]));
+        assert_eq!(base64("-_8=", &Base64Style::url_safe()), Ok(vec![0xfb, 0xff]));
+
+        let input = "YQ==,rest".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        assert_eq!(buffer.base64_bytes::<()>(&standard), Ok(b"a".to_vec()));
+        assert_eq!(buffer.index(), 4);
+    }
+
+    #[test]
+    fn should_point_at_offending_base64_character() {
+        let standard = Base64Style::standard();
+        assert_eq!(base64("aGVsbG8", &standard), Err("at 7: Custom { label: Some(\"base64\") }\n".into()));
+        assert_eq!(base64("YR==", &standard), Err("at 1: Custom { label: Some(\"base64\") }\n".into()));
+        assert_eq!(base64("YQ===", &standard), Err("at 4: Custom { label: Some(\"base64\") }\n".into()));
+        assert_eq!(base64("abcde", &standard), Err("at 4: Custom { label: Some(\"base64\") }\n".into()));
+
+        let mut strict = Base64Style::url_safe();
+        strict.set_padding(Padding::Forbidden);
+        assert_eq!(base64("YQ==", &strict), Err("at 2: Custom { label: Some(\"base64\") }\n".into()));
+    }
+
+    #[test]
+    fn should_decode_hex_runs() {
+        let input = b"DEad01 x".to_vec();
+        let mut buffer = Parser::new(&input);
+        assert_eq!(buffer.hex_bytes::<()>(), Ok(vec![0xde, 0xad, 0x01]));
+
+        let input = "abc".chars().collect::<Vec<_>>();
+        let error = Parser::new(&input).hex_bytes::<Diagnostic>().unwrap_err();
+        assert_eq!(error.snapshot(), "at 2: Custom { label: Some(\"hex\") }\n");
+    }
+}
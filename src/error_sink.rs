@@ -0,0 +1,182 @@
+
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::{JlnError, Parser};
+
+pub(crate) type ErrorSink = Rc<RefCell<Sink>>;
+
+#[derive(Default)]
+pub(crate) struct Sink {
+    kind : Option<TypeId>,
+    entries : Vec<Rc<dyn Any>>,
+}
+
+impl Sink {
+    fn typed<E : 'static>() -> Self {
+        Sink { kind: Some(TypeId::of::<E>()), entries: vec![] }
+    }
+
+    fn push(&mut self, error : Rc<dyn Any>) {
+        let kind = (*error).type_id();
+        match self.kind {
+            Some(x) if x != kind => { panic!("recorded errors must all have the same type"); },
+            Some(_) => { },
+            None => { self.kind = Some(kind); },
+        }
+        self.entries.push(error);
+    }
+
+    fn check<E : 'static>(&self, message : &str) {
+        if self.kind.is_some_and(|x| x != TypeId::of::<E>()) {
+            panic!("{}", message);
+        }
+    }
+}
+
+impl<'a, T> Parser<'a, T> {
+    pub fn enable_errors(&mut self) {
+        if self.errors.is_none() {
+            self.errors = Some(Rc::new(RefCell::new(Sink::default())));
+        }
+    }
+
+    pub fn disable_errors(&mut self) {
+        self.errors = None;
+    }
+
+    pub fn record_error<E : 'static>(&self, error : E) {
+        if let Some(errors) = &self.errors {
+            errors.borrow_mut().push(Rc::new(error));
+        }
+    }
+
+    pub fn errors<E : Clone + 'static>(&self) -> Vec<E> {
+        match &self.errors {
+            Some(errors) => {
+                let errors = errors.borrow();
+                errors.check::<E>("errors read back as a different type than recorded");
+                errors.entries.iter().filter_map(|x| x.downcast_ref::<E>()).cloned().collect()
+            },
+            None => vec![],
+        }
+    }
+
+    pub fn parse_recovering<S, E : JlnError + Clone + 'static, F : FnOnce(&mut Parser<'a, T>) -> Result<S, E>>(&mut self, f : F) -> (Option<S>, Vec<E>) {
+        let outer = self.errors.replace(Rc::new(RefCell::new(Sink::typed::<E>())));
+        let result = f(self);
+        let sink = std::mem::replace(&mut self.errors, outer).unwrap_or_default();
+        let mut errors = sink.borrow_mut().entries.drain(..).filter_map(|x| x.downcast::<E>().ok()).map(Rc::unwrap_or_clone).collect::<Vec<_>>();
+        match result {
+            Ok(value) => (Some(value), errors),
+            Err(e) => {
+                errors.push(e);
+                (None, errors)
+            },
+        }
+    }
+
+    pub(crate) fn error_mark(&self) -> usize {
+        self.errors.as_ref().map_or(0, |x| x.borrow().entries.len())
+    }
+
+    pub(crate) fn errors_since(&self, mark : usize) -> Vec<Rc<dyn Any>> {
+        self.errors.as_ref().map(|x| x.borrow().entries[mark..].to_vec()).unwrap_or_default()
+    }
+
+    pub(crate) fn replay_error(&self, error : Rc<dyn Any>) {
+        if let Some(errors) = &self.errors {
+            errors.borrow_mut().push(error);
+        }
+    }
+
+    pub(crate) fn discard_errors(&self, mark : usize) {
+        if let Some(errors) = &self.errors {
+            errors.borrow_mut().entries.truncate(mark);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Diagnostic;
+
+    fn statement(input : &mut Parser<char>) -> Result<char, Diagnostic> {
+        let name = *input.get::<Diagnostic>()?;
+        if !name.is_alphabetic() {
+            return Err(Diagnostic::from_kind(crate::ErrorKind::Unexpected, input.index() - 1));
+        }
+        input.expect::<Diagnostic>(&';')?;
+        Ok(name)
+    }
+
+    fn program(input : &mut Parser<char>) -> Result<Vec<char>, Diagnostic> {
+        let mut names = vec![];
+        while !input.end() {
+            match input.with_rollback(statement) {
+                Ok(name) => { names.push(name); },
+                Err(e) => {
+                    input.record_error(e);
+                    input.take_while::<Diagnostic, _>(|c| *c != ';')?;
+                    input.expect::<Diagnostic>(&';')?;
+                },
+            }
+        }
+        Ok(names)
+    }
+
+    #[test]
+    fn should_collect_every_recovered_error() {
+        let input = "a;1;bc;d;".chars().collect::<Vec<_>>();
+
+        let (names, errors) = Parser::new(&input).parse_recovering(program);
+
+        assert_eq!(names, Some(vec!['a', 'd']));
+        assert_eq!(errors.iter().map(|x| x.snapshot()).collect::<Vec<_>>(), vec!["at 2: Unexpected\n", "at 5: Unexpected\n"]);
+    }
+
+    #[test]
+    fn should_append_final_error_and_discard_rolled_back_errors() {
+        let input = "1;b".chars().collect::<Vec<_>>();
+
+        let (names, errors) = Parser::new(&input).parse_recovering(program);
+        assert_eq!(names, None);
+        assert_eq!(errors.len(), 3);
+
+        let mut buffer = Parser::new(&input);
+        buffer.enable_errors();
+        assert!(buffer.with_rollback(|input| {
+            input.record_error(());
+            input.expect::<()>(&'x').copied()
+        }).is_err());
+        assert_eq!(buffer.errors::<()>(), vec![]);
+        buffer.record_error(());
+        assert_eq!(buffer.errors::<()>(), vec![()]);
+    }
+
+    #[test]
+    #[should_panic(expected = "recorded errors must all have the same type")]
+    fn should_panic_when_recorded_errors_have_another_type() {
+        let input = "a".chars().collect::<Vec<_>>();
+
+        let _ = Parser::new(&input).parse_recovering(|input| {
+            input.record_error(1u8);
+            input.get::<Diagnostic>().copied()
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "recorded errors must all have the same type")]
+    fn should_keep_sink_type_after_errors_are_discarded() {
+        let input = "a".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        buffer.enable_errors();
+        let _ = buffer.with_rollback(|input| {
+            input.record_error(());
+            input.expect::<()>(&'x').copied()
+        });
+        buffer.record_error(1u8);
+    }
+}
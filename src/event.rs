@@ -0,0 +1,215 @@
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::{JlnError, Parser};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    Start { kind : &'static str, index : usize },
+    Token { index : usize },
+    End { kind : &'static str, index : usize },
+    Error { label : &'static str, index : usize },
+}
+
+pub trait EventSink {
+    fn event(&mut self, event : Event);
+}
+
+impl<F : FnMut(Event)> EventSink for F {
+    fn event(&mut self, event : Event) {
+        self(event)
+    }
+}
+
+pub(crate) struct Events {
+    sink : Box<dyn EventSink>,
+    pending : Vec<Event>,
+    speculation : usize,
+}
+
+pub(crate) type EventLog = Rc<RefCell<Events>>;
+
+impl<'a, T> Parser<'a, T> {
+    pub fn set_event_sink<S : EventSink + 'static>(&mut self, sink : S) {
+        self.events = Some(Rc::new(RefCell::new(Events { sink: Box::new(sink), pending: vec![], speculation: 0 })));
+    }
+
+    pub fn clear_event_sink(&mut self) {
+        self.events = None;
+    }
+
+    pub fn node<S, E : JlnError, F : FnOnce(&mut Parser<'a, T>) -> Result<S, E>>(&mut self, kind : &'static str, f : F) -> Result<S, E> {
+        if self.speculating() {
+            return self.attempt(|input| {
+                input.emit(Event::Start { kind, index: input.index });
+                let r = f(input)?;
+                input.emit(Event::End { kind, index: input.index });
+                Ok(r)
+            });
+        }
+        self.emit(Event::Start { kind, index: self.index });
+        let result = self.rollback(f);
+        if result.is_err() {
+            self.emit(Event::Error { label: kind, index: self.index });
+        }
+        self.emit(Event::End { kind, index: self.index });
+        result
+    }
+
+    pub fn emit_error(&mut self, label : &'static str) {
+        self.emit(Event::Error { label, index: self.index });
+    }
+
+    pub(crate) fn emit(&self, event : Event) {
+        if let Some(events) = &self.events {
+            let mut events = events.borrow_mut();
+            if events.speculation == 0 {
+                events.sink.event(event);
+            }
+            else {
+                events.pending.push(event);
+            }
+        }
+    }
+
+    fn speculating(&self) -> bool {
+        self.events.as_ref().is_some_and(|events| events.borrow().speculation > 0)
+    }
+
+    pub(crate) fn speculate(&self) -> Option<usize> {
+        self.events.as_ref().map(|events| {
+            let mut events = events.borrow_mut();
+            events.speculation += 1;
+            events.pending.len()
+        })
+    }
+
+    pub(crate) fn settle(&self, mark : Option<usize>, keep : bool) {
+        if let (Some(events), Some(mark)) = (&self.events, mark) {
+            let mut events = events.borrow_mut();
+            events.speculation -= 1;
+            if !keep {
+                events.pending.truncate(mark);
+            }
+            if events.speculation == 0 {
+                let Events { sink, pending, .. } = &mut *events;
+                for event in pending.drain(..) {
+                    sink.event(event);
+                }
+            }
+        }
+    }
+
+    pub(crate) fn event_mark(&self) -> Option<usize> {
+        self.events.as_ref().map(|events| events.borrow().pending.len())
+    }
+
+    pub(crate) fn events_since(&self, mark : Option<usize>) -> Vec<Event> {
+        match (&self.events, mark) {
+            (Some(events), Some(mark)) => events.borrow().pending[mark..].to_vec(),
+            _ => vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn recording(buffer : &mut Parser<char>) -> Rc<RefCell<Vec<Event>>> {
+        let events = Rc::new(RefCell::new(vec![]));
+        let sink = Rc::clone(&events);
+        buffer.set_event_sink(move |event| sink.borrow_mut().push(event));
+        events
+    }
+
+    fn letter(input : &mut Parser<char>) -> Result<char, ()> {
+        match *input.get()? {
+            c if c.is_alphabetic() => Ok(c),
+            _ => Err(()),
+        }
+    }
+
+    fn word(input : &mut Parser<char>) -> Result<String, ()> {
+        input.node("word", |input| Ok(input.list(letter)?.into_iter().collect()))
+    }
+
+    fn pair(input : &mut Parser<char>) -> Result<(String, String), ()> {
+        input.node("pair", |input| {
+            let a = word(input)?;
+            input.expect(&'=')?;
+            let b = word(input)?;
+            Ok((a, b))
+        })
+    }
+
+    fn single(input : &mut Parser<char>) -> Result<(String, String), ()> {
+        Ok((word(input)?, String::new()))
+    }
+
+    #[test]
+    fn should_emit_node_and_token_events() {
+        let input = "ab".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        let events = recording(&mut buffer);
+
+        word(&mut buffer).unwrap();
+
+        assert_eq!(*events.borrow(), vec![
+            Event::Start { kind: "word", index: 0 },
+            Event::Token { index: 0 },
+            Event::Token { index: 1 },
+            Event::End { kind: "word", index: 2 },
+        ]);
+    }
+
+    #[test]
+    fn should_discard_events_from_failed_alternatives() {
+        let input = "ab".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        let events = recording(&mut buffer);
+
+        buffer.or([pair, single]).unwrap();
+
+        assert_eq!(*events.borrow(), vec![
+            Event::Start { kind: "word", index: 0 },
+            Event::Token { index: 0 },
+            Event::Token { index: 1 },
+            Event::End { kind: "word", index: 2 },
+        ]);
+    }
+
+    #[test]
+    fn should_emit_errors_and_skip_check_events() {
+        let input = "a".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        let events = recording(&mut buffer);
+
+        assert!(buffer.check(word).unwrap());
+        buffer.emit_error("missing");
+
+        assert_eq!(*events.borrow(), vec![Event::Error { label: "missing", index: 0 }]);
+    }
+
+    #[test]
+    fn should_stream_start_before_children_finish() {
+        let input = "ab=c".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        let events = recording(&mut buffer);
+        let seen = Rc::clone(&events);
+
+        let result = buffer.node::<_, (), _>("outer", |input| {
+            assert_eq!(*seen.borrow(), vec![Event::Start { kind: "outer", index: 0 }]);
+            pair(input)?;
+            assert_eq!(seen.borrow().len(), 11);
+            input.expect::<()>(&'!')?;
+            Ok(())
+        });
+
+        assert!(result.is_err());
+        assert_eq!(buffer.index(), 0);
+        assert_eq!(events.borrow()[1], Event::Start { kind: "pair", index: 0 });
+        assert_eq!(events.borrow()[11..], [Event::Error { label: "outer", index: 0 }, Event::End { kind: "outer", index: 0 }]);
+    }
+}
@@ -0,0 +1,145 @@
+
+use crate::{ErrorKind, JlnError, Parser};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Assoc {
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone)]
+pub struct OperatorTable<K> {
+    infix : Vec<(K, u32, Assoc)>,
+}
+
+impl<K> Default for OperatorTable<K> {
+    fn default() -> Self {
+        OperatorTable { infix: vec![] }
+    }
+}
+
+impl<K : PartialEq> OperatorTable<K> {
+    pub fn new() -> Self {
+        OperatorTable::default()
+    }
+
+    pub fn insert(&mut self, op : K, precedence : u32, assoc : Assoc) {
+        match self.infix.iter_mut().find(|(x, _, _)| *x == op) {
+            Some(entry) => { *entry = (op, precedence, assoc); },
+            None => { self.infix.push((op, precedence, assoc)); },
+        }
+    }
+
+    pub fn remove(&mut self, op : &K) {
+        self.infix.retain(|(x, _, _)| x != op);
+    }
+
+    pub fn get(&self, op : &K) -> Option<(u32, Assoc)> {
+        self.infix.iter().find(|(x, _, _)| x == op).map(|(_, p, a)| (*p, *a))
+    }
+}
+
+impl<'a, T> Parser<'a, T> {
+    pub fn expression<K, S, E, O, P, B>(&mut self, table : &OperatorTable<K>, mut operand : O, mut operator : P, mut build : B) -> Result<S, E>
+        where K : PartialEq,
+              E : JlnError,
+              O : FnMut(&mut Parser<'a, T>) -> Result<S, E>,
+              P : FnMut(&mut Parser<'a, T>) -> Result<K, E>,
+              B : FnMut(S, K, S) -> S,
+    {
+        self.with_rollback(|input| input.climb(table, 0, &mut operand, &mut operator, &mut build))
+    }
+
+    fn climb<K, S, E, O, P, B>(&mut self, table : &OperatorTable<K>, min : u32, operand : &mut O, operator : &mut P, build : &mut B) -> Result<S, E>
+        where K : PartialEq,
+              E : JlnError,
+              O : FnMut(&mut Parser<'a, T>) -> Result<S, E>,
+              P : FnMut(&mut Parser<'a, T>) -> Result<K, E>,
+              B : FnMut(S, K, S) -> S,
+    {
+        let mut lhs = operand(self)?;
+        loop {
+            let next = self.option(|input| {
+                let index = input.index;
+                let op = operator(input)?;
+                match table.get(&op) {
+                    Some((precedence, assoc)) if precedence >= min => Ok((op, precedence, assoc)),
+                    _ => Err(E::from_kind(ErrorKind::Unexpected, index)),
+                }
+            })?;
+            let Some((op, precedence, assoc)) = next else { break; };
+            let next_min = match assoc {
+                Assoc::Left => precedence + 1,
+                Assoc::Right => precedence,
+            };
+            let rhs = self.climb(table, next_min, operand, operator, build)?;
+            lhs = build(lhs, op, rhs);
+        }
+        Ok(lhs)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn digit(input : &mut Parser<char>) -> Result<String, ()> {
+        match *input.get()? {
+            c if c.is_ascii_digit() => Ok(c.to_string()),
+            _ => Err(()),
+        }
+    }
+
+    fn op(input : &mut Parser<char>) -> Result<char, ()> {
+        match *input.get()? {
+            c if c.is_ascii_punctuation() => Ok(c),
+            _ => Err(()),
+        }
+    }
+
+    fn parse(table : &OperatorTable<char>, text : &str) -> Result<String, ()> {
+        let input = text.chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        buffer.expression(table, digit, op, |a, o, b| format!("({a}{o}{b})"))
+    }
+
+    #[test]
+    fn should_respect_precedence_and_associativity() {
+        let mut table = OperatorTable::new();
+        table.insert('+', 1, Assoc::Left);
+        table.insert('*', 2, Assoc::Left);
+        table.insert('^', 3, Assoc::Right);
+
+        assert_eq!(parse(&table, "1+2*3+4"), Ok("((1+(2*3))+4)".into()));
+        assert_eq!(parse(&table, "2^3^4*5"), Ok("((2^(3^4))*5)".into()));
+    }
+
+    #[test]
+    fn should_use_operators_inserted_at_runtime() {
+        let mut table = OperatorTable::new();
+        table.insert('+', 1, Assoc::Left);
+
+        assert_eq!(parse(&table, "1+2$3"), Ok("(1+2)".into()));
+
+        table.insert('$', 5, Assoc::Right);
+        assert_eq!(parse(&table, "1+2$3"), Ok("(1+(2$3))".into()));
+
+        table.insert('$', 0, Assoc::Left);
+        assert_eq!(parse(&table, "1+2$3"), Ok("((1+2)$3)".into()));
+
+        table.remove(&'$');
+        assert_eq!(table.get(&'$'), None);
+    }
+
+    #[test]
+    fn should_leave_unmatched_operator_unconsumed() {
+        let table = OperatorTable::new();
+        let input = "1+".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        let result = buffer.expression(&table, digit, op, |a, o, b| format!("({a}{o}{b})"));
+
+        assert_eq!(result, Ok("1".into()));
+        assert_eq!(buffer.index(), 1);
+    }
+}
@@ -0,0 +1,80 @@
+
+use std::rc::Rc;
+
+use crate::{ErrorKind, JlnError, Parser};
+
+impl<'a, T> Parser<'a, T> {
+    pub fn fork(&self) -> Parser<'a, T> {
+        let mut fork = Parser::from_input(self.input.clone());
+        fork.index = self.index;
+        fork.depth = self.depth;
+        fork.max_depth = self.max_depth;
+        fork.indents = self.indents.clone();
+        fork.fuel = self.fuel.clone();
+        fork.halted = Rc::clone(&self.halted);
+        fork.trivia = self.trivia;
+        fork.skip_trivia = self.skip_trivia;
+        fork.limit = self.limit;
+        fork.state = self.state.clone();
+        fork.streaming = self.streaming.clone();
+        fork.pushback = self.pushback.clone();
+        fork
+    }
+
+    pub fn adopt<E : JlnError>(&mut self, fork : Parser<'a, T>) -> Result<(), E> {
+        if fork.index < self.index || fork.index > self.bound() {
+            return Err(E::from_kind(ErrorKind::Unexpected, fork.index));
+        }
+        while self.index < fork.index {
+            self.advance();
+        }
+        self.state = fork.state;
+        self.pushback = fork.pushback;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_adopt_position_of_fork() {
+        let input = "{a{b}}c".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        buffer.expect::<()>(&'{').unwrap();
+
+        let mut scout = buffer.fork();
+        let mut depth = 1;
+        while depth > 0 {
+            match scout.get::<()>().unwrap() {
+                '{' => { depth += 1; },
+                '}' => { depth -= 1; },
+                _ => { },
+            }
+        }
+        assert_eq!(buffer.index(), 1);
+        buffer.adopt::<()>(scout).unwrap();
+        assert_eq!(buffer.get::<()>(), Ok(&'c'));
+    }
+
+    #[test]
+    fn should_share_fuel_and_reject_stale_forks() {
+        let input = vec![1, 2, 3];
+        let mut buffer = Parser::new(&input);
+        buffer.set_fuel(Some(2));
+        buffer.set_state(0usize);
+
+        let mut fork = buffer.fork();
+        fork.get::<()>().unwrap();
+        *fork.state_mut::<usize>().unwrap() += 1;
+        assert_eq!(buffer.state::<usize>(), Some(&0));
+        buffer.adopt::<()>(fork).unwrap();
+        assert_eq!(buffer.state::<usize>(), Some(&1));
+        assert_eq!(buffer.fuel(), Some(1));
+
+        let stale = Parser::new(&input).fork();
+        assert!(buffer.adopt::<()>(stale).is_err());
+        assert_eq!(buffer.index(), 1);
+    }
+}
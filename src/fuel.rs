@@ -0,0 +1,102 @@
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use crate::{ErrorKind, JlnError, Parser};
+
+impl<'a, T> Parser<'a, T> {
+    pub fn set_fuel(&mut self, fuel : Option<u64>) {
+        self.fuel = fuel.map(|x| Rc::new(Cell::new(x)));
+    }
+
+    pub fn fuel(&self) -> Option<u64> {
+        self.fuel.as_ref().map(|x| x.get())
+    }
+
+    pub(crate) fn burn<E : JlnError>(&self) -> Result<(), E> {
+        match &self.fuel {
+            Some(fuel) if fuel.get() == 0 => Err(self.raise(ErrorKind::BudgetExhausted, self.index)),
+            Some(fuel) => {
+                fuel.set(fuel.get() - 1);
+                Ok(())
+            },
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::TError;
+
+    #[test]
+    fn should_consume_fuel_on_get() {
+        let input = vec![1, 2, 3];
+        let mut buffer = Parser::new(&input);
+        buffer.set_fuel(Some(2));
+
+        assert!(buffer.get::<TError>().is_ok());
+        assert!(buffer.get::<TError>().is_ok());
+        assert_eq!(buffer.fuel(), Some(0));
+
+        let result = buffer.get::<TError>();
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().is_fatal());
+        assert_eq!(buffer.index(), 2);
+    }
+
+    #[test]
+    fn should_consume_fuel_on_backtrack() {
+        let input = vec![1, 2, 3];
+        let mut buffer = Parser::new(&input);
+        buffer.set_fuel(Some(10));
+
+        let result = buffer.list(|buffer| {
+            buffer.get::<TError>()?;
+            buffer.get::<TError>()?;
+            buffer.get::<TError>()?;
+            Err::<(), _>(TError(false))
+        });
+
+        assert!(result.is_ok());
+        assert_eq!(buffer.index(), 0);
+        assert_eq!(buffer.fuel(), Some(6));
+    }
+
+    #[test]
+    fn should_abort_unbounded_backtracking_when_exhausted() {
+        fn spin(input : &mut Parser<usize>) -> Result<(), TError> {
+            loop {
+                input.option(|input| { input.get::<TError>()?; Err::<(), _>(TError(false)) })?;
+            }
+        }
+
+        let input = vec![1];
+        let mut buffer = Parser::new(&input);
+        buffer.set_fuel(Some(100));
+
+        let result = spin(&mut buffer);
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().is_fatal());
+        assert_eq!(buffer.fuel(), Some(0));
+    }
+
+    #[test]
+    fn should_stop_option_loops_when_fuel_runs_out_for_any_error_type() {
+        let input = vec![1; 100];
+        let mut buffer = Parser::new(&input);
+        buffer.set_fuel(Some(3));
+
+        let mut rounds = 0;
+        while rounds < 1000 && buffer.option(|input| input.get::<()>().copied()).is_ok() {
+            rounds += 1;
+        }
+
+        assert_eq!(rounds, 3);
+        assert!(buffer.halted());
+        assert_eq!(buffer.list(|input| input.get::<()>().copied()), Err(()));
+    }
+}
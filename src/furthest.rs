@@ -0,0 +1,110 @@
+
+use std::panic::Location;
+
+use crate::{JlnError, Parser};
+
+pub struct Furthest<E, const N : usize> {
+    inline : [Option<(usize, E)>; N],
+    len : usize,
+    spill : Vec<(usize, E)>,
+}
+
+impl<E, const N : usize> Default for Furthest<E, N> {
+    fn default() -> Self {
+        Furthest::new()
+    }
+}
+
+impl<E, const N : usize> Furthest<E, N> {
+    pub fn new() -> Self {
+        Furthest { inline: std::array::from_fn(|_| None), len: 0, spill: vec![] }
+    }
+
+    pub fn push(&mut self, reach : usize, error : E) {
+        match self.inline.get_mut(self.len) {
+            Some(slot) => { *slot = Some((reach, error)); },
+            None => { self.spill.push((reach, error)); },
+        }
+        self.len += 1;
+    }
+
+    pub fn reach(&self) -> Option<usize> {
+        self.inline.iter().flatten().chain(&self.spill).map(|x| x.0).max()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<E : JlnError, const N : usize> Furthest<E, N> {
+    pub fn into_error(self) -> E {
+        let furthest = self.reach();
+        E::aggregate(self.inline.into_iter().flatten().chain(self.spill).filter(|x| Some(x.0) == furthest).map(|x| x.1).collect())
+    }
+}
+
+impl<'a, T> Parser<'a, T> {
+    pub fn alternative<S, E : JlnError, F : FnOnce(&mut Parser<'a, T>) -> Result<S, E>, const N : usize>(&mut self, furthest : &mut Furthest<E, N>, f : F) -> Result<Option<S>, E> {
+        let (result, reach) = self.attempt_reach(f);
+        match result {
+            Ok(value) => Ok(Some(value)),
+            Err(e) if self.fatal(&e) => Err(e),
+            Err(e) => {
+                furthest.push(reach, e);
+                Ok(None)
+            },
+        }
+    }
+
+    pub fn alternative_at<S, E : JlnError, F : FnOnce(&mut Parser<'a, T>) -> Result<S, E>, const N : usize>(&mut self, furthest : &mut Furthest<E, N>, location : &'static Location<'static>, f : F) -> Result<Option<S>, E> {
+        let branch = furthest.len();
+        self.count_alternative(location, branch, N);
+        let result = self.alternative(furthest, f);
+        if let Ok(Some(_)) = result {
+            self.cover_alternative(location, branch, N);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Diagnostic;
+
+    #[test]
+    fn should_keep_errors_of_furthest_alternatives() {
+        let input = "ab!".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        let mut furthest = Furthest::<_, 2>::new();
+
+        let short = buffer.alternative(&mut furthest, |input| input.expect::<Diagnostic>(&'x').copied());
+        let long = buffer.alternative(&mut furthest, |input| {
+            input.expect::<Diagnostic>(&'a')?;
+            input.expect::<Diagnostic>(&'b')?;
+            input.expect::<Diagnostic>(&'c').copied()
+        });
+
+        assert_eq!((short, long), (Ok(None), Ok(None)));
+        assert_eq!(buffer.index(), 0);
+        assert_eq!((furthest.len(), furthest.reach()), (2, Some(2)));
+        assert_eq!(furthest.into_error().snapshot(), "at 2: Unexpected\n");
+        assert_eq!(Furthest::<(), 1>::new().reach(), None);
+    }
+
+    #[test]
+    fn should_spill_past_inline_capacity() {
+        let mut furthest = Furthest::<Diagnostic, 1>::new();
+        furthest.push(1, Diagnostic::from_kind(crate::ErrorKind::Unexpected, 1));
+        furthest.push(3, Diagnostic::from_kind(crate::ErrorKind::Unexpected, 3));
+        furthest.push(2, Diagnostic::from_kind(crate::ErrorKind::Unexpected, 2));
+
+        assert_eq!((furthest.len(), furthest.reach()), (3, Some(3)));
+        assert_eq!(furthest.into_error().snapshot(), "at 3: Unexpected\n");
+    }
+}
@@ -0,0 +1,164 @@
+
+use std::fmt::{self, Display, Formatter, Write};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GraphNode {
+    Any,
+    Rule(&'static str),
+    Token(&'static str),
+    Pattern(&'static str),
+    Seq(Vec<GraphNode>),
+    Alt(Vec<GraphNode>),
+    Many(Box<GraphNode>),
+    Many1(Box<GraphNode>),
+    Optional(Box<GraphNode>),
+    Lookahead { positive : bool, node : Box<GraphNode> },
+}
+
+impl GraphNode {
+    fn collect_rules(&self, rules : &mut Vec<&'static str>) {
+        match self {
+            GraphNode::Rule(name) => {
+                if !rules.contains(name) {
+                    rules.push(name);
+                }
+            },
+            GraphNode::Seq(nodes) | GraphNode::Alt(nodes) => {
+                for node in nodes {
+                    node.collect_rules(rules);
+                }
+            },
+            GraphNode::Many(node) | GraphNode::Many1(node) | GraphNode::Optional(node) | GraphNode::Lookahead { node, .. } => {
+                node.collect_rules(rules);
+            },
+            GraphNode::Any | GraphNode::Token(_) | GraphNode::Pattern(_) => { },
+        }
+    }
+}
+
+impl Display for GraphNode {
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result {
+        let join = |f : &mut Formatter<'_>, nodes : &[GraphNode], separator : &str| -> fmt::Result {
+            for (i, node) in nodes.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(separator)?;
+                }
+                match node {
+                    GraphNode::Seq(_) | GraphNode::Alt(_) => write!(f, "({})", node)?,
+                    _ => write!(f, "{}", node)?,
+                }
+            }
+            Ok(())
+        };
+        let postfix = |f : &mut Formatter<'_>, node : &GraphNode, op : &str| match node {
+            GraphNode::Seq(_) | GraphNode::Alt(_) => write!(f, "({}){}", node, op),
+            _ => write!(f, "{}{}", node, op),
+        };
+        match self {
+            GraphNode::Any => f.write_str("_"),
+            GraphNode::Rule(name) => f.write_str(name),
+            GraphNode::Token(token) => f.write_str(token),
+            GraphNode::Pattern(pattern) => write!(f, "[{}]", pattern),
+            GraphNode::Seq(nodes) => join(f, nodes, " "),
+            GraphNode::Alt(nodes) => join(f, nodes, " | "),
+            GraphNode::Many(node) => postfix(f, node, "*"),
+            GraphNode::Many1(node) => postfix(f, node, "+"),
+            GraphNode::Optional(node) => postfix(f, node, "?"),
+            GraphNode::Lookahead { positive, node } => {
+                f.write_str(if *positive { "&" } else { "!" })?;
+                postfix(f, node, "")
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphRule {
+    pub name : &'static str,
+    pub output : &'static str,
+    pub body : GraphNode,
+}
+
+impl GraphRule {
+    pub fn references(&self) -> Vec<&'static str> {
+        let mut rules = vec![];
+        self.body.collect_rules(&mut rules);
+        rules
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Graph {
+    pub rules : Vec<GraphRule>,
+}
+
+impl Graph {
+    pub fn rule(&self, name : &str) -> Option<&GraphRule> {
+        self.rules.iter().find(|x| x.name == name)
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph grammar {\n    node [shape=box];\n");
+        for rule in &self.rules {
+            let label = format!("{} : {}\n{}", rule.name, rule.output, rule.body);
+            let _ = writeln!(dot, "    {} [label={}];", quote(rule.name), quote(&label));
+        }
+        for rule in &self.rules {
+            for reference in rule.references() {
+                let _ = writeln!(dot, "    {} -> {};", quote(rule.name), quote(reference));
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+fn quote(text : &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn graph() -> Graph {
+        Graph { rules: vec![
+            GraphRule { name: "list", output: "Vec<u32>", body: GraphNode::Seq(vec![
+                GraphNode::Token("'['"),
+                GraphNode::Many(Box::new(GraphNode::Alt(vec![GraphNode::Rule("item"), GraphNode::Rule("list")]))),
+                GraphNode::Token("']'"),
+            ]) },
+            GraphRule { name: "item", output: "u32", body: GraphNode::Pattern("'0' ..= '9'") },
+        ] }
+    }
+
+    #[test]
+    fn should_render_rule_bodies() {
+        let graph = graph();
+
+        assert_eq!(graph.rule("list").unwrap().body.to_string(), "'[' (item | list)* ']'");
+        assert_eq!(graph.rule("list").unwrap().references(), vec!["item", "list"]);
+        assert!(graph.rule("missing").is_none());
+    }
+
+    #[test]
+    fn should_render_dot() {
+        let dot = graph().to_dot();
+
+        assert!(dot.starts_with("digraph grammar {\n"));
+        assert!(dot.contains("    \"list\" [label=\"list : Vec<u32>\\n'[' (item | list)* ']'\"];\n"));
+        assert!(dot.contains("    \"list\" -> \"item\";\n"));
+        assert!(dot.contains("    \"list\" -> \"list\";\n"));
+        assert!(!dot.contains("\"item\" ->"));
+    }
+}
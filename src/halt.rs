@@ -0,0 +1,19 @@
+
+use crate::{ErrorKind, JlnError, Parser};
+
+impl<'a, T> Parser<'a, T> {
+    pub fn halted(&self) -> bool {
+        self.halted.get()
+    }
+
+    pub(crate) fn fatal<E : JlnError>(&self, error : &E) -> bool {
+        error.is_fatal() || self.halted.get()
+    }
+
+    pub(crate) fn raise<E : JlnError>(&self, kind : ErrorKind, index : usize) -> E {
+        if kind.is_fatal() {
+            self.halted.set(true);
+        }
+        E::from_kind(kind, index)
+    }
+}
@@ -0,0 +1,120 @@
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::Parser;
+
+type TokenHook<T> = Box<dyn FnMut(usize, &T)>;
+type EnterHook = Box<dyn FnMut(&'static str, usize)>;
+type ExitHook = Box<dyn FnMut(&'static str, usize, bool)>;
+
+pub(crate) struct Hooks<T> {
+    token : Vec<TokenHook<T>>,
+    enter : Vec<EnterHook>,
+    exit : Vec<ExitHook>,
+}
+
+pub(crate) type HookRegistry<T> = Rc<RefCell<Hooks<T>>>;
+
+impl<'a, T> Parser<'a, T> {
+    pub fn on_token<F : FnMut(usize, &T) + 'static>(&mut self, hook : F) {
+        self.hooks().borrow_mut().token.push(Box::new(hook));
+    }
+
+    pub fn on_rule_enter<F : FnMut(&'static str, usize) + 'static>(&mut self, hook : F) {
+        self.hooks().borrow_mut().enter.push(Box::new(hook));
+    }
+
+    pub fn on_rule_exit<F : FnMut(&'static str, usize, bool) + 'static>(&mut self, hook : F) {
+        self.hooks().borrow_mut().exit.push(Box::new(hook));
+    }
+
+    pub fn clear_hooks(&mut self) {
+        self.hooks = None;
+    }
+
+    fn hooks(&mut self) -> &HookRegistry<T> {
+        self.hooks.get_or_insert_with(|| Rc::new(RefCell::new(Hooks { token: vec![], enter: vec![], exit: vec![] })))
+    }
+
+    pub(crate) fn hook_token(&self) {
+        if let Some(hooks) = &self.hooks {
+            for hook in &mut hooks.borrow_mut().token {
+                hook(self.index, &self.input[self.index]);
+            }
+        }
+    }
+
+    pub(crate) fn hook_enter(&self, rule : &'static str) {
+        if let Some(hooks) = &self.hooks {
+            for hook in &mut hooks.borrow_mut().enter {
+                hook(rule, self.index);
+            }
+        }
+    }
+
+    pub(crate) fn hook_exit(&self, rule : &'static str, success : bool) {
+        if let Some(hooks) = &self.hooks {
+            for hook in &mut hooks.borrow_mut().exit {
+                hook(rule, self.index, success);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::TError;
+
+    fn letter(input : &mut Parser<char>) -> Result<char, TError> {
+        input.context("letter", |input| input.satisfy(|c : &char| c.is_alphabetic()).copied())
+    }
+
+    fn word(input : &mut Parser<char>) -> Result<String, TError> {
+        input.context("word", |input| Ok(input.list(letter)?.into_iter().collect()))
+    }
+
+    #[test]
+    fn should_invoke_hooks_on_tokens_and_rules() {
+        let input = "ab".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        let log = Rc::new(RefCell::new(vec![]));
+
+        let sink = Rc::clone(&log);
+        buffer.on_token(move |index, c| sink.borrow_mut().push(format!("token {} {}", index, c)));
+        let sink = Rc::clone(&log);
+        buffer.on_rule_enter(move |rule, index| sink.borrow_mut().push(format!("enter {} {}", rule, index)));
+        let sink = Rc::clone(&log);
+        buffer.on_rule_exit(move |rule, index, ok| sink.borrow_mut().push(format!("exit {} {} {}", rule, index, ok)));
+
+        word(&mut buffer).map_err(|_| ()).unwrap();
+
+        assert_eq!(*log.borrow(), vec![
+            "enter word 0",
+            "enter letter 0",
+            "token 0 a",
+            "exit letter 1 true",
+            "enter letter 1",
+            "token 1 b",
+            "exit letter 2 true",
+            "enter letter 2",
+            "exit letter 2 false",
+            "exit word 2 true",
+        ]);
+    }
+
+    #[test]
+    fn should_clear_hooks() {
+        let input = "a".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        let count = Rc::new(RefCell::new(0));
+
+        let sink = Rc::clone(&count);
+        buffer.on_token(move |_, _| *sink.borrow_mut() += 1);
+        buffer.clear_hooks();
+        letter(&mut buffer).map_err(|_| ()).unwrap();
+
+        assert_eq!(*count.borrow(), 0);
+    }
+}
@@ -0,0 +1,222 @@
+
+use crate::{ErrorKind, JlnError, Parser, Span};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestLine {
+    pub method : String,
+    pub target : String,
+    pub version : (u8, u8),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusLine {
+    pub version : (u8, u8),
+    pub code : u16,
+    pub reason : Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Header {
+    pub name : String,
+    pub value : Vec<u8>,
+    pub span : Span,
+}
+
+fn token(b : u8) -> bool {
+    b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b)
+}
+
+fn blank(b : u8) -> bool {
+    b == b' ' || b == b'\t'
+}
+
+impl<'a> Parser<'a, u8> {
+    pub fn request_line<E : JlnError>(&mut self) -> Result<RequestLine, E> {
+        self.with_rollback(|input| input.no_trivia(|input| {
+            let method = input.http_token()?;
+            input.expect::<E>(&b' ')?;
+            let start = input.index;
+            let target = input.take_while::<E, _>(|b| b.is_ascii_graphic())?.iter().map(|b| *b as char).collect::<String>();
+            if target.is_empty() {
+                return Err(E::from_kind(ErrorKind::Unexpected, start));
+            }
+            input.expect::<E>(&b' ')?;
+            let version = input.http_version()?;
+            input.http_newline()?;
+            Ok(RequestLine { method, target, version })
+        }))
+    }
+
+    pub fn status_line<E : JlnError>(&mut self) -> Result<StatusLine, E> {
+        self.with_rollback(|input| input.no_trivia(|input| {
+            let version = input.http_version()?;
+            input.expect::<E>(&b' ')?;
+            let start = input.index;
+            let mut code = 0;
+            for _ in 0..3 {
+                match *input.get::<E>()? {
+                    b @ b'0'..=b'9' => { code = code * 10 + (b - b'0') as u16; },
+                    _ => { return Err(E::from_kind(ErrorKind::InvalidNumber, start)); },
+                }
+            }
+            input.expect::<E>(&b' ')?;
+            let reason = input.take_while::<E, _>(|b| *b != b'\r' && *b != b'\n')?.to_vec();
+            input.http_newline()?;
+            Ok(StatusLine { version, code, reason })
+        }))
+    }
+
+    pub fn header_field<E : JlnError>(&mut self) -> Result<Header, E> {
+        self.with_rollback(|input| input.no_trivia(|input| {
+            let start = input.index;
+            let name = input.http_token()?;
+            input.expect::<E>(&b':')?;
+            let mut value = vec![];
+            loop {
+                input.take_while::<E, _>(|b| blank(*b))?;
+                if !value.is_empty() {
+                    value.push(b' ');
+                }
+                value.extend_from_slice(input.take_while::<E, _>(|b| *b != b'\r' && *b != b'\n')?);
+                while value.last().is_some_and(|b| blank(*b)) {
+                    value.pop();
+                }
+                let end = input.index;
+                input.http_newline()?;
+                match input.peek::<E>() {
+                    Ok(b) if blank(*b) => { },
+                    Err(e) if input.incomplete() => { return Err(e); },
+                    _ => { return Ok(Header { name, value, span: Span::new(start, end) }); },
+                }
+            }
+        }))
+    }
+
+    pub fn headers<E : JlnError>(&mut self) -> Result<Vec<Header>, E> {
+        self.with_rollback(|input| {
+            let mut headers = vec![];
+            loop {
+                if matches!(input.peek::<E>()?, b'\r' | b'\n') {
+                    input.http_newline()?;
+                    return Ok(headers);
+                }
+                headers.push(input.header_field()?);
+            }
+        })
+    }
+
+    pub fn request_head<E : JlnError>(&mut self) -> Result<(RequestLine, Vec<Header>), E> {
+        self.with_rollback(|input| Ok((input.request_line()?, input.headers()?)))
+    }
+
+    pub fn chunk_size<E : JlnError>(&mut self) -> Result<u64, E> {
+        self.with_rollback(|input| input.no_trivia(|input| {
+            let start = input.index;
+            let digits = input.take_while::<E, _>(|b| b.is_ascii_hexdigit())?;
+            if digits.is_empty() {
+                return Err(E::from_kind(ErrorKind::InvalidNumber, start));
+            }
+            let size = digits.iter().try_fold(0u64, |acc, b| acc.checked_mul(16)?.checked_add((*b as char).to_digit(16)? as u64));
+            let size = size.ok_or_else(|| E::from_kind(ErrorKind::Overflow, start))?;
+            input.take_while::<E, _>(|b| blank(*b))?;
+            if *input.peek::<E>()? == b';' {
+                input.take_while::<E, _>(|b| *b != b'\r' && *b != b'\n')?;
+            }
+            input.http_newline()?;
+            Ok(size)
+        }))
+    }
+
+    fn http_token<E : JlnError>(&mut self) -> Result<String, E> {
+        let start = self.index;
+        let name = self.take_while::<E, _>(|b| token(*b))?;
+        if name.is_empty() {
+            self.peek::<E>()?;
+            return Err(E::from_kind(ErrorKind::Unexpected, start));
+        }
+        Ok(name.iter().map(|b| *b as char).collect())
+    }
+
+    fn http_version<E : JlnError>(&mut self) -> Result<(u8, u8), E> {
+        for b in b"HTTP/" {
+            self.expect::<E>(b)?;
+        }
+        let major = self.http_digit()?;
+        self.expect::<E>(&b'.')?;
+        let minor = self.http_digit()?;
+        Ok((major, minor))
+    }
+
+    fn http_digit<E : JlnError>(&mut self) -> Result<u8, E> {
+        let b = *self.peek::<E>()?;
+        if !b.is_ascii_digit() {
+            return Err(E::from_kind(ErrorKind::Unexpected, self.index));
+        }
+        self.get::<E>()?;
+        Ok(b - b'0')
+    }
+
+    fn http_newline<E : JlnError>(&mut self) -> Result<(), E> {
+        if *self.peek::<E>()? == b'\r' {
+            self.get::<E>()?;
+        }
+        self.expect::<E>(&b'\n')?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Diagnostic, StreamBuffer};
+
+    #[test]
+    fn should_parse_request_head() {
+        let input = b"GET /a?b=1 HTTP/1.1\r\nHost: example.com \r\nX-Long: one\r\n  two\r\n\r\nbody".to_vec();
+        let mut buffer = Parser::new(&input);
+
+        let (line, headers) = buffer.request_head::<()>().unwrap();
+
+        assert_eq!(line, RequestLine { method: "GET".into(), target: "/a?b=1".into(), version: (1, 1) });
+        assert_eq!(headers[0], Header { name: "Host".into(), value: b"example.com".to_vec(), span: Span::new(21, 39) });
+        assert_eq!(headers[1].value, b"one two".to_vec());
+        assert_eq!(buffer.index(), input.len() - 4);
+        assert_eq!(Parser::new(b"A: b\r\n").header_field::<()>().map(|x| x.value), Ok(b"b".to_vec()));
+    }
+
+    #[test]
+    fn should_parse_status_and_chunk_lines() {
+        let input = b"HTTP/1.0 404 Not Found\n1aF;ext=1\r\n0\r\n".to_vec();
+        let mut buffer = Parser::new(&input);
+
+        let status = buffer.status_line::<()>().unwrap();
+        assert_eq!((status.version, status.code, status.reason), ((1, 0), 404, b"Not Found".to_vec()));
+        assert_eq!(buffer.chunk_size::<()>(), Ok(0x1af));
+        assert_eq!(buffer.chunk_size::<()>(), Ok(0));
+    }
+
+    #[test]
+    fn should_wait_for_more_input_when_streaming() {
+        let mut stream = StreamBuffer::new();
+        stream.feed(b"POST / HTTP/1.1\r\nContent-Length: 3\r\n".iter().copied());
+
+        assert_eq!(stream.next(|input| input.request_head::<Diagnostic>()), Ok(None));
+        stream.feed(b"\r\n".iter().copied());
+        let (line, headers) = stream.next(|input| input.request_head::<Diagnostic>()).unwrap().unwrap();
+        assert_eq!(line.method, "POST");
+        assert_eq!(headers.len(), 1);
+
+        stream.feed(b"fff".iter().copied());
+        assert_eq!(stream.next(|input| input.chunk_size::<Diagnostic>()), Ok(None));
+    }
+
+    #[test]
+    fn should_report_malformed_lines() {
+        let parse = |text : &[u8]| Parser::new(text).request_line::<Diagnostic>().unwrap_err().snapshot();
+
+        assert_eq!(parse(b" / HTTP/1.1\r\n"), "at 0: Unexpected\n");
+        assert_eq!(parse(b"GET / HTTP/x.1\r\n"), "at 11: Unexpected\n");
+        let status = Parser::new(b"HTTP/1.1 2x0 OK\r\n").status_line::<Diagnostic>().unwrap_err();
+        assert_eq!(status.snapshot(), "at 9: InvalidNumber\n");
+    }
+}
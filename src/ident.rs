@@ -0,0 +1,167 @@
+
+use crate::{ErrorKind, JlnError, Parser, TextToken};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Word<K> {
+    Keyword(K),
+    Ident(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct KeywordTable<K> {
+    keywords : Vec<(String, K)>,
+}
+
+impl<K> Default for KeywordTable<K> {
+    fn default() -> Self {
+        KeywordTable { keywords: vec![] }
+    }
+}
+
+impl<K : Clone> KeywordTable<K> {
+    pub fn new() -> Self {
+        KeywordTable::default()
+    }
+
+    pub fn insert(&mut self, word : &str, keyword : K) {
+        match self.keywords.iter_mut().find(|(x, _)| x == word) {
+            Some(entry) => { entry.1 = keyword; },
+            None => { self.keywords.push((word.to_string(), keyword)); },
+        }
+    }
+
+    pub fn remove(&mut self, word : &str) {
+        self.keywords.retain(|(x, _)| x != word);
+    }
+
+    pub fn get(&self, word : &str) -> Option<K> {
+        self.keywords.iter().find(|(x, _)| x == word).map(|(_, k)| k.clone())
+    }
+}
+
+pub fn ident_start(c : char) -> bool {
+    c == '_' || c.is_alphabetic()
+}
+
+pub fn ident_continue(c : char) -> bool {
+    c == '_' || c.is_alphanumeric()
+}
+
+impl<'a, T : TextToken> Parser<'a, T> {
+    pub fn identifier<E : JlnError>(&mut self, start : fn(char) -> bool, cont : fn(char) -> bool) -> Result<String, E> {
+        self.with_rollback(|input| {
+            input.consume_trivia();
+            input.no_trivia(|input| {
+                let at = input.index;
+                let first = T::decode::<E>(input)?;
+                if !start(first) {
+                    return Err(E::from_kind(ErrorKind::Unexpected, at));
+                }
+                let mut word = String::from(first);
+                while let Some((c, probe)) = input.peek_text::<E>()? {
+                    if !cont(c) {
+                        break;
+                    }
+                    input.adopt::<E>(probe)?;
+                    word.push(c);
+                }
+                Ok(word)
+            })
+        })
+    }
+
+    pub fn word<K : Clone, E : JlnError>(&mut self, table : &KeywordTable<K>, start : fn(char) -> bool, cont : fn(char) -> bool) -> Result<Word<K>, E> {
+        let word = self.identifier(start, cont)?;
+        match table.get(&word) {
+            Some(keyword) => Ok(Word::Keyword(keyword)),
+            None => Ok(Word::Ident(word)),
+        }
+    }
+
+    pub fn keyword<E : JlnError>(&mut self, keyword : &str, cont : fn(char) -> bool) -> Result<(), E> {
+        let start = self.next_significant();
+        self.with_rollback(|input| {
+            input.tag_str::<E>(keyword)?;
+            match input.no_trivia(|input| input.peek_text::<E>())? {
+                Some((c, _)) if cont(c) => Err(E::from_kind(ErrorKind::Unexpected, start)),
+                _ => Ok(()),
+            }
+        })
+    }
+}
+
+impl<'a, T : TextToken> Parser<'a, T> {
+    fn peek_text<E : JlnError>(&self) -> Result<Option<(char, Parser<'a, T>)>, E> {
+        let mut probe = self.fork();
+        match T::decode::<E>(&mut probe) {
+            Ok(c) => Ok(Some((c, probe))),
+            Err(e) if self.fatal(&e) => Err(e),
+            Err(_) => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Kw {
+        If,
+        Else,
+    }
+
+    fn table() -> KeywordTable<Kw> {
+        let mut table = KeywordTable::new();
+        table.insert("if", Kw::If);
+        table.insert("else", Kw::Else);
+        table
+    }
+
+    #[test]
+    fn should_distinguish_keywords_from_identifiers() {
+        let input = "if if_x else".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        buffer.set_trivia(Some(|c| *c == ' '));
+        let table = table();
+
+        assert_eq!(buffer.word::<_, ()>(&table, ident_start, ident_continue), Ok(Word::Keyword(Kw::If)));
+        assert_eq!(buffer.word::<_, ()>(&table, ident_start, ident_continue), Ok(Word::Ident(String::from("if_x"))));
+        assert_eq!(buffer.word::<_, ()>(&table, ident_start, ident_continue), Ok(Word::Keyword(Kw::Else)));
+        assert!(buffer.end());
+    }
+
+    #[test]
+    fn should_reject_identifier_with_bad_start() {
+        let input = b"9abc".to_vec();
+        let mut buffer = Parser::new(&input);
+
+        assert!(buffer.identifier::<()>(ident_start, ident_continue).is_err());
+        assert_eq!(buffer.index(), 0);
+    }
+
+    #[test]
+    fn should_decode_utf8_identifiers_on_bytes() {
+        let input = "é_1 ö".as_bytes().to_vec();
+        let mut buffer = Parser::new(&input);
+
+        assert_eq!(buffer.identifier::<()>(ident_start, ident_continue), Ok(String::from("é_1")));
+        assert_eq!(buffer.index(), 4);
+        assert!(Parser::new(&[0xC3]).identifier::<()>(ident_start, ident_continue).is_err());
+        assert!(Parser::new("ifé".as_bytes()).keyword::<()>("if", ident_continue).is_err());
+        assert_eq!(Parser::new(&b"if\xff"[..]).keyword::<()>("if", ident_continue), Ok(()));
+    }
+
+    #[test]
+    fn should_match_keyword_only_at_word_boundary() {
+        let input = "if_x if(".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        buffer.set_trivia(Some(|c| *c == ' '));
+
+        assert!(buffer.keyword::<()>("if", ident_continue).is_err());
+        assert_eq!(buffer.index(), 0);
+        assert_eq!(buffer.identifier::<()>(ident_start, ident_continue), Ok(String::from("if_x")));
+        assert_eq!(buffer.keyword::<()>("if", ident_continue), Ok(()));
+        assert_eq!(buffer.expect::<()>(&'('), Ok(&'('));
+    }
+}
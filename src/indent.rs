@@ -0,0 +1,167 @@
+
+use crate::{ErrorKind, JlnError, Parser};
+
+impl<'a> Parser<'a, char> {
+    pub fn column(&self) -> usize {
+        self.input[..self.index].iter().rev().take_while(|c| **c != '\n').count()
+    }
+
+    pub fn indent_level(&self) -> usize {
+        self.indents.last().copied().unwrap_or(0)
+    }
+
+    pub fn indent<E : JlnError>(&mut self) -> Result<usize, E> {
+        let level = self.indent_level();
+        let column = self.column();
+        if column > level {
+            self.indents.push(column);
+            Ok(column)
+        }
+        else {
+            Err(E::from_kind(ErrorKind::Indentation { level, column }, self.index))
+        }
+    }
+
+    pub fn dedent<E : JlnError>(&mut self) -> Result<(), E> {
+        let level = self.indent_level();
+        let column = self.column();
+        if column < level {
+            self.indents.pop();
+            Ok(())
+        }
+        else {
+            Err(E::from_kind(ErrorKind::Indentation { level, column }, self.index))
+        }
+    }
+
+    pub fn same_level<E : JlnError>(&mut self) -> Result<(), E> {
+        let level = self.indent_level();
+        let column = self.column();
+        if column == level {
+            Ok(())
+        }
+        else {
+            Err(E::from_kind(ErrorKind::Indentation { level, column }, self.index))
+        }
+    }
+
+    pub fn skip_layout(&mut self) {
+        while self.index < self.bound() && self.input[self.index].is_whitespace() {
+            self.advance();
+        }
+    }
+
+    pub fn indented_block<S, E : JlnError, F : FnMut(&mut Parser<'a, char>) -> Result<S, E>>(&mut self, mut f : F) -> Result<Vec<S>, E> {
+        self.with_rollback(|input| {
+            input.skip_layout();
+            let column = input.indent()?;
+            let mut items = vec![f(input)?];
+            while input.check(|input| {
+                input.skip_layout();
+                if input.end() || input.column() < column {
+                    Err(E::from_kind(ErrorKind::Indentation { level: column, column: input.column() }, input.index))
+                }
+                else {
+                    Ok(())
+                }
+            })? {
+                items.push(input.with_rollback(|input| {
+                    input.skip_layout();
+                    input.same_level()?;
+                    f(input)
+                })?);
+            }
+            input.indents.pop();
+            Ok(items)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::TError;
+
+    fn word(input : &mut Parser<char>) -> Result<String, TError> {
+        let word = input.list(|input| match input.get::<TError>()? {
+            c if c.is_alphanumeric() => Ok(*c),
+            _ => Err(TError(false)),
+        })?;
+        if word.is_empty() { Err(TError(false)) } else { Ok(word.into_iter().collect()) }
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Tree {
+        Leaf(String),
+        Node(String, Vec<Tree>),
+    }
+
+    fn tree(input : &mut Parser<char>) -> Result<Tree, TError> {
+        let name = word(input)?;
+        if input.option(|input| match input.get::<TError>()? { ':' => Ok(()), _ => Err(TError(false)) })?.is_some() {
+            Ok(Tree::Node(name, input.indented_block(tree)?))
+        }
+        else {
+            Ok(Tree::Leaf(name))
+        }
+    }
+
+    #[test]
+    fn should_report_column() {
+        let input = "ab\n  cd".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        assert_eq!(buffer.column(), 0);
+        for _ in 0..5 {
+            buffer.get::<()>().unwrap();
+        }
+        assert_eq!(buffer.column(), 2);
+    }
+
+    #[test]
+    fn should_parse_nested_blocks() {
+        let input = "a:\n  b\n  c:\n    d\n  e\nf".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        let result = buffer.list(|input| { input.skip_layout(); input.same_level()?; tree(input) }).unwrap();
+
+        assert_eq!(result, vec![
+            Tree::Node("a".into(), vec![
+                Tree::Leaf("b".into()),
+                Tree::Node("c".into(), vec![Tree::Leaf("d".into())]),
+                Tree::Leaf("e".into()),
+            ]),
+            Tree::Leaf("f".into()),
+        ]);
+        assert_eq!(buffer.indent_level(), 0);
+        assert!(buffer.end());
+    }
+
+    #[test]
+    fn should_reject_unexpected_indent_in_block() {
+        let input = "a:\n  b\n    c".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        let result = buffer.with_rollback(tree);
+
+        assert!(result.is_err());
+        assert_eq!(buffer.index(), 0);
+        assert_eq!(buffer.indent_level(), 0);
+    }
+
+    #[test]
+    fn should_indent_and_dedent() {
+        let input = "a\n  b\nc".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        assert!(buffer.indent::<()>().is_err());
+        buffer.get::<()>().unwrap();
+        buffer.skip_layout();
+        assert_eq!(buffer.indent::<()>(), Ok(2));
+        buffer.get::<()>().unwrap();
+        buffer.skip_layout();
+        assert!(buffer.same_level::<()>().is_err());
+        assert!(buffer.dedent::<()>().is_ok());
+        assert!(buffer.same_level::<()>().is_ok());
+    }
+}
@@ -0,0 +1,186 @@
+
+use crate::{ErrorKind, JlnError, Parser, Span};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IniEntry {
+    pub key : String,
+    pub key_span : Span,
+    pub value : String,
+    pub value_span : Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IniSection {
+    pub name : String,
+    pub span : Span,
+    pub entries : Vec<IniEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ini {
+    pub sections : Vec<IniSection>,
+}
+
+impl IniSection {
+    pub fn get(&self, key : &str) -> Option<&IniEntry> {
+        self.entries.iter().find(|x| x.key == key)
+    }
+
+    fn insert(&mut self, entry : IniEntry) {
+        match self.entries.iter_mut().find(|x| x.key == entry.key) {
+            Some(existing) => { *existing = entry; },
+            None => { self.entries.push(entry); },
+        }
+    }
+}
+
+impl Ini {
+    pub fn section(&self, name : &str) -> Option<&IniSection> {
+        self.sections.iter().find(|x| x.name == name)
+    }
+
+    pub fn get(&self, section : &str, key : &str) -> Option<&str> {
+        self.section(section)?.get(key).map(|x| x.value.as_str())
+    }
+}
+
+fn blank(c : char) -> bool {
+    c == ' ' || c == '\t'
+}
+
+impl<'a> Parser<'a, char> {
+    pub fn ini<E : JlnError>(&mut self) -> Result<Ini, E> {
+        self.with_rollback(|input| input.no_trivia(|input| {
+            let mut sections = vec![IniSection { name: String::new(), span: Span::new(input.index, input.index), entries: vec![] }];
+            let mut current = 0;
+            while !input.end() {
+                input.take_while::<E, _>(|c| blank(*c))?;
+                match input.peek::<E>().ok().copied() {
+                    None => { },
+                    Some('\n' | '\r') => { },
+                    Some(';' | '#') => { input.take_while::<E, _>(|c| *c != '\n')?; },
+                    Some('[') => {
+                        let (name, span) = input.ini_header()?;
+                        current = match sections.iter().position(|x| x.name == name) {
+                            Some(index) => index,
+                            None => {
+                                sections.push(IniSection { name, span, entries: vec![] });
+                                sections.len() - 1
+                            },
+                        };
+                    },
+                    Some(_) => {
+                        let entry = input.ini_entry()?;
+                        sections[current].insert(entry);
+                    },
+                }
+                input.ini_line_end()?;
+            }
+            Ok(Ini { sections })
+        }))
+    }
+
+    fn ini_header<E : JlnError>(&mut self) -> Result<(String, Span), E> {
+        let start = self.index;
+        self.get::<E>()?;
+        let name = self.take_while::<E, _>(|c| *c != ']' && *c != '\n')?.iter().collect::<String>();
+        if self.peek::<E>().ok() != Some(&']') {
+            return Err(self.raise(ErrorKind::Unterminated, start));
+        }
+        self.get::<E>()?;
+        let span = Span::new(start, self.index);
+        self.take_while::<E, _>(|c| blank(*c))?;
+        if let Ok(';' | '#') = self.peek::<E>().copied() {
+            self.take_while::<E, _>(|c| *c != '\n')?;
+        }
+        Ok((name.trim().to_string(), span))
+    }
+
+    fn ini_entry<E : JlnError>(&mut self) -> Result<IniEntry, E> {
+        let start = self.index;
+        let key = self.take_while::<E, _>(|c| !matches!(c, '=' | ':' | '\n' | '\r'))?.iter().collect::<String>();
+        let key_span = Span::new(start, start + key.trim_end().chars().count());
+        if key.trim().is_empty() || !matches!(self.peek::<E>().ok(), Some('=' | ':')) {
+            return Err(E::from_kind(ErrorKind::Unexpected, self.index));
+        }
+        self.get::<E>()?;
+        self.take_while::<E, _>(|c| blank(*c))?;
+        let value_start = self.index;
+        let mut value_end = self.index;
+        let mut value = String::new();
+        loop {
+            let line = self.take_while::<E, _>(|c| *c != '\n' && *c != '\r')?.iter().collect::<String>();
+            let trimmed = line.trim_end();
+            match trimmed.strip_suffix('\\') {
+                Some(head) => {
+                    value.push_str(head);
+                    value_end = self.index - (line.chars().count() - head.chars().count());
+                    self.ini_line_end()?;
+                    self.take_while::<E, _>(|c| blank(*c))?;
+                },
+                None => {
+                    value.push_str(trimmed);
+                    if !trimmed.is_empty() {
+                        value_end = self.index - (line.chars().count() - trimmed.chars().count());
+                    }
+                    break;
+                },
+            }
+        }
+        Ok(IniEntry { key: key.trim_end().to_string(), key_span, value, value_span: Span::new(value_start, value_end) })
+    }
+
+    fn ini_line_end<E : JlnError>(&mut self) -> Result<(), E> {
+        self.option(|input| input.expect::<E>(&'\r').map(|_| ()))?;
+        match self.peek::<E>() {
+            Ok('\n') => {
+                self.get::<E>()?;
+                Ok(())
+            },
+            Ok(_) => Err(E::from_kind(ErrorKind::Unexpected, self.index)),
+            Err(_) => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Diagnostic;
+
+    fn parse(text : &str) -> Result<Ini, String> {
+        let input = text.chars().collect::<Vec<_>>();
+        Parser::new(&input).ini::<Diagnostic>().map_err(|e| e.snapshot())
+    }
+
+    #[test]
+    fn should_parse_sections_in_order() {
+        let ini = parse("top = 1\n; comment\n[server] # main\nhost=a\r\nport : 80\n\n[client]\nname = x y  \n[server]\nhost = b\n").unwrap();
+
+        assert_eq!(ini.sections.iter().map(|x| x.name.as_str()).collect::<Vec<_>>(), vec!["", "server", "client"]);
+        assert_eq!(ini.get("", "top"), Some("1"));
+        assert_eq!(ini.get("client", "name"), Some("x y"));
+        let server = ini.section("server").unwrap();
+        assert_eq!(server.entries.iter().map(|x| (x.key.as_str(), x.value.as_str())).collect::<Vec<_>>(), vec![("host", "b"), ("port", "80")]);
+        assert_eq!(server.span, Span::new(18, 26));
+    }
+
+    #[test]
+    fn should_join_continued_values_with_spans() {
+        let ini = parse("  path = /usr/bin:\\\n    /bin \nempty=\n").unwrap();
+        let path = ini.sections[0].get("path").unwrap();
+
+        assert_eq!(path.value, "/usr/bin:/bin");
+        assert_eq!(path.key_span, Span::new(2, 6));
+        assert_eq!(path.value_span, Span::new(9, 28));
+        assert_eq!(ini.get("", "empty"), Some(""));
+        assert_eq!(ini.sections[0].get("empty").unwrap().value_span, Span::new(36, 36));
+    }
+
+    #[test]
+    fn should_report_malformed_lines() {
+        assert_eq!(parse("[open\nkey=1"), Err("at 0: Unterminated\n".into()));
+        assert_eq!(parse("a=1\nnovalue\n"), Err("at 11: Unexpected\n".into()));
+        assert_eq!(parse("[s] x\n"), Err("at 4: Unexpected\n".into()));
+    }
+}
@@ -0,0 +1,88 @@
+
+use crate::{JlnError, Parser, Span};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Islands<S> {
+    pub islands : Vec<(S, Span)>,
+    pub gaps : Vec<Span>,
+}
+
+impl<'a, T> Parser<'a, T> {
+    pub fn islands<S, E : JlnError, F : FnMut(&mut Parser<'a, T>) -> Result<S, E>>(&mut self, mut rule : F) -> Result<Islands<S>, E> {
+        let mut islands = vec![];
+        let mut gaps = vec![];
+        let mut gap = self.index;
+        while self.index < self.bound() {
+            let start = self.index;
+            match self.attempt(|input| input.spanned(&mut rule)) {
+                Ok((value, span)) if span.end > start => {
+                    if gap < start {
+                        gaps.push(Span::new(gap, start));
+                    }
+                    islands.push((value, span));
+                    gap = self.index;
+                    continue;
+                },
+                Ok(_) => {
+                    self.index = start;
+                },
+                Err(e) if self.fatal(&e) => { return Err(e); },
+                Err(_) => { },
+            }
+            self.no_trivia(|input| input.get::<E>().map(|_| ()))?;
+        }
+        if gap < self.index {
+            gaps.push(Span::new(gap, self.index));
+        }
+        Ok(Islands { islands, gaps })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn number(input : &mut Parser<char>) -> Result<u32, ()> {
+        input.integer()
+    }
+
+    #[test]
+    fn should_collect_islands_and_gaps() {
+        let input = "id=12 and 7!".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        let result = buffer.islands(number).unwrap();
+
+        assert_eq!(result.islands, vec![(12, Span::new(3, 5)), (7, Span::new(10, 11))]);
+        assert_eq!(result.gaps, vec![Span::new(0, 3), Span::new(5, 10), Span::new(11, 12)]);
+        assert!(buffer.end());
+    }
+
+    #[test]
+    fn should_resynchronize_after_island() {
+        fn pair(input : &mut Parser<char>) -> Result<(char, char), ()> {
+            let a = *input.satisfy(|c : &char| c.is_alphabetic())?;
+            let b = *input.satisfy(|c : &char| c.is_alphabetic())?;
+            Ok((a, b))
+        }
+
+        let input = "abc".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        let result = buffer.islands(pair).unwrap();
+
+        assert_eq!(result.islands, vec![(('a', 'b'), Span::new(0, 2))]);
+        assert_eq!(result.gaps, vec![Span::new(2, 3)]);
+    }
+
+    #[test]
+    fn should_skip_empty_matches() {
+        let input = "ab".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        let result = buffer.islands(|_| Ok::<(), ()>(())).unwrap();
+
+        assert!(result.islands.is_empty());
+        assert_eq!(result.gaps, vec![Span::new(0, 2)]);
+    }
+}
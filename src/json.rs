@@ -0,0 +1,245 @@
+
+use crate::{Diagnostic, ErrorKind, JlnError, Parser, Span, StringStyle, TextToken};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<Member>),
+    Invalid,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Json {
+    pub value : Value,
+    pub span : Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Member {
+    pub key : String,
+    pub key_span : Span,
+    pub value : Json,
+}
+
+impl Json {
+    pub fn get(&self, key : &str) -> Option<&Json> {
+        match &self.value {
+            Value::Object(members) => members.iter().find(|x| x.key == key).map(|x| &x.value),
+            _ => None,
+        }
+    }
+
+    pub fn index(&self, index : usize) -> Option<&Json> {
+        match &self.value {
+            Value::Array(items) => items.get(index),
+            _ => None,
+        }
+    }
+}
+
+pub fn parse(text : &str) -> Result<Json, Diagnostic> {
+    let input = text.chars().collect::<Vec<_>>();
+    document(&mut Parser::new(&input))
+}
+
+pub fn value<T : TextToken, E : JlnError>(input : &mut Parser<'_, T>) -> Result<Json, E> {
+    element(input, &mut None)
+}
+
+pub fn document<T : TextToken, E : JlnError>(input : &mut Parser<'_, T>) -> Result<Json, E> {
+    input.with_rollback(|input| {
+        let json = value(input)?;
+        finish(input)?;
+        Ok(json)
+    })
+}
+
+pub fn document_recovering<T : TextToken, E : JlnError>(input : &mut Parser<'_, T>) -> Result<(Json, Vec<E>), E> {
+    let mut errors = vec![];
+    let json = element(input, &mut Some(&mut errors))?;
+    finish(input)?;
+    Ok((json, errors))
+}
+
+fn finish<T : TextToken, E : JlnError>(input : &mut Parser<'_, T>) -> Result<(), E> {
+    whitespace(input)?;
+    if input.end() {
+        Ok(())
+    }
+    else {
+        Err(E::from_kind(ErrorKind::Unexpected, input.index()))
+    }
+}
+
+fn whitespace<T : TextToken, E : JlnError>(input : &mut Parser<'_, T>) -> Result<(), E> {
+    input.take_while(|t : &T| matches!(t.to_char(), ' ' | '\t' | '\n' | '\r'))?;
+    Ok(())
+}
+
+fn element<T : TextToken, E : JlnError>(input : &mut Parser<'_, T>, errors : &mut Option<&mut Vec<E>>) -> Result<Json, E> {
+    let mut input = input.enter_rule()?;
+    whitespace(&mut input)?;
+    let start = input.index();
+    let value = match input.peek::<E>()?.to_char() {
+        '{' => object(&mut input, errors)?,
+        '[' => array(&mut input, errors)?,
+        '"' => Value::String(input.string_literal::<E>(&StringStyle::json())?.0),
+        't' => literal(&mut input, "true", Value::Bool(true))?,
+        'f' => literal(&mut input, "false", Value::Bool(false))?,
+        'n' => literal(&mut input, "null", Value::Null)?,
+        '-' | '0'..='9' => Value::Number(number(&mut input)?),
+        _ => { return Err(E::from_kind(ErrorKind::Unexpected, start)); },
+    };
+    Ok(Json { value, span: Span::new(start, input.index()) })
+}
+
+fn literal<T : TextToken, E : JlnError>(input : &mut Parser<'_, T>, text : &str, value : Value) -> Result<Value, E> {
+    input.tag_str::<E>(text)?;
+    Ok(value)
+}
+
+fn number<T : TextToken, E : JlnError>(input : &mut Parser<'_, T>) -> Result<f64, E> {
+    let start = input.index();
+    let (value, span) = input.spanned(|input| input.float::<f64, E>())?;
+    let text = input.input[span.start..span.end].iter().map(|t| t.to_char()).collect::<String>();
+    let digits = text.trim_start_matches('-');
+    if text.contains('_') || text.starts_with('+') || (digits.starts_with('0') && digits[1..].starts_with(|c : char| c.is_ascii_digit())) {
+        input.index = start;
+        return Err(E::from_kind(ErrorKind::InvalidNumber, start));
+    }
+    Ok(value)
+}
+
+fn array<T : TextToken, E : JlnError>(input : &mut Parser<'_, T>, errors : &mut Option<&mut Vec<E>>) -> Result<Value, E> {
+    input.expect_no_case::<E>('[')?;
+    let mut items = vec![];
+    whitespace(input)?;
+    if input.option(|input| input.expect_no_case::<E>(']'))?.is_some() {
+        return Ok(Value::Array(items));
+    }
+    loop {
+        items.push(recover(input, errors, ']', |input, errors| element(input, errors))?);
+        whitespace(input)?;
+        match input.get::<E>()?.to_char() {
+            ',' => { },
+            ']' => { return Ok(Value::Array(items)); },
+            _ => { return Err(E::from_kind(ErrorKind::Unexpected, input.index() - 1)); },
+        }
+    }
+}
+
+fn object<T : TextToken, E : JlnError>(input : &mut Parser<'_, T>, errors : &mut Option<&mut Vec<E>>) -> Result<Value, E> {
+    input.expect_no_case::<E>('{')?;
+    let mut members = vec![];
+    whitespace(input)?;
+    if input.option(|input| input.expect_no_case::<E>('}'))?.is_some() {
+        return Ok(Value::Object(members));
+    }
+    loop {
+        whitespace(input)?;
+        let (key, key_span) = input.string_literal::<E>(&StringStyle::json())?;
+        whitespace(input)?;
+        input.expect_no_case::<E>(':')?;
+        let value = recover(input, errors, '}', |input, errors| element(input, errors))?;
+        members.push(Member { key, key_span, value });
+        whitespace(input)?;
+        match input.get::<E>()?.to_char() {
+            ',' => { },
+            '}' => { return Ok(Value::Object(members)); },
+            _ => { return Err(E::from_kind(ErrorKind::Unexpected, input.index() - 1)); },
+        }
+    }
+}
+
+type Element<T, E> = fn(&mut Parser<'_, T>, &mut Option<&mut Vec<E>>) -> Result<Json, E>;
+
+fn recover<T : TextToken, E : JlnError>(input : &mut Parser<'_, T>, errors : &mut Option<&mut Vec<E>>, close : char, f : Element<T, E>) -> Result<Json, E> {
+    whitespace(input)?;
+    let start = input.index();
+    let error = match input.with_rollback(|input| f(input, errors)) {
+        Ok(json) => { return Ok(json); },
+        Err(e) if input.fatal(&e) => { return Err(e); },
+        Err(e) => e,
+    };
+    let Some(errors) = errors else { return Err(error); };
+    errors.push(error);
+    input.emit_error("json");
+    skip_element(input, close)?;
+    Ok(Json { value: Value::Invalid, span: Span::new(start, input.index()) })
+}
+
+fn skip_element<T : TextToken, E : JlnError>(input : &mut Parser<'_, T>, close : char) -> Result<(), E> {
+    let mut depth = 0usize;
+    let mut string = false;
+    while let Ok(t) = input.peek::<E>() {
+        let c = t.to_char();
+        if string {
+            match c {
+                '\\' => { input.get::<E>()?; },
+                '"' => { string = false; },
+                _ => { },
+            }
+        }
+        else {
+            match c {
+                '"' => { string = true; },
+                '[' | '{' => { depth += 1; },
+                ']' | '}' if depth > 0 => { depth -= 1; },
+                ',' if depth == 0 => { break; },
+                c if c == close && depth == 0 => { break; },
+                ']' | '}' => { break; },
+                _ => { },
+            }
+        }
+        input.get::<E>()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_parse_documents_with_spans() {
+        let json = parse(r#" {"a": [1, -2.5e1, true, null], "bé": {"c": "x\n"}} "#).unwrap();
+
+        assert_eq!(json.span, Span::new(1, 51));
+        let a = json.get("a").unwrap();
+        assert_eq!(a.span, Span::new(7, 30));
+        assert_eq!(a.index(1).unwrap().value, Value::Number(-25.0));
+        assert_eq!(a.index(2).unwrap().value, Value::Bool(true));
+        assert_eq!(a.index(3).unwrap().span, Span::new(25, 29));
+        assert_eq!(json.get("bé").unwrap().get("c").unwrap().value, Value::String("x\n".into()));
+        if let Value::Object(members) = &json.value {
+            assert_eq!(members[1].key_span, Span::new(32, 36));
+        }
+    }
+
+    #[test]
+    fn should_reject_invalid_documents() {
+        for text in ["[1,]", "{\"a\" 1}", "01", "+1", "1_0", "[1] x", "\"abc", "tru", ""] {
+            assert!(parse(text).is_err(), "{}", text);
+        }
+        assert_eq!(parse("[\"a\", 00]").unwrap_err().snapshot(), "at 6: InvalidNumber\n");
+    }
+
+    #[test]
+    fn should_recover_from_invalid_elements() {
+        let input = b"[1, tru, {\"a\": [x, 2], \"b\": 3}, 4]".to_vec();
+        let mut buffer = Parser::new(&input);
+
+        let (json, errors) = document_recovering::<u8, Diagnostic>(&mut buffer).unwrap();
+
+        assert_eq!(errors.len(), 2);
+        assert_eq!(json.index(1).unwrap().value, Value::Invalid);
+        assert_eq!(json.index(1).unwrap().span, Span::new(4, 7));
+        assert_eq!(json.index(2).unwrap().get("a").unwrap().index(0).unwrap().value, Value::Invalid);
+        assert_eq!(json.index(2).unwrap().get("b").unwrap().value, Value::Number(3.0));
+        assert_eq!(json.index(3).unwrap().value, Value::Number(4.0));
+    }
+}
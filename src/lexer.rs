@@ -0,0 +1,79 @@
+
+use logos::{Logos, Source};
+
+use crate::{ErrorKind, JlnError, Lexed, Span};
+
+impl<K> Lexed<K> {
+    pub fn logos<'s, E : JlnError>(source : &'s K::Source) -> Result<Self, E> where K : Logos<'s>, K::Extras : Default {
+        let mut lexer = K::lexer(source);
+        let mut tokens = vec![];
+        let mut spans = vec![];
+        while let Some(token) = lexer.next() {
+            let span = lexer.span();
+            match token {
+                Ok(token) => {
+                    tokens.push(token);
+                    spans.push(Span::new(span.start, span.end));
+                },
+                Err(_) => { return Err(E::from_kind(ErrorKind::Unexpected, span.start)); },
+            }
+        }
+        Ok(Lexed::new(tokens, spans, source.len()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use logos::Logos;
+
+    use crate::{Parser, Span};
+
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Logos)]
+    #[logos(skip r"[ \t]+")]
+    enum Tok {
+        #[token("+")]
+        Plus,
+        #[regex("[0-9]+", |lex| lex.slice().parse().ok())]
+        Num(u32),
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct At(usize);
+
+    impl JlnError for At {
+        fn is_fatal(&self) -> bool { false }
+        fn eof() -> Self { At(usize::MAX) }
+        fn aggregate(_errors : Vec<Self>) -> Self { At(usize::MAX) }
+        fn from_kind(_kind : ErrorKind, index : usize) -> Self { At(index) }
+    }
+
+    fn sum(input : &mut Parser<Tok>) -> Result<u32, At> {
+        let mut total = 0;
+        loop {
+            match input.get()? {
+                Tok::Num(n) => { total += n; },
+                Tok::Plus => { return Err(At(input.index() - 1)); },
+            }
+            if input.end() {
+                return Ok(total);
+            }
+            input.expect(&Tok::Plus)?;
+        }
+    }
+
+    #[test]
+    fn should_lex_with_logos_and_parse() {
+        let lexed = Lexed::<Tok>::logos::<At>("1 + 22 + 3").unwrap();
+
+        assert_eq!(lexed.span(1), Span::new(2, 3));
+        assert_eq!(lexed.source_span(Span::new(0, 3)), Span::new(0, 6));
+        assert_eq!(sum(&mut lexed.into_parser()), Ok(26));
+    }
+
+    #[test]
+    fn should_surface_lexer_errors() {
+        assert_eq!(Lexed::<Tok>::logos::<At>("1 + ?").map(|_| ()), Err(At(4)));
+    }
+}
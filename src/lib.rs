@@ -1,10 +1,164 @@
 
+mod macros;
+mod memo;
+mod depth;
+mod indent;
+mod event;
+mod fuel;
+mod partial;
+mod expr;
+mod cst;
+mod trivia;
+mod text;
+mod number;
+mod span;
+mod string;
+mod ident;
+mod binary;
+mod limit;
+mod state;
+mod island;
+mod recover;
+mod pipeline;
+mod rule;
+mod debug;
+mod tokens;
+mod transaction;
+mod parse;
+mod builder;
+mod stream;
+mod deferred;
+mod nest;
+mod region;
+mod graph;
+mod stats;
+mod coverage;
+mod hooks;
+mod diagnostic;
+mod trace;
+mod profile;
+mod csv;
+mod datetime;
+mod shell;
+mod ini;
+mod version;
+mod net;
+mod http1;
+mod mail;
+mod sexpr;
+mod encoding;
+mod fork;
+mod warning;
+mod either;
+mod pushback;
+mod lines;
+mod channel;
+mod decode;
+mod annotate;
+mod error_sink;
+mod backtrace;
+mod furthest;
+mod halt;
+#[cfg(feature = "regex")]
+mod pattern;
+#[cfg(feature = "unicode")]
+mod unicode;
+#[cfg(feature = "serde")]
+mod de;
+#[cfg(feature = "nom")]
+mod bridge;
+#[cfg(feature = "logos")]
+mod lexer;
+#[cfg(feature = "tokio")]
+mod codec;
+#[cfg(feature = "bumpalo")]
+mod arena;
+#[cfg(feature = "memchr")]
+mod scan;
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "bytes")]
+mod bytes_input;
+
+pub mod prelude;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "json")]
+pub mod json;
+
+pub use memo::Memo;
+pub use depth::RuleGuard;
+pub use event::{Event, EventSink};
+pub use partial::{Partial, Progress};
+pub use expr::{Assoc, OperatorTable};
+pub use cst::{CstBuilder, CstElement, CstNode};
+pub use text::TextToken;
+pub use number::Integer;
+pub use span::Span;
+pub use string::StringStyle;
+pub use ident::{ident_continue, ident_start, KeywordTable, Word};
+pub use binary::zigzag;
+pub use island::Islands;
+pub use recover::Recovered;
+pub use pipeline::{Lexed, Lexemes};
+pub use tokens::Tokens;
+pub use transaction::Transaction;
+pub use parse::Parse;
+pub use builder::{Config, ParserBuilder};
+pub use stream::StreamBuffer;
+pub use deferred::Deferred;
+pub use region::Region;
+pub use graph::{Graph, GraphNode, GraphRule};
+pub use stats::Stats;
+pub use coverage::Coverage;
+pub use diagnostic::Diagnostic;
+pub use trace::{Decision, Trace};
+pub use profile::{Profile, RuleProfile};
+pub use csv::CsvStyle;
+pub use datetime::{Date, DateTime, IsoDuration, Offset, Time};
+pub use ini::{Ini, IniEntry, IniSection};
+pub use version::Version;
+pub use net::Host;
+pub use http1::{Header, RequestLine, StatusLine};
+pub use mail::{MailAddress, MailHeader, Mailbox};
+pub use sexpr::{Sexp, SexpValue};
+pub use encoding::{Base64Style, Padding};
+pub use warning::Warning;
+pub use either::{Either, Either3};
+pub use lines::{Line, Lines};
+pub use channel::ChannelInput;
+pub use decode::{DecodeMode, Decoded};
+pub use annotate::Annotations;
+pub use furthest::Furthest;
+pub use rule::{BoxedRule, Map, Optional, Or, Repeated, Rule, Sequence, Then};
+#[cfg(feature = "macros")]
+pub use jlnexus_macros::{grammar, Parse};
+#[cfg(feature = "unicode")]
+pub use unicode_properties::{GeneralCategory, GeneralCategoryGroup};
+#[cfg(feature = "unicode")]
+pub use unicode::{xid_continue, xid_start};
+#[cfg(feature = "serde")]
+pub use de::{from_value, DeError, Value};
+#[cfg(feature = "nom")]
+pub use bridge::nom_rule;
+#[cfg(feature = "tokio")]
+pub use codec::{CodecError, FrameCodec};
+#[cfg(feature = "regex")]
+pub use pattern::{BytesPattern, Pattern};
+
+use std::any::Any;
+use std::borrow::Cow;
+use std::cell::{Cell, OnceCell};
 use std::rc::Rc;
+use std::sync::Arc;
 use std::ops::Deref;
+use std::panic::Location;
 
-enum Input<'a, T> {
+pub enum Input<'a, T> {
     Ref(&'a [T]),
     Rc(Rc<[T]>),
+    Arc(Arc<[T]>),
+    Owned(Rc<dyn AsRef<[T]>>),
 }
 
 impl<'a, T> Clone for Input<'a, T> {
@@ -12,6 +166,8 @@ impl<'a, T> Clone for Input<'a, T> {
         match self {
             Input::Ref(x) => Input::Ref(x),
             Input::Rc(x) => Input::Rc(Rc::clone(x)),
+            Input::Arc(x) => Input::Arc(Arc::clone(x)),
+            Input::Owned(x) => Input::Owned(Rc::clone(x)),
         }
     }
 }
@@ -22,7 +178,45 @@ impl<'a, T> Deref for Input<'a, T> {
     fn deref(&self) -> &Self::Target {
         match self {
             Input::Ref(x) => x,
-            Input::Rc(x) => &*x,
+            Input::Rc(x) => x,
+            Input::Arc(x) => x,
+            Input::Owned(x) => x.as_ref().as_ref(),
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorKind {
+    NestingTooDeep { limit : usize },
+    Indentation { level : usize, column : usize },
+    Unexpected,
+    BudgetExhausted,
+    Overflow,
+    InvalidNumber,
+    Unterminated,
+    InvalidEscape,
+    Truncated,
+    External { fatal : bool },
+    Custom { label : Option<&'static str> },
+    Incomplete,
+}
+
+impl ErrorKind {
+    pub fn is_fatal(&self) -> bool {
+        match self {
+            ErrorKind::NestingTooDeep { .. } => true,
+            ErrorKind::Indentation { .. } => false,
+            ErrorKind::Unexpected => false,
+            ErrorKind::BudgetExhausted => true,
+            ErrorKind::Overflow => false,
+            ErrorKind::InvalidNumber => false,
+            ErrorKind::Unterminated => true,
+            ErrorKind::InvalidEscape => false,
+            ErrorKind::Truncated => false,
+            ErrorKind::External { fatal } => *fatal,
+            ErrorKind::Custom { .. } => false,
+            ErrorKind::Incomplete => true,
         }
     }
 }
@@ -30,29 +224,129 @@ impl<'a, T> Deref for Input<'a, T> {
 pub trait JlnError : Sized {
     fn is_fatal(&self) -> bool;
     fn eof() -> Self;
+    fn eof_at(_index : usize) -> Self { Self::eof() }
     fn aggregate(errors : Vec<Self>) -> Self;
+    fn from_kind(_kind : ErrorKind, index : usize) -> Self { Self::eof_at(index) }
+    fn context(self, _rule : &'static str, _index : usize) -> Self { self }
+    fn custom(label : Option<&'static str>, index : usize) -> Self { Self::from_kind(ErrorKind::Custom { label }, index) }
+    fn expected(self, _description : &str) -> Self { self }
+    fn backtrace(self, _rules : &[(&'static str, usize)]) -> Self { self }
 }
 
+pub(crate) struct Stop;
+
+impl JlnError for Stop {
+    fn is_fatal(&self) -> bool { true }
+    fn eof() -> Self { Stop }
+    fn aggregate(_errors : Vec<Self>) -> Self { Stop }
+    fn from_kind(_kind : ErrorKind, _index : usize) -> Self { Stop }
+}
+
+pub type Alternative<T, S, E> = for<'b> fn(&mut Parser<'b, T>) -> Result<S, E>;
+
 pub struct Parser<'a, T> {
     input : Input<'a, T>,
     index : usize,
+    reach : usize,
+    memo : Option<memo::MemoTable>,
+    depth : usize,
+    max_depth : Option<usize>,
+    indents : Vec<usize>,
+    checking : bool,
+    events : Option<event::EventLog>,
+    fuel : Option<Rc<Cell<u64>>>,
+    halted : Rc<Cell<bool>>,
+    shared : Rc<OnceCell<Arc<[T]>>>,
+    trivia : Option<fn(&T) -> bool>,
+    skip_trivia : bool,
+    limit : Option<usize>,
+    state : Option<Rc<dyn Any>>,
+    streaming : Option<Rc<Cell<bool>>>,
+    stats : Option<stats::StatsLog>,
+    coverage : Option<coverage::CoverageLog>,
+    hooks : Option<hooks::HookRegistry<T>>,
+    trace : Option<trace::TraceLog>,
+    profile : Option<profile::ProfileLog>,
+    warnings : Option<warning::WarningLog>,
+    errors : Option<error_sink::ErrorSink>,
+    rules : Option<Vec<(&'static str, usize)>>,
+    pushback : pushback::Pushback<T>,
+    popped : pushback::Pushback<T>,
+    spliced : Vec<T>,
+}
+
+pub(crate) struct Checkpoint<T> {
+    index : usize,
+    depth : usize,
+    max_depth : Option<usize>,
+    indents : Vec<usize>,
+    checking : bool,
+    trivia : Option<fn(&T) -> bool>,
+    skip_trivia : bool,
+    limit : Option<usize>,
+    state : Option<Rc<dyn Any>>,
+    warnings : usize,
+    errors : usize,
+    pushback : pushback::Pushback<T>,
 }
 
 impl<'a, T> From<&'a [T]> for Parser<'a, T> {
     fn from(item : &'a [T]) -> Self {
-        Parser { input: Input::Ref(item), index: 0 }
+        Parser::from_input(Input::Ref(item))
     }
 }
 
 impl<'a, T> From<Vec<T>> for Parser<'a, T> {
     fn from(item : Vec<T>) -> Self {
-        Parser { input: Input::Rc(item.into()), index: 0 }
+        Parser::from_input(Input::Rc(item.into()))
     }
 }
 
 impl<'a, T> From<&Rc<[T]>> for Parser<'a, T> {
     fn from(item : &Rc<[T]>) -> Self {
-        Parser { input: Input::Rc(Rc::clone(item)), index: 0 }
+        Parser::from_input(Input::Rc(Rc::clone(item)))
+    }
+}
+
+impl<'a, T> From<Box<[T]>> for Parser<'a, T> {
+    fn from(item : Box<[T]>) -> Self {
+        Parser::from_input(Input::Rc(item.into()))
+    }
+}
+
+impl<'a, T, const N : usize> From<[T; N]> for Parser<'a, T> {
+    fn from(item : [T; N]) -> Self {
+        Parser::from_input(Input::Rc(Rc::new(item)))
+    }
+}
+
+impl<'a, T> From<Arc<[T]>> for Parser<'a, T> {
+    fn from(item : Arc<[T]>) -> Self {
+        Parser::from_input(Input::Arc(item))
+    }
+}
+
+impl<'a, T> From<&Arc<[T]>> for Parser<'a, T> {
+    fn from(item : &Arc<[T]>) -> Self {
+        Parser::from_input(Input::Arc(Arc::clone(item)))
+    }
+}
+
+impl<'a> From<&str> for Parser<'a, char> {
+    fn from(item : &str) -> Self {
+        item.chars().collect()
+    }
+}
+
+impl<'a> From<String> for Parser<'a, char> {
+    fn from(item : String) -> Self {
+        item.chars().collect()
+    }
+}
+
+impl<'a> From<&'a str> for Parser<'a, u8> {
+    fn from(item : &'a str) -> Self {
+        Parser::new(item.as_bytes())
     }
 }
 
@@ -64,83 +358,322 @@ impl<'a, T> FromIterator<T> for Parser<'a, T> {
 
 impl<'a, T> Clone for Parser<'a, T> {
     fn clone(&self) -> Self {
-        Parser { input: Input::clone(&self.input), index: self.index }
+        Parser {
+            input: Input::clone(&self.input),
+            index: self.index,
+            reach: self.reach,
+            memo: self.memo.clone(),
+            depth: self.depth,
+            max_depth: self.max_depth,
+            indents: self.indents.clone(),
+            checking: self.checking,
+            events: self.events.clone(),
+            fuel: self.fuel.clone(),
+            halted: Rc::clone(&self.halted),
+            shared: Rc::clone(&self.shared),
+            trivia: self.trivia,
+            skip_trivia: self.skip_trivia,
+            limit: self.limit,
+            state: self.state.clone(),
+            streaming: self.streaming.clone(),
+            stats: self.stats.clone(),
+            coverage: self.coverage.clone(),
+            hooks: self.hooks.clone(),
+            trace: self.trace.clone(),
+            profile: self.profile.clone(),
+            warnings: self.warnings.clone(),
+            errors: self.errors.clone(),
+            rules: self.rules.clone(),
+            pushback: self.pushback.clone(),
+            popped: None,
+            spliced: vec![],
+        }
     }
 }
 
 impl<'a, T> Parser<'a, T> {
+    fn from_input(input : Input<'a, T>) -> Parser<'a, T> {
+        Parser {
+            input,
+            index: 0,
+            reach: 0,
+            memo: None,
+            depth: 0,
+            max_depth: None,
+            indents: vec![],
+            checking: false,
+            events: None,
+            fuel: None,
+            halted: Rc::new(Cell::new(false)),
+            shared: Rc::new(OnceCell::new()),
+            trivia: None,
+            skip_trivia: true,
+            limit: None,
+            state: None,
+            streaming: None,
+            stats: None,
+            coverage: None,
+            hooks: None,
+            trace: None,
+            profile: None,
+            warnings: None,
+            errors: None,
+            rules: None,
+            pushback: None,
+            popped: None,
+            spliced: vec![],
+        }
+    }
+
     pub fn new(input : &'a [T]) -> Parser<'a, T> {
-        Parser { input: Input::Ref(input), index: 0 }
+        Parser::from_input(Input::Ref(input))
+    }
+
+    pub fn borrowed<R : AsRef<[T]> + ?Sized>(input : &'a R) -> Parser<'a, T> {
+        Parser::from_input(Input::Ref(input.as_ref()))
+    }
+
+    pub fn into_inner(self) -> (Input<'a, T>, usize) {
+        (self.input, self.index)
+    }
+
+    pub fn into_remaining(self) -> Cow<'a, [T]> where T : Clone {
+        match self.input {
+            Input::Ref(x) => Cow::Borrowed(&x[self.index.min(x.len())..]),
+            Input::Rc(x) => Cow::Owned(x[self.index.min(x.len())..].to_vec()),
+            Input::Arc(x) => Cow::Owned(x[self.index.min(x.len())..].to_vec()),
+            Input::Owned(x) => {
+                let x = x.as_ref().as_ref();
+                Cow::Owned(x[self.index.min(x.len())..].to_vec())
+            },
+        }
+    }
+
+    #[track_caller]
+    pub fn or<S, E : JlnError, const N : usize>(&mut self, targets : [Alternative<T, S, E>; N]) -> Result<S, E> {
+        self.or_indexed(targets).map(|(_, s)| s)
     }
 
-    pub fn or<S, E : JlnError, const N : usize>(&mut self, targets : [for<'b> fn(&mut Parser<'b, T>) -> Result<S, E>; N]) -> Result<S, E> {
+    #[track_caller]
+    pub fn or_indexed<S, E : JlnError, const N : usize>(&mut self, targets : [Alternative<T, S, E>; N]) -> Result<(usize, S), E> {
+        let location = Location::caller();
+        if let Some(branch) = self.replay_branch(N) {
+            self.count_alternative(location, branch, N);
+            match self.attempt(targets[branch]) {
+                Ok(s) => {
+                    self.cover_alternative(location, branch, N);
+                    return Ok((branch, s));
+                },
+                Err(e) if self.fatal(&e) => { return Err(e); },
+                Err(_) => { },
+            }
+        }
+
+        let slot = self.trace_branch_start();
+        let mut furthest = Furthest::<E, N>::new();
+        for (branch, target) in targets.into_iter().enumerate() {
+            self.count_alternative(location, branch, N);
+            match self.alternative(&mut furthest, target) {
+                Ok(Some(s)) => {
+                    self.cover_alternative(location, branch, N);
+                    self.trace_branch_end(slot, Some(branch));
+                    return Ok((branch, s));
+                },
+                Ok(None) => { },
+                Err(e) => {
+                    self.trace_branch_end(slot, None);
+                    return Err(e);
+                },
+            }
+        }
+
+        self.trace_branch_end(slot, None);
+        Err(furthest.into_error())
+    }
+
+    pub fn or_all<S, E : JlnError, const N : usize>(&self, targets : [Alternative<T, S, E>; N]) -> Result<Vec<(S, usize)>, E> {
+        let mut successes = vec![];
         let mut errors = vec![];
         for target in targets {
             let mut ops = self.clone();
-            match target(&mut ops) {
-                Ok(s) => { 
-                    self.index = ops.index;
-                    return Ok(s); 
-                },
-                Err(e) if e.is_fatal() => { return Err(e); },
+            let mark = self.speculate();
+            let result = target(&mut ops);
+            self.settle(mark, false);
+            match result {
+                Ok(s) => { successes.push((s, ops.index)); },
+                Err(e) if self.fatal(&e) => { return Err(e); },
                 Err(e) => { errors.push(e); },
             }
         }
 
-        Err(JlnError::aggregate(errors))
+        if successes.is_empty() {
+            Err(JlnError::aggregate(errors))
+        }
+        else {
+            Ok(successes)
+        }
+    }
+
+    #[track_caller]
+    pub fn dispatch<S, E : JlnError, K : FnOnce(&T) -> Option<usize>, const N : usize>(&mut self, key : K, targets : [Alternative<T, S, E>; N]) -> Result<S, E> {
+        match self.peek::<Stop>().ok().and_then(key) {
+            Some(branch) if branch < N => {
+                let location = Location::caller();
+                self.count_alternative(location, branch, N);
+                let result = self.attempt(targets[branch]);
+                if result.is_ok() {
+                    self.cover_alternative(location, branch, N);
+                }
+                result
+            },
+            _ => self.or(targets),
+        }
     }
 
     pub fn option<S, E : JlnError, F : FnOnce(&mut Parser<'a, T>) -> Result<S, E>>(&mut self, f : F) -> Result<Option<S>, E> {
-            let mut ops = self.clone();
-            match f(&mut ops) {
-                Ok(v) => {
-                    self.index = ops.index;
-                    Ok(Some(v))
-                },
-                Err(e) if e.is_fatal() => { return Err(e); },
-                Err(_) => Ok(None),
-            }
+        match self.attempt(f) {
+            Ok(v) => Ok(Some(v)),
+            Err(e) if self.fatal(&e) => Err(e),
+            Err(_) => Ok(None),
+        }
+    }
+
+    pub fn if_then<S, E : JlnError, F : FnOnce(&mut Parser<'a, T>) -> Result<S, E>>(&mut self, cond : bool, f : F) -> Result<Option<S>, E> {
+        if !cond {
+            return Ok(None);
+        }
+        self.attempt(f).map(Some)
     }
 
     pub fn list<S, E : JlnError, F : FnMut(&mut Parser<'a, T>) -> Result<S, E>>(&mut self, mut f : F) -> Result<Vec<S>, E> {
         let mut rets = vec![];
         loop {
-            let mut ops = self.clone();
-            match f(&mut ops) {
-                Ok(v) => {
-                    self.index = ops.index;
-                    rets.push(v);
-                },
-                Err(e) if e.is_fatal() => { return Err(e); },
+            match self.attempt(&mut f) {
+                Ok(v) => { rets.push(v); },
+                Err(e) if self.fatal(&e) => { return Err(e); },
                 Err(_) => { break; },
             }
         }
         Ok(rets)
     }
 
+    pub fn array_of<const N : usize, S, E : JlnError, F : FnMut(&mut Parser<'a, T>) -> Result<S, E>>(&mut self, mut f : F) -> Result<[S; N], E> {
+        self.attempt(|input| {
+            let mut items : [Option<S>; N] = std::array::from_fn(|_| None);
+            for item in items.iter_mut() {
+                *item = Some(f(input)?);
+            }
+            Ok(items.map(Option::unwrap))
+        })
+    }
+
+    pub fn skip_many<S, E : JlnError, F : FnMut(&mut Parser<'a, T>) -> Result<S, E>>(&mut self, mut f : F) -> Result<usize, E> {
+        let mut count = 0;
+        loop {
+            match self.attempt(&mut f) {
+                Ok(_) => { count += 1; },
+                Err(e) if self.fatal(&e) => { return Err(e); },
+                Err(_) => { break; },
+            }
+        }
+        Ok(count)
+    }
+
+    pub fn inspect<S, E : JlnError, F : FnOnce(&mut Parser<'a, T>) -> Result<S, E>, G : FnOnce(&S, Span)>(&mut self, f : F, g : G) -> Result<S, E> {
+        let (value, span) = self.spanned(f)?;
+        g(&value, span);
+        Ok(value)
+    }
+
+    pub fn consumed_count<S, E : JlnError, F : FnOnce(&mut Parser<'a, T>) -> Result<S, E>>(&mut self, f : F) -> Result<(S, usize), E> {
+        let start = self.index;
+        let value = self.attempt(f)?;
+        Ok((value, self.index - start))
+    }
+
+    pub fn map_err_parse<S, E : JlnError, R : JlnError, F : FnOnce(&mut Parser<'a, T>) -> Result<S, E>, G : FnOnce(E, usize) -> R>(&mut self, f : F, g : G) -> Result<S, R> {
+        let start = self.next_significant();
+        self.attempt(f).map_err(|e| g(e, start))
+    }
+
+    pub fn expecting<S, E : JlnError, F : FnOnce(&mut Parser<'a, T>) -> Result<S, E>>(&mut self, description : &str, f : F) -> Result<S, E> {
+        self.attempt(f).map_err(|e| if self.fatal(&e) { e } else { e.expected(description) })
+    }
+
+    pub fn context<S, E : JlnError, F : FnOnce(&mut Parser<'a, T>) -> Result<S, E>>(&mut self, rule : &'static str, f : F) -> Result<S, E> {
+        let start = self.index;
+        self.hook_enter(rule);
+        self.profile_enter(rule);
+        self.push_rule(rule, start);
+        let result = f(self).map_err(|e| self.capture_backtrace(e));
+        self.pop_rule();
+        self.profile_exit();
+        self.hook_exit(rule, result.is_ok());
+        let value = result.map_err(|e| e.context(rule, start))?;
+        self.cover_rule(rule);
+        Ok(value)
+    }
+
+    pub fn check<S, E : JlnError, F : FnOnce(&mut Parser<'a, T>) -> Result<S, E>>(&mut self, f : F) -> Result<bool, E> {
+        let checkpoint = self.checkpoint();
+        self.checking = true;
+        let mark = self.speculate();
+        let result = f(self);
+        self.restore(checkpoint);
+        self.settle(mark, false);
+        match result {
+            Ok(_) => Ok(true),
+            Err(e) if self.fatal(&e) => Err(e),
+            Err(_) => Ok(false),
+        }
+    }
+
+    pub fn fail<E : JlnError, L : Into<Option<&'static str>>>(&self, label : L) -> E {
+        E::custom(label.into(), self.index)
+    }
+
+    pub fn checking(&self) -> bool {
+        self.checking
+    }
+
     pub fn peek<E : JlnError>(&self) -> Result<&T, E> {
-        if self.index < self.input.len() {
-            let r = &self.input[self.index];
+        if let Some(token) = self.peek_pushback() {
+            return Ok(token);
+        }
+        let index = self.next_significant();
+        if index < self.bound() {
+            let r = &self.input[index];
             Ok(r)
         }
         else {
-            Err(JlnError::eof())
+            Err(self.exhausted())
         }
     }
 
     pub fn get<E : JlnError>(&mut self) -> Result<&T, E> {
-        if self.index < self.input.len() {
-            let r = &self.input[self.index];
-            self.index += 1;
-            Ok(r)
+        if self.pushback.is_some() {
+            return self.pop_pushback();
+        }
+        self.consume_trivia();
+        if self.index < self.bound() {
+            self.burn()?;
+            self.advance();
+            Ok(&self.input[self.index - 1])
         }
         else {
-            Err(JlnError::eof())
+            Err(self.exhausted())
+        }
+    }
+
+    pub fn next_if<P : FnOnce(&T) -> bool>(&mut self, pred : P) -> Option<&T> {
+        match self.peek::<Stop>() {
+            Ok(t) if pred(t) => self.get::<Stop>().ok(),
+            _ => None,
         }
     }
 
     pub fn end(&self) -> bool {
-        self.index >= self.input.len()
+        self.pushback.is_none() && self.next_significant() >= self.bound() && !self.awaiting_input()
     }
 
     pub fn index(&self) -> usize {
@@ -148,10 +681,166 @@ impl<'a, T> Parser<'a, T> {
     }
 
     pub fn with_rollback<S, E : JlnError, F : FnOnce(&mut Parser<'a, T>) -> Result<S, E>>(&mut self, f : F) -> Result<S, E> {
-        let mut ops = self.clone();
-        let r = f(&mut ops)?;
-        self.index = ops.index;
-        Ok(r)
+        self.attempt(f)
+    }
+
+    fn attempt<S, E : JlnError, F : FnOnce(&mut Parser<'a, T>) -> Result<S, E>>(&mut self, f : F) -> Result<S, E> {
+        let mark = self.speculate();
+        let result = self.rollback(f);
+        self.settle(mark, result.is_ok());
+        result
+    }
+
+    fn rollback<S, E : JlnError, F : FnOnce(&mut Parser<'a, T>) -> Result<S, E>>(&mut self, f : F) -> Result<S, E> {
+        let checkpoint = self.checkpoint();
+        let trace = self.trace_mark();
+        match f(self) {
+            Ok(v) => Ok(v),
+            Err(e) => {
+                self.trace_rollback(trace, self.index, checkpoint.index);
+                self.restore(checkpoint);
+                self.count_backtrack();
+                self.burn()?;
+                Err(e)
+            },
+        }
+    }
+
+    fn attempt_reach<S, E : JlnError, F : FnOnce(&mut Parser<'a, T>) -> Result<S, E>>(&mut self, f : F) -> (Result<S, E>, usize) {
+        let outer = self.reach;
+        self.reach = self.index;
+        let result = self.attempt(f);
+        let reach = self.reach;
+        self.reach = outer.max(reach);
+        (result, reach)
+    }
+
+    pub(crate) fn checkpoint(&self) -> Checkpoint<T> {
+        Checkpoint {
+            index: self.index,
+            depth: self.depth,
+            max_depth: self.max_depth,
+            indents: self.indents.clone(),
+            checking: self.checking,
+            trivia: self.trivia,
+            skip_trivia: self.skip_trivia,
+            limit: self.limit,
+            state: self.state.clone(),
+            warnings: self.warning_mark(),
+            errors: self.error_mark(),
+            pushback: self.pushback.clone(),
+        }
+    }
+
+    pub(crate) fn restore(&mut self, checkpoint : Checkpoint<T>) {
+        self.index = checkpoint.index;
+        self.depth = checkpoint.depth;
+        self.max_depth = checkpoint.max_depth;
+        self.indents = checkpoint.indents;
+        self.checking = checkpoint.checking;
+        self.trivia = checkpoint.trivia;
+        self.skip_trivia = checkpoint.skip_trivia;
+        self.limit = checkpoint.limit;
+        self.state = checkpoint.state;
+        self.discard_warnings(checkpoint.warnings);
+        self.discard_errors(checkpoint.errors);
+        self.pushback = checkpoint.pushback;
+    }
+
+    pub fn take_while<E : JlnError, P : FnMut(&T) -> bool>(&mut self, mut pred : P) -> Result<&[T], E> where T : Clone {
+        if self.pushback.is_some() {
+            let mut tokens = vec![];
+            while let Some(token) = self.peek_pushback() {
+                if !pred(token) {
+                    return Ok(self.splice(tokens));
+                }
+                tokens.push(self.pop_pushback::<E>()?.clone());
+            }
+            tokens.extend_from_slice(self.take_while(pred)?);
+            return Ok(self.splice(tokens));
+        }
+        self.consume_trivia();
+        let start = self.index;
+        let length = self.input[start..self.bound()].iter().take_while(|x| pred(x)).count();
+        self.consume_to(start + length)?;
+        Ok(&self.input[start..self.index])
+    }
+
+    pub(crate) fn consume_to<E : JlnError>(&mut self, target : usize) -> Result<(), E> {
+        while self.index < target {
+            self.burn()?;
+            self.advance();
+        }
+        Ok(())
+    }
+
+    fn advance(&mut self) {
+        self.emit(Event::Token { index: self.index });
+        self.count_token();
+        self.hook_token();
+        self.index += 1;
+        self.reach = self.reach.max(self.index);
+    }
+}
+
+impl<'a, T : PartialEq> Parser<'a, T> {
+    pub fn expect<E : JlnError>(&mut self, token : &T) -> Result<&T, E> {
+        match self.peek::<E>()? {
+            t if t == token => self.get(),
+            _ => Err(E::from_kind(ErrorKind::Unexpected, self.next_significant())),
+        }
+    }
+
+    pub fn next_if_eq(&mut self, token : &T) -> Option<&T> {
+        self.next_if(|t| t == token)
+    }
+
+    pub fn take_until<E : JlnError>(&mut self, token : &T) -> Result<&[T], E> where T : Clone {
+        if self.pushback.is_some() {
+            let pushback = self.pushback.clone();
+            let mut tokens = vec![];
+            while let Some(next) = self.peek_pushback() {
+                if next == token {
+                    return Ok(self.splice(tokens));
+                }
+                tokens.push(self.pop_pushback::<E>()?.clone());
+            }
+            match self.take_until(token) {
+                Ok(taken) => { tokens.extend_from_slice(taken); },
+                Err(e) => {
+                    self.pushback = pushback;
+                    return Err(e);
+                },
+            }
+            return Ok(self.splice(tokens));
+        }
+        self.consume_trivia();
+        let start = self.index;
+        match self.input[start..self.bound()].iter().position(|x| x == token) {
+            Some(length) => { self.consume_to(start + length)?; },
+            None => { return Err(self.exhausted()); },
+        }
+        Ok(&self.input[start..self.index])
+    }
+
+    pub fn tag<E : JlnError>(&mut self, tag : &[T]) -> Result<&[T], E> where T : Clone {
+        let pushed = self.pushback.is_some();
+        let start = self.next_significant();
+        self.with_rollback(|input| {
+            if !pushed {
+                input.consume_trivia();
+            }
+            input.no_trivia(|input| {
+                for token in tag {
+                    input.expect(token)?;
+                }
+                Ok(())
+            })
+        })?;
+        if pushed {
+            return Ok(self.splice(tag.to_vec()));
+        }
+        Ok(&self.input[start..self.index])
     }
 }
 
@@ -161,16 +850,19 @@ mod test {
 
     impl JlnError for () {
         fn is_fatal(&self) -> bool { false }
-        fn eof() -> Self { () }
-        fn aggregate(_errors : Vec<Self>) -> Self { () }
+        fn eof() -> Self { }
+        fn aggregate(_errors : Vec<Self>) -> Self { }
+        fn from_kind(_kind : ErrorKind, _index : usize) -> Self { }
     }
 
-    struct TError(bool);
+    #[derive(Debug)]
+    pub(crate) struct TError(pub(crate) bool);
 
     impl JlnError for TError {
         fn is_fatal(&self) -> bool { self.0 }
         fn eof() -> Self { TError(false) }
         fn aggregate(errors : Vec<Self>) -> Self { TError(errors.into_iter().any(|x| x.is_fatal())) }
+        fn from_kind(kind : ErrorKind, _index : usize) -> Self { TError(kind.is_fatal()) }
     }
 
     #[test]
@@ -208,7 +900,7 @@ mod test {
 
     #[test]
     fn should_create_borrow_parser_with_into() {
-        let input = vec![1, 2, 3];
+        let input = [1, 2, 3];
         let mut buffer : Parser<usize> = (&input[..]).into();
 
         let value = buffer.get::<()>().unwrap();
@@ -217,6 +909,62 @@ mod test {
         assert_eq!(buffer.index(), 1)
     }
 
+    #[test]
+    fn should_recover_input_from_parser() {
+        let input : Rc<[u8]> = vec![1, 2, 3].into();
+        let mut buffer : Parser<u8> = (&input).into();
+        buffer.get::<()>().unwrap();
+
+        let (inner, index) = buffer.into_inner();
+
+        assert!(matches!(inner, Input::Rc(ref x) if Rc::ptr_eq(x, &input)));
+        assert_eq!(index, 1);
+    }
+
+    #[test]
+    fn should_borrow_remaining_input() {
+        let input = vec![1, 2, 3];
+        let mut buffer = Parser::new(&input);
+        buffer.get::<()>().unwrap();
+
+        let rest = buffer.into_remaining();
+
+        assert!(matches!(rest, Cow::Borrowed(&[2, 3])));
+    }
+
+    #[test]
+    fn should_create_parsers_from_owned_collections() {
+        let from_box : Parser<u8> = vec![1, 2].into_boxed_slice().into();
+        let from_array : Parser<u8> = [1, 2].into();
+        let from_arc : Parser<u8> = Arc::<[u8]>::from(vec![1, 2]).into();
+
+        for mut buffer in [from_box, from_array, from_arc] {
+            assert_eq!(buffer.get::<()>(), Ok(&1));
+            assert_eq!(buffer.get::<()>(), Ok(&2));
+            assert!(buffer.end());
+        }
+    }
+
+    #[test]
+    fn should_create_parsers_from_text() {
+        let mut chars : Parser<char> = "hé".into();
+        let mut owned : Parser<char> = String::from("hé").into();
+        let mut bytes : Parser<u8> = "hé".into();
+
+        assert_eq!(chars.tag_str::<()>("hé").map(|x| x.len()), Ok(2));
+        assert_eq!(owned.tag_str::<()>("hé").map(|x| x.len()), Ok(2));
+        assert_eq!(bytes.tokens().count(), 3);
+    }
+
+    #[test]
+    fn should_borrow_from_as_ref() {
+        let input = vec![1, 2, 3];
+        let mut buffer = Parser::borrowed(&input);
+
+        assert_eq!(buffer.get::<()>(), Ok(&1));
+        assert!(matches!(buffer.into_inner().0, Input::Ref(x) if std::ptr::eq(x, &input[..])));
+    }
+
     #[test]
     fn should_get() {
         let input = vec![1, 2, 3];
@@ -239,6 +987,84 @@ mod test {
         assert_eq!(buffer.index(), 0);
     }
 
+    #[test]
+    fn should_expect() {
+        let input = vec![1, 2, 3];
+        let mut buffer = Parser::new(&input);
+
+        assert!(buffer.expect::<()>(&2).is_err());
+        assert_eq!(buffer.index(), 0);
+        assert_eq!(buffer.expect::<()>(&1), Ok(&1));
+        assert_eq!(buffer.index(), 1);
+    }
+
+    #[test]
+    fn should_consume_next_only_on_match() {
+        let input = vec![1, 2, 3];
+        let mut buffer = Parser::new(&input);
+
+        assert_eq!(buffer.next_if(|x| *x == 2), None);
+        assert_eq!(buffer.next_if(|x| *x == 1), Some(&1));
+        assert_eq!(buffer.next_if_eq(&2), Some(&2));
+        assert_eq!(buffer.next_if_eq(&2), None);
+        assert_eq!(buffer.index(), 2);
+        assert_eq!(buffer.next_if_eq(&3), Some(&3));
+        assert_eq!(buffer.next_if(|_| true), None);
+    }
+
+    #[test]
+    fn should_accept_error_types_written_against_original_trait() {
+        #[derive(Debug, PartialEq)]
+        struct Minimal(usize);
+
+        impl JlnError for Minimal {
+            fn is_fatal(&self) -> bool { false }
+            fn eof() -> Self { Minimal(usize::MAX) }
+            fn eof_at(index : usize) -> Self { Minimal(index) }
+            fn aggregate(_errors : Vec<Self>) -> Self { Minimal(0) }
+        }
+
+        let input = vec![1, 2];
+        let mut buffer = Parser::new(&input);
+        buffer.get::<Minimal>().unwrap();
+
+        assert_eq!(buffer.expect::<Minimal>(&3), Err(Minimal(1)));
+        assert_eq!(buffer.fail::<Minimal, _>("x"), Minimal(1));
+    }
+
+    #[test]
+    fn should_fail_at_current_position() {
+        #[derive(Debug, PartialEq)]
+        struct Custom(Option<&'static str>, usize);
+
+        impl JlnError for Custom {
+            fn is_fatal(&self) -> bool { false }
+            fn eof() -> Self { Custom(None, 0) }
+            fn aggregate(_errors : Vec<Self>) -> Self { Custom(None, 0) }
+            fn from_kind(_kind : ErrorKind, index : usize) -> Self { Custom(None, index) }
+            fn custom(label : Option<&'static str>, index : usize) -> Self { Custom(label, index) }
+        }
+
+        let input = vec![1, 2];
+        let mut buffer = Parser::new(&input);
+        buffer.get::<()>().unwrap();
+
+        assert_eq!(buffer.fail::<Custom, _>("even number required"), Custom(Some("even number required"), 1));
+        assert_eq!(buffer.fail::<Custom, _>(None), Custom(None, 1));
+        assert!(!buffer.fail::<TError, _>("x").is_fatal());
+    }
+
+    #[test]
+    fn should_match_tag() {
+        let input = vec![1, 2, 3];
+        let mut buffer = Parser::new(&input);
+
+        assert!(buffer.tag::<()>(&[1, 3]).is_err());
+        assert_eq!(buffer.index(), 0);
+        assert_eq!(buffer.tag::<()>(&[1, 2]), Ok(&[1, 2][..]));
+        assert_eq!(buffer.index(), 2);
+    }
+
     #[test]
     fn should_rollback() {
         let input = vec![1, 2, 3];
@@ -252,6 +1078,45 @@ mod test {
         assert_eq!(buffer.index(), 0);
     }
 
+    #[test]
+    fn should_backtrack_without_cloning_input() {
+        let input : Rc<[u8]> = vec![1, 2].into();
+        let mut buffer : Parser<u8> = (&input).into();
+
+        let result = buffer.with_rollback(|buffer| {
+            buffer.get::<()>()?;
+            assert_eq!(Rc::strong_count(&input), 2);
+            Err::<(), ()>(())
+        });
+
+        assert!(result.is_err());
+        assert_eq!(buffer.index(), 0);
+        assert_eq!(buffer.option(|buffer| buffer.get::<()>().copied()), Ok(Some(1)));
+    }
+
+    #[test]
+    fn should_aggregate_or_errors_in_order() {
+        #[derive(Debug, PartialEq)]
+        struct Labels(Vec<u8>);
+
+        impl JlnError for Labels {
+            fn is_fatal(&self) -> bool { false }
+            fn eof() -> Self { Labels(vec![]) }
+            fn aggregate(errors : Vec<Self>) -> Self { Labels(errors.into_iter().flat_map(|x| x.0).collect()) }
+            fn from_kind(_kind : ErrorKind, _index : usize) -> Self { Labels(vec![]) }
+        }
+
+        fn first(_input : &mut Parser<u8>) -> Result<u8, Labels> { Err(Labels(vec![1])) }
+        fn second(_input : &mut Parser<u8>) -> Result<u8, Labels> { Err(Labels(vec![2])) }
+        fn third(input : &mut Parser<u8>) -> Result<u8, Labels> { input.get().copied() }
+
+        let input = vec![7];
+        let mut buffer = Parser::new(&input);
+
+        assert_eq!(buffer.or([first, second]), Err(Labels(vec![1, 2])));
+        assert_eq!(buffer.or([first, second, third]), Ok(7));
+    }
+
     #[test]
     fn should_indicate_end() {
         let input = vec![1, 2, 3];
@@ -266,6 +1131,29 @@ mod test {
         assert!(buffer.end());
     }
 
+    #[test]
+    fn should_return_all_successful_alternatives() {
+        fn one(input : &mut Parser<usize>) -> Result<usize, ()> {
+            input.get().copied()
+        }
+
+        fn two(input : &mut Parser<usize>) -> Result<usize, ()> {
+            let a = *input.get()?;
+            Ok(a + input.get()?)
+        }
+
+        fn none(_input : &mut Parser<usize>) -> Result<usize, ()> {
+            Err(())
+        }
+
+        let input = vec![1, 2];
+        let buffer = Parser::new(&input);
+
+        assert_eq!(buffer.or_all([one, none, two]), Ok(vec![(1, 1), (3, 2)]));
+        assert_eq!(buffer.index(), 0);
+        assert!(buffer.or_all([none]).is_err());
+    }
+
     #[test]
     fn should_get_option() {
         let input = vec![1, 2, 3];
@@ -290,6 +1178,184 @@ mod test {
         assert_eq!(result, vec![1, 2, 3]);
     }
 
+    #[test]
+    fn should_dispatch_on_first_token() {
+        fn one(input : &mut Parser<u8>) -> Result<u8, ()> {
+            input.expect(&1)?;
+            Ok(10)
+        }
+
+        fn two(input : &mut Parser<u8>) -> Result<u8, ()> {
+            input.expect(&2)?;
+            Ok(20)
+        }
+
+        fn any(input : &mut Parser<u8>) -> Result<u8, ()> {
+            Ok(*input.get()?)
+        }
+
+        let key = |t : &u8| match t { 1 => Some(0), 2 => Some(1), _ => None };
+
+        let input = vec![2, 1, 7];
+        let mut buffer = Parser::new(&input);
+
+        assert_eq!(buffer.dispatch(key, [one, two, any]), Ok(20));
+        assert_eq!(buffer.dispatch(key, [one, two, any]), Ok(10));
+        assert_eq!(buffer.dispatch(key, [one, two, any]), Ok(7));
+        assert_eq!(buffer.index(), 3);
+    }
+
+    #[test]
+    fn should_not_fall_back_when_dispatched_branch_fails() {
+        fn one(input : &mut Parser<u8>) -> Result<u8, ()> {
+            input.expect(&1)?;
+            input.expect(&1)?;
+            Ok(10)
+        }
+
+        fn any(input : &mut Parser<u8>) -> Result<u8, ()> {
+            Ok(*input.get()?)
+        }
+
+        let input = vec![1, 2];
+        let mut buffer = Parser::new(&input);
+
+        assert!(buffer.dispatch(|t| (*t == 1).then_some(0), [one, any]).is_err());
+        assert_eq!(buffer.index(), 0);
+        assert_eq!(buffer.or([one, any]), Ok(1));
+    }
+
+    #[test]
+    fn should_take_while_and_until() {
+        let input = "ab1;c".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        assert_eq!(buffer.take_while::<(), _>(|c| c.is_alphabetic()), Ok(&['a', 'b'][..]));
+        assert_eq!(buffer.take_until::<()>(&';'), Ok(&['1'][..]));
+        assert_eq!(buffer.take_while::<(), _>(|c| c.is_alphabetic()), Ok(&[][..]));
+        assert_eq!(buffer.index(), 3);
+        assert!(buffer.take_until::<()>(&'!').is_err());
+        assert_eq!(buffer.index(), 3);
+    }
+
+    #[test]
+    fn should_skip_many_and_count() {
+        let input = vec![1, 1, 2];
+        let mut buffer = Parser::new(&input);
+
+        let count = buffer.skip_many(|input| input.expect::<()>(&1).map(|_| ()));
+
+        assert_eq!(count, Ok(2));
+        assert_eq!(buffer.index(), 2);
+        assert_eq!(buffer.skip_many(|input| input.expect::<()>(&1).map(|_| ())), Ok(0));
+        assert!(buffer.skip_many(|_| Err::<(), _>(TError(true))).is_err());
+    }
+
+    #[test]
+    fn should_parse_dependent_field_only_when_flagged() {
+        let input = vec![8, 5, 6, 0, 6];
+        let mut buffer = Parser::new(&input);
+
+        let flags = *buffer.get::<()>().unwrap();
+        assert_eq!(buffer.if_then(flags & 8 != 0, |input| input.get::<()>().copied()), Ok(Some(5)));
+        assert_eq!(buffer.if_then(false, |input| input.get::<()>().copied()), Ok(None));
+        assert_eq!(buffer.index(), 2);
+        assert!(buffer.if_then(true, |input| input.expect::<()>(&0).copied()).is_err());
+        assert_eq!(buffer.index(), 2);
+    }
+
+    #[test]
+    fn should_inspect_value_and_span() {
+        let input = vec![0, 1, 2, 3];
+        let mut buffer = Parser::new(&input);
+        buffer.get::<()>().unwrap();
+        let mut seen = vec![];
+
+        let value = buffer.inspect(|input| input.take_while::<(), _>(|x| *x < 3).map(|x| x.len()), |v, span| seen.push((*v, span)));
+
+        assert_eq!(value, Ok(2));
+        assert_eq!(seen, vec![(2, Span::new(1, 3))]);
+        assert!(buffer.inspect(|input| input.expect::<()>(&0).copied(), |_, _| panic!()).is_err());
+        assert_eq!(buffer.index(), 3);
+    }
+
+    #[test]
+    fn should_count_consumed_tokens() {
+        let input = vec![1, 2, 3, 4];
+        let mut buffer = Parser::new(&input);
+        buffer.get::<()>().unwrap();
+
+        assert_eq!(buffer.consumed_count(|input| input.take_while::<(), _>(|x| *x < 4).map(|x| x.len())), Ok((2, 2)));
+        assert_eq!(buffer.consumed_count(|input| input.check::<_, (), _>(|input| input.get::<()>().copied())), Ok((true, 0)));
+        assert!(buffer.consumed_count(|input| input.expect::<()>(&9).copied()).is_err());
+        assert_eq!(buffer.index(), 3);
+    }
+
+    #[test]
+    fn should_map_errors_with_start_index() {
+        let input = vec![1, 2, 3];
+        let mut buffer = Parser::new(&input);
+        buffer.get::<()>().unwrap();
+
+        let result = buffer.map_err_parse(|input| input.seq((|input : &mut Parser<i32>| input.expect::<()>(&2).copied(), |input : &mut Parser<i32>| input.expect::<()>(&4).copied())), |_, start| TError(start == 1));
+
+        assert!(result.unwrap_err().is_fatal());
+        assert_eq!(buffer.index(), 1);
+        let branded = buffer.map_err_parse(|input| input.expect::<()>(&9).copied(), |_, start| Diagnostic::custom(Some("header"), start));
+        assert_eq!(branded.unwrap_err().snapshot(), "at 1: Custom { label: Some(\"header\") }\n");
+        assert!(buffer.map_err_parse(|_| Err::<(), _>(TError(true)), |e, _| e).unwrap_err().is_fatal());
+        assert_eq!(buffer.map_err_parse(|input| input.expect::<()>(&2).copied(), |_, _| TError(true)).map_err(|_| ()), Ok(2));
+    }
+
+    #[test]
+    fn should_check_without_consuming() {
+        let input = vec![1, 2, 3];
+        let mut buffer = Parser::new(&input);
+
+        let matched = buffer.check(|buffer| {
+            assert!(buffer.checking());
+            let items = buffer.list(|buffer| Ok::<usize, ()>(*buffer.get()?))?;
+            assert_eq!(items, vec![1, 2, 3]);
+            Ok::<_, ()>(())
+        }).unwrap();
+
+        assert!(matched);
+        assert!(!buffer.checking());
+        assert_eq!(buffer.index(), 0);
+
+        let matched = buffer.check(|buffer| { buffer.get::<()>()?; Err::<(), ()>(()) }).unwrap();
+
+        assert!(!matched);
+        assert_eq!(buffer.index(), 0);
+    }
+
+    #[test]
+    fn should_check_rules_that_depend_on_list_results() {
+        fn pair(input : &mut Parser<usize>) -> Result<usize, ()> {
+            let items = input.list(|input| input.satisfy::<(), _>(|x : &usize| *x < 5).copied())?;
+            if items.len() != 2 {
+                return Err(());
+            }
+            Ok(items[0] + items[1])
+        }
+        let input = vec![1, 2, 9];
+
+        assert_eq!(Parser::new(&input).check(pair), Ok(true));
+        assert_eq!(Parser::new(&input[1..]).check(pair), Ok(false));
+        assert_eq!(Parser::new(&input).check(|input| input.skip_many(|input| input.satisfy::<(), _>(|x : &usize| *x < 5).copied())), Ok(true));
+    }
+
+    #[test]
+    fn should_indicate_err_when_check_encounters_fatal() {
+        let input = vec![1, 2, 3];
+        let mut buffer = Parser::new(&input);
+
+        let result = buffer.check(|_input| Err::<(), _>(TError(true)));
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().is_fatal());
+    }
+
     #[test]
     fn should_get_or() {
         fn even(input : &mut Parser<usize>) -> Result<bool, ()> {
@@ -318,6 +1384,36 @@ mod test {
         assert_eq!(result, vec![false, true, false]);
     }
 
+    #[test]
+    fn should_parse_fixed_arity_array() {
+        let input = vec![1, 2, 3, 4, 5];
+        let mut buffer = Parser::new(&input);
+
+        let rgb : Result<[u8; 3], ()> = buffer.array_of(|input| input.get().copied());
+        assert_eq!(rgb, Ok([1, 2, 3]));
+        assert!(buffer.array_of::<3, u8, (), _>(|input| input.get().copied()).is_err());
+        assert_eq!(buffer.index(), 3);
+        assert_eq!(buffer.array_of::<0, u8, (), _>(|input| input.get().copied()), Ok([]));
+    }
+
+    #[test]
+    fn should_report_matched_branch_index() {
+        fn zero(input : &mut Parser<usize>) -> Result<usize, ()> {
+            input.expect(&0).copied()
+        }
+
+        fn any(input : &mut Parser<usize>) -> Result<usize, ()> {
+            input.get().copied()
+        }
+
+        let input = vec![0, 7];
+        let mut buffer = Parser::new(&input);
+
+        assert_eq!(buffer.or_indexed([zero, any]), Ok((0, 0)));
+        assert_eq!(buffer.or_indexed([zero, any]), Ok((1, 7)));
+        assert!(buffer.or_indexed([zero, any]).is_err());
+    }
+
     #[test]
     fn should_early_exit_or_on_fatal() {
         fn even(input : &mut Parser<usize>) -> Result<bool, TError> {
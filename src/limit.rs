@@ -0,0 +1,106 @@
+
+use crate::{ErrorKind, JlnError, Parser};
+
+impl<'a, T> Parser<'a, T> {
+    pub fn length_value<N, S, E, L, F>(&mut self, length : L, body : F) -> Result<S, E>
+        where N : TryInto<usize>,
+              E : JlnError,
+              L : FnOnce(&mut Parser<'a, T>) -> Result<N, E>,
+              F : FnOnce(&mut Parser<'a, T>) -> Result<S, E>,
+    {
+        self.with_rollback(|input| {
+            let at = input.index;
+            let length = length(input)?.try_into().map_err(|_| E::from_kind(ErrorKind::Overflow, at))?;
+            let start = input.index;
+            let end = match start.checked_add(length) {
+                Some(end) if end <= input.bound() => end,
+                _ if input.awaiting_input() => { return Err(input.exhausted()); },
+                _ => { return Err(E::from_kind(ErrorKind::Truncated, start)); },
+            };
+            input.bounded(end, |input| {
+                let value = body(input)?;
+                input.consume_trivia();
+                if input.index == end {
+                    Ok(value)
+                }
+                else {
+                    Err(E::from_kind(ErrorKind::Unexpected, input.index))
+                }
+            })
+        })
+    }
+
+    pub fn limit<S, E : JlnError, F : FnOnce(&mut Parser<'a, T>) -> Result<S, E>>(&mut self, n : usize, f : F) -> Result<S, E> {
+        let end = self.index.saturating_add(n);
+        self.bounded(end, |input| input.attempt(f))
+    }
+
+    pub(crate) fn bound(&self) -> usize {
+        match self.limit {
+            Some(limit) => limit.min(self.input.len()),
+            None => self.input.len(),
+        }
+    }
+
+    pub(crate) fn bounded<S, F : FnOnce(&mut Parser<'a, T>) -> S>(&mut self, end : usize, f : F) -> S {
+        let previous = self.limit;
+        self.limit = Some(end.min(self.bound()));
+        let result = f(self);
+        self.limit = previous;
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn length(input : &mut Parser<u8>) -> Result<u8, ()> {
+        input.get().copied()
+    }
+
+    #[test]
+    fn should_confine_body_to_block() {
+        let input = vec![2, 7, 8, 9];
+        let mut buffer = Parser::new(&input);
+
+        let body = buffer.length_value(length, |input| input.list(|input| input.get::<()>().copied()));
+
+        assert_eq!(body, Ok(vec![7, 8]));
+        assert_eq!(buffer.index(), 3);
+        assert_eq!(buffer.get::<()>(), Ok(&9));
+    }
+
+    #[test]
+    fn should_reject_under_consumed_block() {
+        let input = vec![2, 7, 8];
+        let mut buffer = Parser::new(&input);
+
+        assert!(buffer.length_value(length, |input| input.get::<()>().copied()).is_err());
+        assert_eq!(buffer.index(), 0);
+    }
+
+    #[test]
+    fn should_reject_block_past_end_of_input() {
+        let input = vec![3, 7, 8];
+        let mut buffer = Parser::new(&input);
+        let result = buffer.length_value(length, |input| Ok::<_, ()>(input.index()));
+
+        assert!(result.is_err());
+        assert_eq!(buffer.index(), 0);
+    }
+
+    #[test]
+    fn should_cap_sub_rule_to_next_tokens() {
+        let input = vec![1, 2, 3, 4];
+        let mut buffer = Parser::new(&input);
+
+        let field = buffer.limit(2, |input| input.list(|input| input.get::<()>().copied()));
+
+        assert_eq!(field, Ok(vec![1, 2]));
+        assert!(buffer.limit(1, |input| input.tag::<()>(&[3, 4]).map(|x| x.len())).is_err());
+        assert_eq!(buffer.index(), 2);
+        assert_eq!(buffer.limit(9, |input| input.tag::<()>(&[3, 4]).map(|x| x.to_vec())), Ok(vec![3, 4]));
+        assert!(buffer.end());
+    }
+}
@@ -0,0 +1,101 @@
+
+use std::io::{self, BufRead};
+
+use crate::Parser;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Line {
+    pub number : usize,
+    pub offset : usize,
+    pub text : String,
+}
+
+pub struct Lines<R> {
+    reader : R,
+    number : usize,
+    offset : usize,
+}
+
+impl Line {
+    pub fn parser(&self) -> Parser<'static, char> {
+        self.text.as_str().into()
+    }
+
+    pub fn position(&self, index : usize) -> (usize, usize) {
+        (self.number, index + 1)
+    }
+}
+
+impl<R : BufRead> Lines<R> {
+    pub fn new(reader : R) -> Self {
+        Lines { reader, number: 0, offset: 0 }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R : BufRead> Iterator for Lines<R> {
+    type Item = io::Result<Line>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut text = String::new();
+        let read = match self.reader.read_line(&mut text) {
+            Ok(0) => { return None; },
+            Ok(read) => read,
+            Err(e) => { return Some(Err(e)); },
+        };
+        if text.ends_with('\n') {
+            text.pop();
+            if text.ends_with('\r') {
+                text.pop();
+            }
+        }
+        self.number += 1;
+        let line = Line { number: self.number, offset: self.offset, text };
+        self.offset += read;
+        Some(Ok(line))
+    }
+}
+
+impl<'a> Parser<'a, String> {
+    pub fn from_lines<R : BufRead>(reader : R) -> io::Result<Parser<'a, String>> {
+        let lines = Lines::new(reader).map(|x| x.map(|x| x.text)).collect::<io::Result<Vec<_>>>()?;
+        Ok(lines.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::Diagnostic;
+
+    #[test]
+    fn should_read_lines_lazily_with_positions() {
+        let mut lines = Lines::new(Cursor::new("GET /a 200\r\nbad line\nPUT /b 201"));
+
+        let first = lines.next().unwrap().unwrap();
+        assert_eq!((first.number, first.offset, first.text.as_str()), (1, 0, "GET /a 200"));
+        assert_eq!(first.parser().tag_str::<()>("GET").map(|x| x.len()), Ok(3));
+        let second = lines.next().unwrap().unwrap();
+        let error = second.parser().tag_str::<Diagnostic>("bad x").map(|_| ()).unwrap_err();
+        let Diagnostic::Expected { index, .. } = error else { panic!() };
+        assert_eq!(second.position(index), (2, 5));
+        assert_eq!(second.offset, 12);
+        assert_eq!(lines.next().unwrap().unwrap().text, "PUT /b 201");
+        assert!(lines.next().is_none());
+    }
+
+    #[test]
+    fn should_parse_over_line_tokens() {
+        let mut buffer = Parser::from_lines(Cursor::new("[a]\nx=1\n\n[b]\n")).unwrap();
+
+        assert_eq!(buffer.expect::<()>(&String::from("[a]")).cloned(), Ok(String::from("[a]")));
+        assert_eq!(buffer.take_while::<(), _>(|x| !x.starts_with('[')).map(|x| x.len()), Ok(2));
+        assert_eq!(buffer.get::<()>(), Ok(&String::from("[b]")));
+        assert!(buffer.end());
+    }
+}
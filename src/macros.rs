@@ -0,0 +1,161 @@
+
+#[macro_export]
+macro_rules! rule {
+    ($($(#[$meta:meta])* $vis:vis fn $name:ident($input:ident : &mut Parser<$t:ty>) -> $ret:ty $body:block)*) => {
+        $(
+            $(#[$meta])*
+            $vis fn $name($input : &mut $crate::Parser<'_, $t>) -> $ret {
+                $input.context(stringify!($name), |$input| $body)
+            }
+        )*
+    };
+}
+
+#[macro_export]
+macro_rules! assert_parses {
+    ($rule:expr, $input:expr $(,)?) => {
+        {
+            let mut __jln_parser = $crate::Parser::from($input);
+            match ($rule)(&mut __jln_parser) {
+                Ok(value) => {
+                    if !__jln_parser.end() {
+                        panic!("rule stopped before end of input: {:?}", __jln_parser);
+                    }
+                    value
+                },
+                Err(e) => { panic!("rule failed with {:?}: {:?}", e, __jln_parser); },
+            }
+        }
+    };
+    ($rule:expr, $input:expr, $expected:expr $(,)?) => {
+        {
+            let mut __jln_parser = $crate::Parser::from($input);
+            match ($rule)(&mut __jln_parser) {
+                Ok(value) => {
+                    if value != $expected {
+                        panic!("rule produced {:?} instead of {:?}: {:?}", value, $expected, __jln_parser);
+                    }
+                    if !__jln_parser.end() {
+                        panic!("rule stopped before end of input: {:?}", __jln_parser);
+                    }
+                },
+                Err(e) => { panic!("rule failed with {:?}: {:?}", e, __jln_parser); },
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! assert_parse_err {
+    ($rule:expr, $input:expr $(,)?) => {
+        $crate::assert_parse_err!($rule, $input, _)
+    };
+    ($rule:expr, $input:expr, $pattern:pat $(,)?) => {
+        {
+            let mut __jln_parser = $crate::Parser::from($input);
+            match ($rule)(&mut __jln_parser) {
+                Err(e) => {
+                    if !matches!(e, $pattern) {
+                        panic!("rule failed with {:?} instead of {}: {:?}", e, stringify!($pattern), __jln_parser);
+                    }
+                },
+                Ok(value) => { panic!("rule unexpectedly produced {:?}: {:?}", value, __jln_parser); },
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{ErrorKind, JlnError, Parser};
+
+    #[derive(Debug, PartialEq)]
+    struct LError(Vec<(&'static str, usize)>);
+
+    impl JlnError for LError {
+        fn is_fatal(&self) -> bool { false }
+        fn eof() -> Self { LError(vec![]) }
+        fn aggregate(errors : Vec<Self>) -> Self { LError(errors.into_iter().flat_map(|x| x.0).collect()) }
+        fn from_kind(_kind : ErrorKind, _index : usize) -> Self { LError(vec![]) }
+        fn context(mut self, rule : &'static str, index : usize) -> Self {
+            self.0.push((rule, index));
+            self
+        }
+    }
+
+    rule! {
+        fn zero(input : &mut Parser<u8>) -> Result<u8, LError> {
+            match *input.get()? {
+                0 => Ok(0),
+                _ => Err(LError(vec![])),
+            }
+        }
+
+        fn one(input : &mut Parser<u8>) -> Result<u8, LError> {
+            match *input.get()? {
+                1 => Ok(1),
+                _ => Err(LError(vec![])),
+            }
+        }
+
+        fn bit(input : &mut Parser<u8>) -> Result<u8, LError> {
+            input.or([zero, one])
+        }
+    }
+
+    #[test]
+    fn should_define_rules_usable_in_or() {
+        let input = vec![1, 0];
+        let mut buffer = Parser::new(&input);
+
+        let result = buffer.list(bit).unwrap();
+
+        assert_eq!(result, vec![1, 0]);
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum AtError {
+        At(usize),
+        Eof,
+    }
+
+    impl JlnError for AtError {
+        fn is_fatal(&self) -> bool { false }
+        fn eof() -> Self { AtError::Eof }
+        fn aggregate(mut errors : Vec<Self>) -> Self { errors.pop().unwrap_or(AtError::Eof) }
+        fn from_kind(_kind : ErrorKind, index : usize) -> Self { AtError::At(index) }
+    }
+
+    fn digits(input : &mut Parser<char>) -> Result<u32, AtError> {
+        input.integer()
+    }
+
+    #[test]
+    fn should_assert_successful_parses() {
+        assert_parses!(digits, "42", 42);
+        assert_eq!(assert_parses!(bit, vec![1]), 1);
+    }
+
+    #[test]
+    fn should_assert_failed_parses() {
+        assert_parse_err!(digits, "x");
+        assert_parse_err!(digits, "", AtError::At(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "rule stopped before end of input")]
+    fn should_panic_when_input_remains() {
+        assert_parses!(digits, "4 2", 4);
+    }
+
+    #[test]
+    fn should_label_errors_with_rule_name() {
+        let input = vec![0, 2];
+        let mut buffer = Parser::new(&input);
+        buffer.get::<LError>().unwrap();
+
+        let result = bit(&mut buffer);
+
+        assert_eq!(result, Err(LError(vec![("zero", 1), ("one", 1), ("bit", 1)])));
+    }
+}
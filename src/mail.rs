@@ -0,0 +1,342 @@
+
+use crate::{ErrorKind, JlnError, Parser, Span, TextToken};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MailHeader {
+    pub name : String,
+    pub value : String,
+    pub span : Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mailbox {
+    pub display : Option<String>,
+    pub local : String,
+    pub domain : String,
+    pub span : Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MailAddress {
+    Mailbox(Mailbox),
+    Group { name : String, members : Vec<Mailbox>, span : Span },
+}
+
+impl MailHeader {
+    pub fn is(&self, name : &str) -> bool {
+        self.name.eq_ignore_ascii_case(name)
+    }
+}
+
+impl Mailbox {
+    pub fn address(&self) -> String {
+        format!("{}@{}", self.local, self.domain)
+    }
+}
+
+fn atext(c : char) -> bool {
+    c.is_ascii_alphanumeric() || "!#$%&'*+-/=?^_`{|}~".contains(c)
+}
+
+impl<'a, T : TextToken> Parser<'a, T> {
+    pub fn mail_header<E : JlnError>(&mut self) -> Result<MailHeader, E> {
+        self.with_rollback(|input| input.no_trivia(|input| {
+            let start = input.index;
+            let name = input.take_while::<E, _>(|t| t.to_char().is_ascii_graphic() && t.to_char() != ':')?.iter().map(|t| t.to_char()).collect::<String>();
+            if name.is_empty() {
+                return Err(E::from_kind(ErrorKind::Unexpected, start));
+            }
+            input.expect_no_case::<E>(':')?;
+            let mut value = String::new();
+            loop {
+                value.extend(input.take_while::<E, _>(|t| !matches!(t.to_char(), '\r' | '\n'))?.iter().map(|t| t.to_char()));
+                if !input.mail_fold::<E>()? {
+                    break;
+                }
+            }
+            let span = Span::new(start, input.index);
+            input.mail_line_end()?;
+            Ok(MailHeader { name, value: value.trim_matches([' ', '\t']).to_string(), span })
+        }))
+    }
+
+    pub fn mail_field<S, E : JlnError, F : FnOnce(&mut Parser<'a, T>) -> Result<S, E>>(&mut self, name : &str, f : F) -> Result<S, E> {
+        self.with_rollback(|input| input.no_trivia(|input| {
+            input.tag_no_case::<E>(name)?;
+            input.expect_no_case::<E>(':')?;
+            let value = f(input)?;
+            input.cfws()?;
+            input.mail_line_end()?;
+            Ok(value)
+        }))
+    }
+
+    pub fn cfws<E : JlnError>(&mut self) -> Result<(), E> {
+        loop {
+            match self.peek::<E>().map(|t| t.to_char()) {
+                Ok(' ' | '\t') => { self.get::<E>()?; },
+                Ok('(') => { self.mail_comment()?; },
+                Ok('\r' | '\n') if self.mail_fold::<E>()? => { },
+                _ => { return Ok(()); },
+            }
+        }
+    }
+
+    pub fn mail_quoted<E : JlnError>(&mut self) -> Result<String, E> {
+        self.no_trivia(|input| {
+            let start = input.index;
+            input.expect_no_case::<E>('"')?;
+            let mut value = String::new();
+            loop {
+                input.mail_unfold(start)?;
+                match input.mail_char(start)? {
+                    '"' => { return Ok(value); },
+                    '\\' => { value.push(input.mail_char(start)?); },
+                    c => { value.push(c); },
+                }
+            }
+        })
+    }
+
+    pub fn mailbox<E : JlnError>(&mut self) -> Result<Mailbox, E> {
+        self.with_rollback(|input| input.no_trivia(|input| {
+            input.cfws()?;
+            let start = input.index;
+            let named = input.option(|input| {
+                let display = input.option(|input| input.mail_phrase())?;
+                input.cfws()?;
+                input.expect_no_case::<E>('<')?;
+                let (local, domain) = input.addr_spec()?;
+                input.expect_no_case::<E>('>')?;
+                Ok((display, local, domain))
+            })?;
+            let (display, local, domain) = match named {
+                Some(named) => named,
+                None => {
+                    let (local, domain) = input.addr_spec()?;
+                    (None, local, domain)
+                },
+            };
+            let span = Span::new(start, input.index);
+            input.cfws()?;
+            Ok(Mailbox { display, local, domain, span })
+        }))
+    }
+
+    pub fn mail_address_list<E : JlnError>(&mut self) -> Result<Vec<MailAddress>, E> {
+        self.with_rollback(|input| input.no_trivia(|input| {
+            let mut addresses = vec![input.mail_address()?];
+            while input.peek::<E>().is_ok_and(|t| t.to_char() == ',') {
+                input.get::<E>()?;
+                addresses.push(input.mail_address()?);
+            }
+            Ok(addresses)
+        }))
+    }
+
+    fn mail_address<E : JlnError>(&mut self) -> Result<MailAddress, E> {
+        let group = self.option(|input| {
+            input.cfws()?;
+            let start = input.index;
+            let name = input.mail_phrase()?;
+            input.expect_no_case::<E>(':')?;
+            let mut members = vec![];
+            input.cfws()?;
+            if input.peek::<E>()?.to_char() != ';' {
+                members.push(input.mailbox()?);
+                while input.peek::<E>().is_ok_and(|t| t.to_char() == ',') {
+                    input.get::<E>()?;
+                    members.push(input.mailbox()?);
+                }
+            }
+            input.expect_no_case::<E>(';')?;
+            let span = Span::new(start, input.index);
+            input.cfws()?;
+            Ok(MailAddress::Group { name, members, span })
+        })?;
+        match group {
+            Some(group) => Ok(group),
+            None => self.mailbox().map(MailAddress::Mailbox),
+        }
+    }
+
+    fn mail_phrase<E : JlnError>(&mut self) -> Result<String, E> {
+        let mut words : Vec<String> = vec![];
+        loop {
+            self.cfws()?;
+            let word = match self.peek::<E>().map(|t| t.to_char()) {
+                Ok('"') => self.mail_quoted()?,
+                Ok(c) if atext(c) || c == '.' => self.take_while::<E, _>(|t| atext(t.to_char()) || t.to_char() == '.')?.iter().map(|t| t.to_char()).collect(),
+                _ => { break; },
+            };
+            words.push(word);
+        }
+        if words.is_empty() {
+            return Err(E::from_kind(ErrorKind::Unexpected, self.index));
+        }
+        Ok(words.join(" "))
+    }
+
+    fn addr_spec<E : JlnError>(&mut self) -> Result<(String, String), E> {
+        self.cfws()?;
+        let local = match self.peek::<E>()?.to_char() {
+            '"' => self.mail_quoted()?,
+            _ => self.dot_atom()?,
+        };
+        self.cfws()?;
+        self.expect_no_case::<E>('@')?;
+        self.cfws()?;
+        let domain = match self.peek::<E>()?.to_char() {
+            '[' => {
+                let start = self.index;
+                self.get::<E>()?;
+                let mut literal = String::from("[");
+                loop {
+                    match self.mail_char(start)? {
+                        ']' => { break; },
+                        '\\' => { literal.push(self.mail_char(start)?); },
+                        c => { literal.push(c); },
+                    }
+                }
+                literal.push(']');
+                literal
+            },
+            _ => self.dot_atom()?,
+        };
+        Ok((local, domain))
+    }
+
+    fn dot_atom<E : JlnError>(&mut self) -> Result<String, E> {
+        let mut atom = String::new();
+        loop {
+            let start = self.index;
+            let part = self.take_while::<E, _>(|t| atext(t.to_char()))?;
+            if part.is_empty() {
+                self.peek::<E>()?;
+                return Err(E::from_kind(ErrorKind::Unexpected, start));
+            }
+            atom.extend(part.iter().map(|t| t.to_char()));
+            if !self.peek::<E>().is_ok_and(|t| t.to_char() == '.') {
+                return Ok(atom);
+            }
+            self.get::<E>()?;
+            atom.push('.');
+        }
+    }
+
+    fn mail_comment<E : JlnError>(&mut self) -> Result<(), E> {
+        let start = self.index;
+        let mut depth = 0usize;
+        loop {
+            self.mail_unfold(start)?;
+            match self.mail_char(start)? {
+                '(' => { depth += 1; },
+                ')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Ok(());
+                    }
+                },
+                '\\' => { self.mail_char(start)?; },
+                _ => { },
+            }
+        }
+    }
+
+    fn mail_fold<E : JlnError>(&mut self) -> Result<bool, E> {
+        let rest = &self.input[self.index..self.bound()];
+        let newline = match rest.iter().map(|t| t.to_char()).take(2).collect::<String>().as_str() {
+            "\r\n" => 2,
+            s if s.starts_with('\n') => 1,
+            _ => { return Ok(false); },
+        };
+        if !rest.get(newline).is_some_and(|t| matches!(t.to_char(), ' ' | '\t')) {
+            return Ok(false);
+        }
+        for _ in 0..newline {
+            self.get::<E>()?;
+        }
+        Ok(true)
+    }
+
+    fn mail_unfold<E : JlnError>(&mut self, start : usize) -> Result<(), E> {
+        while self.peek::<E>().is_ok_and(|t| matches!(t.to_char(), '\r' | '\n')) {
+            if !self.mail_fold::<E>()? {
+                return Err(self.raise(ErrorKind::Unterminated, start));
+            }
+        }
+        Ok(())
+    }
+
+    fn mail_line_end<E : JlnError>(&mut self) -> Result<(), E> {
+        if self.end() {
+            return Ok(());
+        }
+        if self.peek::<E>()?.to_char() == '\r' {
+            self.get::<E>()?;
+        }
+        self.expect_no_case::<E>('\n')?;
+        Ok(())
+    }
+
+    fn mail_char<E : JlnError>(&mut self, start : usize) -> Result<char, E> {
+        if self.end() {
+            return Err(self.raise(ErrorKind::Unterminated, start));
+        }
+        Ok(self.get::<E>()?.to_char())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Diagnostic;
+
+    fn chars(text : &str) -> Vec<char> {
+        text.chars().collect()
+    }
+
+    #[test]
+    fn should_unfold_header_values() {
+        let input = chars("Subject: hello\r\n  world \r\nx-Other:1\n");
+        let mut buffer = Parser::new(&input);
+
+        let subject = buffer.mail_header::<()>().unwrap();
+        assert_eq!((subject.name.as_str(), subject.value.as_str(), subject.span), ("Subject", "hello  world", Span::new(0, 24)));
+        let other = buffer.mail_header::<()>().unwrap();
+        assert!(other.is("X-OTHER"));
+        assert_eq!(other.value, "1");
+        assert!(buffer.end());
+    }
+
+    #[test]
+    fn should_parse_address_lists_with_comments() {
+        let input = chars("to: \"Doe, J.\" (work) <j.doe@example.com>,\r\n plain@host (x(y)),\r\n Team: a@b, C <c@[10.0.0.1]>;, \"odd\\\"one\"@q\r\n");
+        let mut buffer = Parser::new(&input);
+
+        let list = buffer.mail_field::<_, Diagnostic, _>("To", |input| input.mail_address_list()).unwrap();
+
+        let MailAddress::Mailbox(first) = &list[0] else { panic!() };
+        assert_eq!(first.display.as_deref(), Some("Doe, J."));
+        assert_eq!(first.address(), "j.doe@example.com");
+        assert_eq!(first.span, Span::new(4, 40));
+        let MailAddress::Mailbox(second) = &list[1] else { panic!() };
+        assert_eq!((second.display.as_deref(), second.address().as_str()), (None, "plain@host"));
+        let MailAddress::Group { name, members, .. } = &list[2] else { panic!() };
+        assert_eq!(name, "Team");
+        assert_eq!(members.iter().map(|x| x.address()).collect::<Vec<_>>(), vec!["a@b", "c@[10.0.0.1]"]);
+        let MailAddress::Mailbox(last) = &list[3] else { panic!() };
+        assert_eq!(last.local, "odd\"one");
+        assert!(buffer.end());
+    }
+
+    #[test]
+    fn should_reject_malformed_addresses() {
+        let parse = |text : &str| Parser::new(&chars(text)).mail_address_list::<Diagnostic>().map_err(|e| e.snapshot());
+
+        assert_eq!(parse("a (open"), Err("at 2: Unterminated\n".into()));
+        assert_eq!(parse("\"x@y"), Err("at 0: Unterminated\n".into()));
+        assert!(parse("a@").is_err());
+        assert!(parse("<a@b").is_err());
+    }
+}
@@ -0,0 +1,434 @@
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::ops::Range;
+use std::rc::Rc;
+
+use crate::{Event, JlnError, Parser, Span, Warning};
+
+pub(crate) type MemoTable = Rc<RefCell<HashMap<(&'static str, usize), Slot, MemoHasher>>>;
+
+pub struct Memo(MemoTable);
+
+pub(crate) struct Slot {
+    extent : usize,
+    shift : isize,
+    entry : Box<dyn Any>,
+}
+
+#[derive(Clone)]
+pub(crate) struct MemoHasher(Rc<dyn Fn() -> Box<dyn Hasher>>);
+
+impl BuildHasher for MemoHasher {
+    type Hasher = Box<dyn Hasher>;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        (self.0)()
+    }
+}
+
+struct Entry<S, E> {
+    result : Result<S, E>,
+    end : usize,
+    reach : usize,
+    events : Vec<Event>,
+    setting : Setting,
+    state : Option<Rc<dyn Any>>,
+    warnings : Vec<Warning>,
+    errors : Vec<Rc<dyn Any>>,
+}
+
+struct Setting {
+    limit : Option<usize>,
+    trivia : Option<usize>,
+    skip_trivia : bool,
+    indents : Vec<usize>,
+    checking : bool,
+    state : Option<Rc<dyn Any>>,
+}
+
+impl Setting {
+    fn of<T>(parser : &Parser<'_, T>) -> Setting {
+        Setting {
+            limit: parser.limit,
+            trivia: parser.trivia.map(|x| x as usize),
+            skip_trivia: parser.skip_trivia,
+            indents: parser.indents.clone(),
+            checking: parser.checking,
+            state: parser.state.clone(),
+        }
+    }
+
+    fn matches<T>(&self, parser : &Parser<'_, T>) -> bool {
+        let same_state = match (&self.state, &parser.state) {
+            (None, None) => true,
+            (Some(a), Some(b)) => std::ptr::addr_eq(Rc::as_ptr(a), Rc::as_ptr(b)),
+            _ => false,
+        };
+        same_state
+            && self.limit == parser.limit
+            && self.trivia == parser.trivia.map(|x| x as usize)
+            && self.skip_trivia == parser.skip_trivia
+            && self.indents == parser.indents
+            && self.checking == parser.checking
+    }
+}
+
+impl<'a, T> Parser<'a, T> {
+    pub fn enable_memo(&mut self) {
+        if self.memo.is_none() {
+            self.enable_memo_with_hasher(RandomState::new());
+        }
+    }
+
+    pub fn enable_memo_with_hasher<B : BuildHasher + 'static>(&mut self, hasher : B) {
+        let hasher = MemoHasher(Rc::new(move || Box::new(hasher.build_hasher())));
+        self.memo = Some(Rc::new(RefCell::new(HashMap::with_hasher(hasher))));
+    }
+
+    pub fn clear_memo(&mut self) {
+        if let Some(table) = &self.memo {
+            table.borrow_mut().clear();
+        }
+    }
+
+    pub fn invalidate_memo(&mut self, range : Range<usize>, delta : isize) {
+        if let Some(table) = &self.memo {
+            let mut table = table.borrow_mut();
+            for ((rule, start), mut slot) in table.drain().collect::<Vec<_>>() {
+                if slot.extent < range.start {
+                    table.insert((rule, start), slot);
+                }
+                else if start >= range.end {
+                    slot.extent = moved(slot.extent, delta);
+                    slot.shift += delta;
+                    table.insert((rule, moved(start, delta)), slot);
+                }
+            }
+        }
+    }
+
+    pub fn take_memo(&mut self) -> Option<Memo> {
+        self.memo.take().map(Memo)
+    }
+
+    pub fn adopt_memo(&mut self, memo : Memo) {
+        self.memo = Some(memo.0);
+    }
+
+    pub fn disable_memo(&mut self) {
+        self.memo = None;
+    }
+
+    pub fn memo<S, E, F>(&mut self, rule : &'static str, f : F) -> Result<S, E>
+        where S : Clone + 'static, E : JlnError + Clone + 'static, F : FnOnce(&mut Parser<'a, T>) -> Result<S, E>
+    {
+        let table = match &self.memo {
+            Some(table) => Rc::clone(table),
+            None => { return self.with_rollback(f); },
+        };
+
+        if self.pushback.is_some() {
+            return self.with_rollback(f);
+        }
+
+        let key = (rule, self.index);
+        if let Some((entry, shift)) = table.borrow().get(&key).and_then(|x| Some((x.entry.downcast_ref::<Entry<S, E>>()?, x.shift))).filter(|x| x.0.setting.matches(self)) {
+            self.index = moved(entry.end, shift);
+            self.reach = self.reach.max(moved(entry.reach, shift));
+            self.state = entry.state.clone();
+            for event in &entry.events {
+                self.emit(shifted(event, shift));
+            }
+            for warning in &entry.warnings {
+                self.warn(warning.note, Span { start: moved(warning.span.start, shift), end: moved(warning.span.end, shift) });
+            }
+            for error in &entry.errors {
+                self.replay_error(Rc::clone(error));
+            }
+            return entry.result.clone();
+        }
+
+        let setting = Setting::of(self);
+        let (warnings, errors) = (self.warning_mark(), self.error_mark());
+        let (result, reach) = self.attempt_reach(|input| {
+            let mark = input.event_mark();
+            let value = f(input)?;
+            Ok((value, input.events_since(mark)))
+        });
+        let (result, events) = match result {
+            Ok((value, events)) => (Ok(value), events),
+            Err(e) => (Err(e), vec![]),
+        };
+        let extent = reach.max(self.index);
+        let entry = Entry {
+            result: result.clone(),
+            end: self.index,
+            reach,
+            events,
+            setting,
+            state: self.state.clone(),
+            warnings: self.warnings_since(warnings),
+            errors: self.errors_since(errors),
+        };
+        table.borrow_mut().insert(key, Slot { extent, shift: 0, entry: Box::new(entry) });
+        result
+    }
+}
+
+fn moved(index : usize, shift : isize) -> usize {
+    index.saturating_add_signed(shift)
+}
+
+fn shifted(event : &Event, shift : isize) -> Event {
+    match *event {
+        Event::Start { kind, index } => Event::Start { kind, index: moved(index, shift) },
+        Event::Token { index } => Event::Token { index: moved(index, shift) },
+        Event::End { kind, index } => Event::End { kind, index: moved(index, shift) },
+        Event::Error { label, index } => Event::Error { label, index: moved(index, shift) },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+
+    use super::*;
+
+    #[test]
+    fn should_reuse_memoized_result() {
+        let input = vec![1, 2, 3];
+        let mut buffer = Parser::new(&input);
+        buffer.enable_memo();
+        let calls = Cell::new(0);
+
+        let rule = |buffer : &mut Parser<usize>| {
+            calls.set(calls.get() + 1);
+            buffer.get::<()>()?;
+            Ok::<usize, ()>(*buffer.get()?)
+        };
+
+        let first = buffer.clone().memo("pair", rule);
+        let second = buffer.memo("pair", rule);
+
+        assert_eq!(first, Ok(2));
+        assert_eq!(second, Ok(2));
+        assert_eq!(buffer.index(), 2);
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn should_keep_memoized_entries_across_or_alternatives() {
+        fn item(input : &mut Parser<usize>) -> Result<usize, ()> {
+            let value = *input.get()?;
+            input.with_rollback(|input| { input.peek::<()>()?; Ok(()) })?;
+            Ok(value)
+        }
+
+        fn first(input : &mut Parser<usize>) -> Result<usize, ()> {
+            input.memo("item", item)?;
+            Err(())
+        }
+
+        fn second(input : &mut Parser<usize>) -> Result<usize, ()> {
+            input.memo("item", item)
+        }
+
+        let input = vec![4, 5];
+        let mut buffer = Parser::new(&input);
+        buffer.enable_memo();
+
+        let result = buffer.or([first, second]);
+
+        assert_eq!(result, Ok(4));
+        assert_eq!(buffer.index(), 1);
+        assert_eq!(buffer.memo.as_ref().unwrap().borrow().len(), 1);
+    }
+
+    #[test]
+    fn should_memoize_failures() {
+        let input = vec![1];
+        let mut buffer = Parser::new(&input);
+        buffer.enable_memo();
+        let calls = Cell::new(0);
+
+        let rule = |buffer : &mut Parser<usize>| {
+            calls.set(calls.get() + 1);
+            buffer.get::<()>()?;
+            Err::<usize, ()>(())
+        };
+
+        assert!(buffer.memo("fail", rule).is_err());
+        assert!(buffer.memo("fail", rule).is_err());
+        assert_eq!(buffer.index(), 0);
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn should_replay_side_effects_of_memoized_run() {
+        let input = [1, 2, 3];
+        let mut buffer = Parser::builder(&input[..]).warnings().errors().build();
+        buffer.enable_memo();
+        buffer.set_state(0usize);
+        let calls = Cell::new(0);
+        let rule = |input : &mut Parser<usize>| {
+            calls.set(calls.get() + 1);
+            let value = *input.get::<()>()?;
+            *input.state_mut::<usize>().unwrap() += value;
+            input.warn("odd", crate::Span::new(0, 1));
+            input.record_error(7u8);
+            Ok::<usize, ()>(value)
+        };
+
+        let mut first = buffer.clone();
+        assert_eq!(first.memo("step", rule), Ok(1));
+        assert_eq!(buffer.memo("step", rule), Ok(1));
+
+        assert_eq!(calls.get(), 1);
+        assert_eq!(buffer.state::<usize>(), Some(&1));
+        assert_eq!(buffer.warnings().len(), 2);
+        assert_eq!(buffer.errors::<u8>(), vec![7, 7]);
+        assert_eq!(buffer.reach, 1);
+    }
+
+    #[test]
+    fn should_rerun_when_parser_setting_differs() {
+        let input = vec![1, 2, 3];
+        let mut buffer = Parser::new(&input);
+        buffer.enable_memo();
+        let calls = Cell::new(0);
+        let rule = |input : &mut Parser<usize>| {
+            calls.set(calls.get() + 1);
+            input.list(|input| input.get::<()>().copied())
+        };
+
+        assert_eq!(buffer.clone().limit(1, |input| input.memo("all", rule)), Ok(vec![1]));
+        assert_eq!(buffer.clone().memo("all", rule), Ok(vec![1, 2, 3]));
+        buffer.set_state(5u8);
+        assert_eq!(buffer.clone().memo("all", rule), Ok(vec![1, 2, 3]));
+        buffer.unread(0);
+        assert_eq!(buffer.memo("all", rule), Ok(vec![0, 1, 2, 3]));
+        assert_eq!(calls.get(), 4);
+    }
+
+    struct Counting;
+
+    struct CountingHasher(u64);
+
+    thread_local! {
+        static BUILT : Cell<usize> = const { Cell::new(0) };
+    }
+
+    impl BuildHasher for Counting {
+        type Hasher = CountingHasher;
+
+        fn build_hasher(&self) -> CountingHasher {
+            BUILT.with(|x| x.set(x.get() + 1));
+            CountingHasher(0)
+        }
+    }
+
+    impl Hasher for CountingHasher {
+        fn finish(&self) -> u64 {
+            self.0
+        }
+
+        fn write(&mut self, bytes : &[u8]) {
+            for b in bytes {
+                self.0 = self.0.rotate_left(5) ^ *b as u64;
+            }
+        }
+    }
+
+    #[test]
+    fn should_use_supplied_hasher() {
+        let input = vec![1, 2];
+        let mut buffer = Parser::new(&input);
+        buffer.enable_memo_with_hasher(Counting);
+
+        assert_eq!(buffer.memo("one", |input| input.get::<()>().copied()), Ok(1));
+        assert!(BUILT.with(|x| x.get()) > 0);
+        assert_eq!(buffer.memo.as_ref().unwrap().borrow().len(), 1);
+    }
+
+    #[test]
+    fn should_invalidate_overlapping_entries() {
+        let input = vec![1, 2, 3, 4, 5, 6];
+        let mut buffer = Parser::new(&input);
+        buffer.enable_memo();
+        let calls = Cell::new(0);
+        let rule = |buffer : &mut Parser<usize>| {
+            calls.set(calls.get() + 1);
+            buffer.get::<()>().copied()
+        };
+        for _ in 0..6 {
+            buffer.memo("token", rule).unwrap();
+        }
+        let fail = buffer.clone().memo("fail", |input| input.expect::<()>(&0).copied());
+        assert!(fail.is_err());
+
+        buffer.invalidate_memo(2..4, 0);
+
+        let keys = buffer.memo.as_ref().unwrap().borrow().keys().map(|x| x.1).collect::<std::collections::BTreeSet<_>>();
+        assert_eq!(keys.into_iter().collect::<Vec<_>>(), vec![0, 4, 5, 6]);
+        buffer.clear_memo();
+        assert!(buffer.memo.as_ref().unwrap().borrow().is_empty());
+        assert_eq!(calls.get(), 6);
+    }
+
+    #[test]
+    fn should_shift_entries_after_insertion_and_move_to_new_parser() {
+        let input = vec![1, 2, 3, 4, 5];
+        let mut buffer = Parser::new(&input);
+        buffer.enable_memo();
+        let calls = Cell::new(0);
+        let rule = |buffer : &mut Parser<usize>| {
+            calls.set(calls.get() + 1);
+            buffer.get::<()>().copied()
+        };
+        for _ in 0..5 {
+            buffer.memo("token", rule).unwrap();
+        }
+
+        buffer.invalidate_memo(2..2, 1);
+        let memo = buffer.take_memo().unwrap();
+        assert!(buffer.memo.is_none());
+
+        let edited = vec![1, 2, 9, 3, 4, 5];
+        let mut next = Parser::new(&edited);
+        next.adopt_memo(memo);
+        let items = (0..6).map(|_| next.memo("token", rule).unwrap()).collect::<Vec<_>>();
+
+        assert_eq!(items, edited);
+        assert_eq!(next.index(), 6);
+        assert_eq!(calls.get(), 7);
+        next.index = 3;
+        assert_eq!(next.memo("token", rule), Ok(3));
+        assert_eq!(next.index(), 4);
+        assert_eq!(calls.get(), 7);
+    }
+
+    #[test]
+    fn should_drop_entries_whose_lookahead_was_edited() {
+        let rule = |input : &mut Parser<usize>| input.list(|input| {
+            input.expect::<()>(&1)?;
+            input.expect::<()>(&2).copied()
+        });
+        let input = vec![1, 2, 1, 9];
+        let mut buffer = Parser::new(&input);
+        buffer.enable_memo();
+        assert_eq!(buffer.memo("pairs", rule), Ok(vec![2]));
+        assert_eq!(buffer.index(), 2);
+
+        buffer.invalidate_memo(3..4, 0);
+        let edited = vec![1, 2, 1, 2];
+        let mut next = Parser::new(&edited);
+        next.adopt_memo(buffer.take_memo().unwrap());
+
+        assert_eq!(next.memo("pairs", rule), Ok(vec![2, 2]));
+        assert_eq!(next.index(), 4);
+    }
+}
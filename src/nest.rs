@@ -0,0 +1,163 @@
+
+use crate::{ErrorKind, JlnError, Parser};
+
+impl<'a, T> Parser<'a, T> {
+    pub fn chain_left<S, K, E, O, P, B>(&mut self, mut operand : O, mut operator : P, mut build : B) -> Result<S, E>
+        where E : JlnError,
+              O : FnMut(&mut Parser<'a, T>) -> Result<S, E>,
+              P : FnMut(&mut Parser<'a, T>) -> Result<K, E>,
+              B : FnMut(S, K, S) -> S,
+    {
+        let mut lhs = operand(self)?;
+        while let Some((op, rhs)) = self.option(|input| Ok((operator(input)?, operand(input)?)))? {
+            lhs = build(lhs, op, rhs);
+        }
+        Ok(lhs)
+    }
+
+    pub fn chain_right<S, K, E, O, P, B>(&mut self, mut operand : O, mut operator : P, mut build : B) -> Result<S, E>
+        where E : JlnError,
+              O : FnMut(&mut Parser<'a, T>) -> Result<S, E>,
+              P : FnMut(&mut Parser<'a, T>) -> Result<K, E>,
+              B : FnMut(S, K, S) -> S,
+    {
+        let first = operand(self)?;
+        let mut rest = vec![];
+        while let Some(pair) = self.option(|input| Ok((operator(input)?, operand(input)?)))? {
+            rest.push(pair);
+        }
+        let Some((mut op, mut rhs)) = rest.pop() else { return Ok(first); };
+        while let Some((prev_op, lhs)) = rest.pop() {
+            rhs = build(lhs, op, rhs);
+            op = prev_op;
+        }
+        Ok(build(first, op, rhs))
+    }
+
+    pub fn prefixed<S, K, E, P, O, B>(&mut self, mut prefix : P, operand : O, mut apply : B) -> Result<S, E>
+        where E : JlnError,
+              P : FnMut(&mut Parser<'a, T>) -> Result<K, E>,
+              O : FnOnce(&mut Parser<'a, T>) -> Result<S, E>,
+              B : FnMut(K, S) -> S,
+    {
+        self.with_rollback(|input| {
+            let mut prefixes = vec![];
+            while let Some(op) = input.option(&mut prefix)? {
+                prefixes.push(op);
+            }
+            let mut value = operand(input)?;
+            while let Some(op) = prefixes.pop() {
+                value = apply(op, value);
+            }
+            Ok(value)
+        })
+    }
+
+    pub fn nested<S, E, O, C, I, B>(&mut self, mut open : O, mut close : C, mut item : I, mut build : B) -> Result<S, E>
+        where E : JlnError,
+              O : FnMut(&mut Parser<'a, T>) -> Result<(), E>,
+              C : FnMut(&mut Parser<'a, T>) -> Result<(), E>,
+              I : FnMut(&mut Parser<'a, T>) -> Result<S, E>,
+              B : FnMut(Vec<S>) -> S,
+    {
+        self.with_rollback(|input| {
+            open(input)?;
+            let mut stack = vec![vec![]];
+            loop {
+                if input.option(&mut open)?.is_some() {
+                    if let Some(limit) = input.max_depth && input.depth + stack.len() >= limit {
+                        return Err(input.raise(ErrorKind::NestingTooDeep { limit }, input.index));
+                    }
+                    stack.push(vec![]);
+                }
+                else if input.option(&mut close)?.is_some() {
+                    let node = build(stack.pop().unwrap_or_default());
+                    match stack.last_mut() {
+                        Some(parent) => { parent.push(node); },
+                        None => { return Ok(node); },
+                    }
+                }
+                else {
+                    let value = item(input)?;
+                    if let Some(children) = stack.last_mut() {
+                        children.push(value);
+                    }
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::TError;
+
+    fn digit(input : &mut Parser<char>) -> Result<String, TError> {
+        input.satisfy(|c : &char| c.is_ascii_digit()).map(|c| c.to_string())
+    }
+
+    fn op(input : &mut Parser<char>) -> Result<char, TError> {
+        input.satisfy(|c : &char| c.is_ascii_punctuation()).copied()
+    }
+
+    #[test]
+    fn should_chain_without_recursion() {
+        let input = "1-2-3-".chars().collect::<Vec<_>>();
+
+        let mut buffer = Parser::new(&input);
+        let left = buffer.chain_left(digit, op, |a, o, b| format!("({a}{o}{b})"));
+        assert_eq!(left.map_err(|_| ()), Ok("((1-2)-3)".into()));
+        assert_eq!(buffer.index(), 5);
+
+        let mut buffer = Parser::new(&input);
+        let right = buffer.chain_right(digit, op, |a, o, b| format!("({a}{o}{b})"));
+        assert_eq!(right.map_err(|_| ()), Ok("(1-(2-3))".into()));
+        assert_eq!(buffer.index(), 5);
+    }
+
+    #[test]
+    fn should_apply_prefixes_innermost_first() {
+        let input = "-!1".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        let value = buffer.prefixed(op, digit, |o, v| format!("{o}({v})"));
+
+        assert_eq!(value.map_err(|_| ()), Ok("-(!(1))".into()));
+    }
+
+    #[test]
+    fn should_parse_adversarial_nesting_iteratively() {
+        let depth = 200_000;
+        let text = format!("{}1{}", "[".repeat(depth), "]".repeat(depth));
+        let input = text.chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        let value = buffer.nested(
+            |input| input.expect::<TError>(&'[').map(|_| ()),
+            |input| input.expect::<TError>(&']').map(|_| ()),
+            |input| digit(input).map(|_| 0usize),
+            |children| children.into_iter().sum::<usize>() + 1,
+        );
+
+        assert_eq!(value.map_err(|_| ()), Ok(depth));
+        assert!(buffer.end());
+    }
+
+    #[test]
+    fn should_honour_max_depth_when_nesting() {
+        let input = "[[[1]]]".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        buffer.set_max_depth(Some(2));
+
+        let value = buffer.nested(
+            |input| input.expect::<TError>(&'[').map(|_| ()),
+            |input| input.expect::<TError>(&']').map(|_| ()),
+            digit,
+            |children| children.concat(),
+        );
+
+        assert!(value.unwrap_err().is_fatal());
+        assert_eq!(buffer.index(), 0);
+    }
+}
@@ -0,0 +1,272 @@
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+
+use crate::{ErrorKind, JlnError, Parser, TextToken};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Host {
+    Ip(IpAddr),
+    Domain(String),
+}
+
+impl<'a, T : TextToken> Parser<'a, T> {
+    pub fn ipv4<E : JlnError>(&mut self) -> Result<Ipv4Addr, E> {
+        self.net_rule(|input| input.ipv4_parts())
+    }
+
+    pub fn ipv6<E : JlnError>(&mut self) -> Result<Ipv6Addr, E> {
+        self.net_rule(|input| input.ipv6_parts())
+    }
+
+    pub fn ipv6_scoped<E : JlnError>(&mut self) -> Result<(Ipv6Addr, Option<String>), E> {
+        self.net_rule(|input| {
+            let addr = input.ipv6_parts()?;
+            Ok((addr, input.ipv6_zone()?))
+        })
+    }
+
+    pub fn ip_addr<E : JlnError>(&mut self) -> Result<IpAddr, E> {
+        self.net_rule(|input| {
+            let first = match input.with_rollback(|input| input.ipv4_parts::<E>()) {
+                Ok(addr) => { return Ok(IpAddr::V4(addr)); },
+                Err(e) if input.fatal(&e) => { return Err(e); },
+                Err(e) => e,
+            };
+            match input.with_rollback(|input| input.ipv6_parts::<E>()) {
+                Ok(addr) => Ok(IpAddr::V6(addr)),
+                Err(e) if input.fatal(&e) => Err(e),
+                Err(e) => Err(E::aggregate(vec![first, e])),
+            }
+        })
+    }
+
+    pub fn socket_addr<E : JlnError>(&mut self) -> Result<SocketAddr, E> {
+        self.net_rule(|input| {
+            if input.peek::<E>()?.to_char() != '[' {
+                let addr = input.ipv4_parts()?;
+                return Ok(SocketAddr::V4(SocketAddrV4::new(addr, input.port()?)));
+            }
+            let (addr, scope) = input.bracketed_ipv6()?;
+            let port = input.port()?;
+            Ok(SocketAddr::V6(SocketAddrV6::new(addr, port, 0, scope)))
+        })
+    }
+
+    pub fn host_port<E : JlnError>(&mut self) -> Result<(Host, u16), E> {
+        self.net_rule(|input| {
+            if input.peek::<E>()?.to_char() == '[' {
+                let (addr, _) = input.bracketed_ipv6()?;
+                return Ok((Host::Ip(IpAddr::V6(addr)), input.port()?));
+            }
+            let ip = input.option(|input| {
+                let addr = input.ipv4_parts()?;
+                match input.peek::<E>()?.to_char() {
+                    ':' => Ok(addr),
+                    _ => Err(E::from_kind(ErrorKind::Unexpected, input.index)),
+                }
+            })?;
+            let host = match ip {
+                Some(addr) => Host::Ip(IpAddr::V4(addr)),
+                None => Host::Domain(input.domain()?),
+            };
+            Ok((host, input.port()?))
+        })
+    }
+
+    fn net_rule<S, E : JlnError, F : FnOnce(&mut Parser<'a, T>) -> Result<S, E>>(&mut self, f : F) -> Result<S, E> {
+        self.with_rollback(|input| {
+            input.consume_trivia();
+            input.no_trivia(f)
+        })
+    }
+
+    fn ipv4_parts<E : JlnError>(&mut self) -> Result<Ipv4Addr, E> {
+        let mut octets = [0u8; 4];
+        for (i, octet) in octets.iter_mut().enumerate() {
+            if i > 0 {
+                self.expect_no_case::<E>('.')?;
+            }
+            let start = self.index;
+            let digits = self.net_digits(10, 4)?;
+            if digits.is_empty() || (digits.len() > 1 && digits.starts_with('0')) {
+                return Err(E::from_kind(ErrorKind::InvalidNumber, start));
+            }
+            *octet = digits.parse().map_err(|_| E::from_kind(ErrorKind::Overflow, start))?;
+        }
+        Ok(Ipv4Addr::from(octets))
+    }
+
+    fn ipv6_parts<E : JlnError>(&mut self) -> Result<Ipv6Addr, E> {
+        let start = self.index;
+        let mut groups = vec![];
+        let mut gap = None;
+        if self.double_colon::<E>()? {
+            gap = Some(0);
+        }
+        let mut required = gap.is_none();
+        loop {
+            let max = if gap.is_some() { 7 } else { 8 };
+            if groups.len() + 2 <= max && self.embedded_ipv4() {
+                let [a, b, c, d] = self.ipv4_parts()?.octets();
+                groups.push(u16::from_be_bytes([a, b]));
+                groups.push(u16::from_be_bytes([c, d]));
+                break;
+            }
+            let at = self.index;
+            let digits = self.net_digits(16, 5)?;
+            if digits.is_empty() {
+                if required {
+                    return Err(E::from_kind(ErrorKind::Unexpected, at));
+                }
+                break;
+            }
+            groups.push(u16::from_str_radix(&digits, 16).map_err(|_| E::from_kind(ErrorKind::Overflow, at))?);
+            if groups.len() == max {
+                break;
+            }
+            if gap.is_none() && self.double_colon::<E>()? {
+                gap = Some(groups.len());
+                required = false;
+            }
+            else if self.peek::<E>().is_ok_and(|t| t.to_char() == ':') {
+                self.get::<E>()?;
+                required = true;
+            }
+            else {
+                break;
+            }
+        }
+        let mut words = [0u16; 8];
+        match gap {
+            Some(at) => {
+                let tail = groups.len() - at;
+                words[..at].copy_from_slice(&groups[..at]);
+                words[8 - tail..].copy_from_slice(&groups[at..]);
+            },
+            None if groups.len() == 8 => { words.copy_from_slice(&groups); },
+            None => { return Err(E::custom(Some("ipv6"), start)); },
+        }
+        Ok(Ipv6Addr::from(words))
+    }
+
+    fn ipv6_zone<E : JlnError>(&mut self) -> Result<Option<String>, E> {
+        if !self.peek::<E>().is_ok_and(|t| t.to_char() == '%') {
+            return Ok(None);
+        }
+        self.get::<E>()?;
+        let start = self.index;
+        let zone = self.take_while::<E, _>(|t| t.to_char().is_ascii_alphanumeric() || matches!(t.to_char(), '-' | '_' | '.'))?.iter().map(|t| t.to_char()).collect::<String>();
+        if zone.is_empty() {
+            return Err(E::custom(Some("zone"), start));
+        }
+        Ok(Some(zone))
+    }
+
+    fn bracketed_ipv6<E : JlnError>(&mut self) -> Result<(Ipv6Addr, u32), E> {
+        self.expect_no_case::<E>('[')?;
+        let addr = self.ipv6_parts()?;
+        let start = self.index + 1;
+        let scope = match self.ipv6_zone()? {
+            Some(zone) => zone.parse().map_err(|_| E::custom(Some("zone"), start))?,
+            None => 0,
+        };
+        self.expect_no_case::<E>(']')?;
+        Ok((addr, scope))
+    }
+
+    fn domain<E : JlnError>(&mut self) -> Result<String, E> {
+        let mut domain = String::new();
+        loop {
+            let start = self.index;
+            let label = self.take_while::<E, _>(|t| t.to_char().is_ascii_alphanumeric() || t.to_char() == '-')?.iter().map(|t| t.to_char()).collect::<String>();
+            if label.is_empty() || label.len() > 63 || label.starts_with('-') || label.ends_with('-') {
+                return Err(E::custom(Some("domain"), start));
+            }
+            domain.push_str(&label);
+            if !self.peek::<E>().is_ok_and(|t| t.to_char() == '.') {
+                return Ok(domain);
+            }
+            self.get::<E>()?;
+            domain.push('.');
+        }
+    }
+
+    fn port<E : JlnError>(&mut self) -> Result<u16, E> {
+        self.expect_no_case::<E>(':')?;
+        let start = self.index;
+        let digits = self.net_digits(10, 6)?;
+        if digits.is_empty() {
+            return Err(E::from_kind(ErrorKind::InvalidNumber, start));
+        }
+        digits.parse().map_err(|_| E::from_kind(ErrorKind::Overflow, start))
+    }
+
+    fn net_digits<E : JlnError>(&mut self, radix : u32, limit : usize) -> Result<String, E> {
+        let mut digits = String::new();
+        while let Ok(t) = self.peek::<E>() {
+            if !t.to_char().is_digit(radix) || digits.len() == limit {
+                break;
+            }
+            digits.push(t.to_char());
+            self.get::<E>()?;
+        }
+        Ok(digits)
+    }
+
+    fn double_colon<E : JlnError>(&mut self) -> Result<bool, E> {
+        if self.index + 1 < self.bound() && self.input[self.index].to_char() == ':' && self.input[self.index + 1].to_char() == ':' {
+            self.get::<E>()?;
+            self.get::<E>()?;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    fn embedded_ipv4(&self) -> bool {
+        let rest = &self.input[self.index..self.bound()];
+        let digits = rest.iter().take_while(|t| t.to_char().is_ascii_digit()).count();
+        (1..=3).contains(&digits) && rest.get(digits).is_some_and(|t| t.to_char() == '.')
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Diagnostic;
+
+    fn parse<S>(text : &str, f : fn(&mut Parser<u8>) -> Result<S, Diagnostic>) -> Result<S, String> {
+        let input = text.as_bytes().to_vec();
+        f(&mut Parser::new(&input)).map_err(|e| e.snapshot())
+    }
+
+    #[test]
+    fn should_parse_addresses_like_std() {
+        for text in ["::", "::1", "1::", "fe80::1:2", "1:2:3:4:5:6:7:8", "::ffff:192.0.2.1", "64:ff9b::10.0.0.1", "1:2:3:4:5:6:1.2.3.4"] {
+            assert_eq!(parse(text, |input| input.ipv6()), Ok(text.parse::<Ipv6Addr>().unwrap()), "{}", text);
+        }
+        assert_eq!(parse("10.0.0.255", |input| input.ip_addr()), Ok("10.0.0.255".parse().unwrap()));
+        assert_eq!(parse("2001:db8::", |input| input.ip_addr()), Ok("2001:db8::".parse().unwrap()));
+        assert_eq!(parse("fe80::1%eth0", |input| input.ipv6_scoped()), Ok(("fe80::1".parse().unwrap(), Some("eth0".into()))));
+    }
+
+    #[test]
+    fn should_report_invalid_address_positions() {
+        assert_eq!(parse("10.0.256.1", |input| input.ipv4()), Err("at 5: Overflow\n".into()));
+        assert_eq!(parse("10.01.0.1", |input| input.ipv4()), Err("at 3: InvalidNumber\n".into()));
+        assert_eq!(parse("1:2:3", |input| input.ipv6()), Err("at 0: Custom { label: Some(\"ipv6\") }\n".into()));
+        assert_eq!(parse("1:2:12345::", |input| input.ipv6()), Err("at 4: Overflow\n".into()));
+        assert_eq!(parse("1:2:x::", |input| input.ipv6()), Err("at 4: Unexpected\n".into()));
+    }
+
+    #[test]
+    fn should_parse_socket_addresses_and_hosts() {
+        assert_eq!(parse("127.0.0.1:8080", |input| input.socket_addr()), Ok("127.0.0.1:8080".parse().unwrap()));
+        assert_eq!(parse("[::1%3]:443", |input| input.socket_addr()), Ok("[::1%3]:443".parse().unwrap()));
+        assert_eq!(parse("[::1%lo]:443", |input| input.socket_addr()), Err("at 5: Custom { label: Some(\"zone\") }\n".into()));
+        assert_eq!(parse("1.2.3.4:70000", |input| input.socket_addr()), Err("at 8: Overflow\n".into()));
+        assert_eq!(parse("1.2.3.4.nip.io:80", |input| input.host_port()), Ok((Host::Domain("1.2.3.4.nip.io".into()), 80)));
+        assert_eq!(parse("10.1.1.1:22", |input| input.host_port()), Ok((Host::Ip("10.1.1.1".parse().unwrap()), 22)));
+        assert_eq!(parse("[2001:db8::1]:53", |input| input.host_port()), Ok((Host::Ip("2001:db8::1".parse().unwrap()), 53)));
+        assert_eq!(parse("-bad.com:1", |input| input.host_port()), Err("at 0: Custom { label: Some(\"domain\") }\n".into()));
+    }
+}
@@ -0,0 +1,223 @@
+
+use std::str::FromStr;
+
+use crate::{ErrorKind, JlnError, Parser, TextToken};
+
+pub trait Integer : Sized + Copy {
+    const SIGNED : bool;
+    const ZERO : Self;
+    fn push_digit(self, radix : u32, digit : u32, negative : bool) -> Option<Self>;
+}
+
+macro_rules! integer {
+    ($signed:expr => $($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const SIGNED : bool = $signed;
+                const ZERO : Self = 0;
+                fn push_digit(self, radix : u32, digit : u32, negative : bool) -> Option<Self> {
+                    let shifted = self.checked_mul(radix as $t)?;
+                    if negative { shifted.checked_sub(digit as $t) } else { shifted.checked_add(digit as $t) }
+                }
+            }
+        )*
+    };
+}
+
+integer!(true => i8, i16, i32, i64, i128, isize);
+integer!(false => u8, u16, u32, u64, u128, usize);
+
+impl<'a, T : TextToken> Parser<'a, T> {
+    pub fn integer<I : Integer, E : JlnError>(&mut self) -> Result<I, E> {
+        self.with_rollback(|input| {
+            input.consume_trivia();
+            input.no_trivia(|input| {
+                let negative = I::SIGNED && input.option(|input| input.expect_no_case::<E>('-'))?.is_some();
+                if !negative {
+                    input.option(|input| input.expect_no_case::<E>('+'))?;
+                }
+
+                let radix = input.option(|input| {
+                    input.expect_no_case::<E>('0')?;
+                    match input.get::<E>()?.to_char() {
+                        'x' | 'X' => Ok(16),
+                        'o' | 'O' => Ok(8),
+                        'b' | 'B' => Ok(2),
+                        _ => Err(E::from_kind(ErrorKind::Unexpected, input.index - 1)),
+                    }
+                })?.unwrap_or(10);
+
+                let start = input.index;
+                let mut value = I::ZERO;
+                let mut digits = 0;
+                while let Ok(t) = input.peek::<E>() {
+                    let c = t.to_char();
+                    if c == '_' && digits > 0 {
+                        input.get::<E>()?;
+                        continue;
+                    }
+                    let Some(digit) = c.to_digit(radix) else { break; };
+                    value = value.push_digit(radix, digit, negative).ok_or_else(|| E::from_kind(ErrorKind::Overflow, start))?;
+                    digits += 1;
+                    input.get::<E>()?;
+                }
+
+                if digits == 0 {
+                    Err(E::from_kind(ErrorKind::InvalidNumber, start))
+                }
+                else {
+                    Ok(value)
+                }
+            })
+        })
+    }
+
+    pub fn float<F : FromStr, E : JlnError>(&mut self) -> Result<F, E> {
+        self.with_rollback(|input| {
+            input.consume_trivia();
+            let start = input.index;
+            input.no_trivia(|input| {
+                let mut text = String::new();
+                if let Some(sign) = input.option(|input| input.satisfy::<E, _>(|t| matches!(t.to_char(), '-' | '+')).copied())? {
+                    text.push(sign.to_char());
+                }
+
+                if input.digits(&mut text)? == 0 {
+                    return Err(E::from_kind(ErrorKind::InvalidNumber, start));
+                }
+
+                let fraction = input.option(|input| {
+                    input.expect_no_case::<E>('.')?;
+                    let mut fraction = String::from(".");
+                    match input.digits(&mut fraction)? {
+                        0 => Err(E::from_kind(ErrorKind::InvalidNumber, input.index)),
+                        _ => Ok(fraction),
+                    }
+                })?;
+                text.extend(fraction);
+
+                let exponent = input.option(|input| {
+                    input.expect_no_case::<E>('e')?;
+                    let mut exponent = String::from("e");
+                    if let Some(sign) = input.option(|input| input.satisfy::<E, _>(|t| matches!(t.to_char(), '-' | '+')).copied())? {
+                        exponent.push(sign.to_char());
+                    }
+                    match input.digits(&mut exponent)? {
+                        0 => Err(E::from_kind(ErrorKind::InvalidNumber, input.index)),
+                        _ => Ok(exponent),
+                    }
+                })?;
+                text.extend(exponent);
+
+                text.parse().map_err(|_| E::from_kind(ErrorKind::InvalidNumber, start))
+            })
+        })
+    }
+
+    fn digits<E : JlnError>(&mut self, text : &mut String) -> Result<usize, E> {
+        let mut digits = 0;
+        while let Ok(t) = self.peek::<E>() {
+            match t.to_char() {
+                c if c.is_ascii_digit() => {
+                    text.push(c);
+                    digits += 1;
+                },
+                '_' if digits > 0 => { },
+                _ => { break; },
+            }
+            self.get::<E>()?;
+        }
+        Ok(digits)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn chars(text : &str) -> Vec<char> {
+        text.chars().collect()
+    }
+
+    #[test]
+    fn should_parse_integers_with_radix_and_separators() {
+        let input = chars("1_000 -42 0xff 0b1010 0o17 +7");
+        let mut buffer = Parser::new(&input);
+        buffer.set_trivia(Some(|c| *c == ' '));
+
+        assert_eq!(buffer.integer::<i32, ()>(), Ok(1000));
+        assert_eq!(buffer.integer::<i32, ()>(), Ok(-42));
+        assert_eq!(buffer.integer::<u8, ()>(), Ok(255));
+        assert_eq!(buffer.integer::<u8, ()>(), Ok(10));
+        assert_eq!(buffer.integer::<u64, ()>(), Ok(15));
+        assert_eq!(buffer.integer::<i8, ()>(), Ok(7));
+        assert!(buffer.end());
+    }
+
+    #[test]
+    fn should_parse_integer_bounds() {
+        let input = b"-128".to_vec();
+        let mut buffer = Parser::new(&input);
+
+        assert_eq!(buffer.integer::<i8, ()>(), Ok(-128));
+    }
+
+    #[test]
+    fn should_report_overflow() {
+        #[derive(Debug, PartialEq)]
+        struct KindError(Option<ErrorKind>);
+
+        impl JlnError for KindError {
+            fn is_fatal(&self) -> bool { false }
+            fn eof() -> Self { KindError(None) }
+            fn aggregate(_errors : Vec<Self>) -> Self { KindError(None) }
+            fn from_kind(kind : ErrorKind, _index : usize) -> Self { KindError(Some(kind)) }
+        }
+
+        let input = chars("256");
+        let mut buffer = Parser::new(&input);
+
+        assert_eq!(buffer.integer::<u8, KindError>(), Err(KindError(Some(ErrorKind::Overflow))));
+        assert_eq!(buffer.index(), 0);
+        assert_eq!(buffer.integer::<u16, KindError>(), Ok(256));
+    }
+
+    #[test]
+    fn should_reject_sign_for_unsigned_and_missing_digits() {
+        let input = chars("-1");
+        let mut buffer = Parser::new(&input);
+        assert!(buffer.integer::<u32, ()>().is_err());
+
+        let input = chars("0x");
+        let mut buffer = Parser::new(&input);
+        assert!(buffer.integer::<u32, ()>().is_err());
+        assert_eq!(buffer.index(), 0);
+    }
+
+    #[test]
+    fn should_parse_floats() {
+        let input = chars("3.25 -1_0.5e-1 2E3 7");
+        let mut buffer = Parser::new(&input);
+        buffer.set_trivia(Some(|c| *c == ' '));
+
+        assert_eq!(buffer.float::<f64, ()>(), Ok(3.25));
+        assert_eq!(buffer.float::<f64, ()>(), Ok(-1.05));
+        assert_eq!(buffer.float::<f32, ()>(), Ok(2000.0));
+        assert_eq!(buffer.float::<f64, ()>(), Ok(7.0));
+    }
+
+    #[test]
+    fn should_not_consume_dangling_fraction_or_exponent() {
+        let input = chars("1.x");
+        let mut buffer = Parser::new(&input);
+
+        assert_eq!(buffer.float::<f64, ()>(), Ok(1.0));
+        assert_eq!(buffer.index(), 1);
+
+        let input = chars("2e");
+        let mut buffer = Parser::new(&input);
+
+        assert_eq!(buffer.float::<f64, ()>(), Ok(2.0));
+        assert_eq!(buffer.index(), 1);
+    }
+}
@@ -0,0 +1,223 @@
+
+use rayon::prelude::*;
+
+use crate::{Alternative, ErrorKind, Furthest, Input, JlnError, Parser};
+
+impl<'a, T : Sync> Parser<'a, T> {
+    #[track_caller]
+    pub fn or_parallel<S : Send, E : JlnError + Send, const N : usize>(&mut self, targets : [Alternative<T, S, E>; N]) -> Result<S, E> {
+        if self.observed() {
+            return self.or(targets);
+        }
+
+        let input : &[T] = &self.input;
+        let (index, depth, max_depth, trivia, skip_trivia, limit) = (self.index, self.depth, self.max_depth, self.trivia, self.skip_trivia, self.limit);
+        let fuel = self.fuel();
+        let indents = &self.indents;
+        let results = targets.par_iter().map(|target| {
+            let mut worker = Parser::from_input(Input::Ref(input));
+            worker.index = index;
+            worker.reach = index;
+            worker.depth = depth;
+            worker.max_depth = max_depth;
+            worker.indents = indents.clone();
+            worker.trivia = trivia;
+            worker.skip_trivia = skip_trivia;
+            worker.limit = limit;
+            worker.set_fuel(fuel);
+            let result = worker.attempt(*target).map(|value| (value, worker.index));
+            let burned = fuel.zip(worker.fuel()).map_or(0, |(start, left)| start - left);
+            (result, worker.reach, burned, worker.halted())
+        }).collect::<Vec<_>>();
+
+        let mut spent = 0;
+        let mut furthest = Furthest::<E, N>::new();
+        for (result, reach, burned, halted) in results {
+            spent += burned;
+            if fuel.is_some_and(|x| spent > x) {
+                self.set_fuel(Some(0));
+                return Err(self.raise(ErrorKind::BudgetExhausted, self.index));
+            }
+            if let Some(fuel) = &self.fuel {
+                fuel.set(fuel.get() - burned);
+            }
+            if halted {
+                self.halted.set(true);
+            }
+            self.reach = self.reach.max(reach);
+            match result {
+                Ok((value, end)) => {
+                    while self.index < end {
+                        self.advance();
+                    }
+                    return Ok(value);
+                },
+                Err(e) if self.fatal(&e) => { return Err(e); },
+                Err(e) => { furthest.push(reach, e); },
+            }
+        }
+        Err(furthest.into_error())
+    }
+
+    fn observed(&self) -> bool {
+        self.pushback.is_some() || self.streaming.is_some() || self.events.is_some() || self.trace.is_some() || self.hooks.is_some()
+            || self.stats.is_some() || self.coverage.is_some() || self.profile.is_some() || self.warnings.is_some() || self.errors.is_some()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::Diagnostic;
+    use crate::test::TError;
+
+    fn number(input : &mut Parser<char>) -> Result<String, TError> {
+        let digits = input.take_while::<TError, _>(|c| c.is_ascii_digit())?;
+        if digits.is_empty() {
+            return Err(TError(false));
+        }
+        Ok(digits.iter().collect())
+    }
+
+    fn word(input : &mut Parser<char>) -> Result<String, TError> {
+        let letters = input.take_while::<TError, _>(|c| c.is_alphabetic())?;
+        if letters.is_empty() {
+            return Err(TError(false));
+        }
+        Ok(letters.iter().collect())
+    }
+
+    fn fatal(input : &mut Parser<char>) -> Result<String, TError> {
+        match input.get::<TError>()? {
+            '!' => Err(TError(true)),
+            _ => Err(TError(false)),
+        }
+    }
+
+    #[test]
+    fn should_commit_first_success_in_order() {
+        let input = "abc12".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        assert_eq!(buffer.or_parallel([number, fatal, word]).map_err(|_| ()), Ok("abc".into()));
+        assert_eq!(buffer.or_parallel([word, number]).map_err(|_| ()), Ok("12".into()));
+        assert_eq!(buffer.index(), 5);
+        assert!(buffer.or_parallel([word, number]).is_err());
+    }
+
+    #[test]
+    fn should_report_furthest_failing_alternatives() {
+        fn ab(input : &mut Parser<char>) -> Result<char, Diagnostic> {
+            input.expect::<Diagnostic>(&'a')?;
+            input.expect::<Diagnostic>(&'b').copied()
+        }
+
+        fn x(input : &mut Parser<char>) -> Result<char, Diagnostic> {
+            input.expect::<Diagnostic>(&'x').copied()
+        }
+
+        let input = "a!".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        assert_eq!(buffer.or_parallel([x, ab]).unwrap_err().snapshot(), buffer.or([ab]).unwrap_err().snapshot());
+        assert_eq!(buffer.index(), 0);
+        assert_eq!(buffer.reach, 1);
+    }
+
+    #[test]
+    fn should_stop_at_fatal_alternative() {
+        let input = "!".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        assert!(buffer.or_parallel([word, fatal, |input : &mut Parser<char>| Ok(input.get::<TError>()?.to_string())]).unwrap_err().is_fatal());
+        assert_eq!(buffer.index(), 0);
+    }
+
+    fn backtrack(input : &mut Parser<char>) -> Result<String, TError> {
+        assert!(input.state::<u32>().is_none());
+        input.with_rollback(|input| input.expect::<TError>(&'?').copied())?;
+        Ok(String::new())
+    }
+
+    #[test]
+    fn should_charge_fuel_of_branches_up_to_winner_and_keep_state_out_of_workers() {
+        let input = "abc".chars().collect::<Vec<_>>();
+        let mut alone = Parser::new(&input);
+        alone.set_fuel(Some(10));
+        word(&mut alone).unwrap();
+        let mut buffer = Parser::new(&input);
+        buffer.set_fuel(Some(10));
+        buffer.set_state(7u32);
+
+        assert_eq!(buffer.or_parallel([backtrack, backtrack, word, word]).map_err(|_| ()), Ok("abc".into()));
+        assert_eq!(buffer.fuel(), alone.fuel().map(|x| x - 4));
+        assert_eq!(buffer.index(), 3);
+        assert_eq!(buffer.state::<u32>(), Some(&7));
+
+        let mut starved = Parser::new(&input);
+        starved.set_fuel(Some(0));
+        assert!(starved.or_parallel([backtrack, word]).unwrap_err().is_fatal());
+    }
+
+    #[test]
+    fn should_spend_fuel_like_sequential_alternation() {
+        let input = "abc".chars().collect::<Vec<_>>();
+        for fuel in 0..8 {
+            let mut parallel = Parser::new(&input);
+            parallel.set_fuel(Some(fuel));
+            let mut sequential = Parser::new(&input);
+            sequential.set_fuel(Some(fuel));
+
+            let a = parallel.or_parallel([backtrack, number, word]).map_err(|e| e.is_fatal());
+            let b = sequential.or([backtrack, number, word]).map_err(|e| e.is_fatal());
+
+            assert_eq!(a, b);
+            assert_eq!(parallel.fuel(), sequential.fuel());
+            assert_eq!(parallel.halted(), sequential.halted());
+            assert_eq!(parallel.index(), sequential.index());
+        }
+    }
+
+    #[test]
+    fn should_halt_parent_when_worker_halts() {
+        fn deep(input : &mut Parser<char>) -> Result<String, ()> {
+            let mut outer = input.enter_rule::<()>()?;
+            let _inner = outer.enter_rule::<()>()?;
+            Ok(String::new())
+        }
+
+        fn any(input : &mut Parser<char>) -> Result<String, ()> {
+            Ok(input.get::<()>()?.to_string())
+        }
+
+        let input = "abc".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::builder(&input[..]).max_depth(1).build();
+
+        assert_eq!(buffer.or_parallel([deep, any]), Err(()));
+        assert!(buffer.halted());
+        assert_eq!(buffer.index(), 0);
+    }
+
+    #[test]
+    fn should_see_pushback_and_events_of_caller() {
+        fn gt(input : &mut Parser<char>) -> Result<String, TError> {
+            Ok(input.expect::<TError>(&'>')?.to_string())
+        }
+
+        let input = "abc".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        buffer.unread('>');
+
+        assert_eq!(buffer.or_parallel([word, gt]).map_err(|_| ()), Ok(">".into()));
+        assert_eq!(buffer.pushed_back(), 0);
+
+        let events = Rc::new(RefCell::new(vec![]));
+        let sink = Rc::clone(&events);
+        buffer.set_event_sink(move |event| sink.borrow_mut().push(event));
+        assert_eq!(buffer.or_parallel([number, word]).map_err(|_| ()), Ok("abc".into()));
+        assert_eq!(events.borrow().len(), 3);
+    }
+}
@@ -0,0 +1,66 @@
+
+use crate::{JlnError, Parser};
+
+pub trait Parse<T, E : JlnError> : Sized {
+    fn parse(input : &mut Parser<'_, T>) -> Result<Self, E>;
+}
+
+impl<T, E : JlnError, U : Parse<T, E>> Parse<T, E> for Box<U> {
+    fn parse(input : &mut Parser<'_, T>) -> Result<Self, E> {
+        U::parse(input).map(Box::new)
+    }
+}
+
+impl<T, E : JlnError, U : Parse<T, E>> Parse<T, E> for Option<U> {
+    fn parse(input : &mut Parser<'_, T>) -> Result<Self, E> {
+        input.option(U::parse)
+    }
+}
+
+impl<T, E : JlnError, U : Parse<T, E>> Parse<T, E> for Vec<U> {
+    fn parse(input : &mut Parser<'_, T>) -> Result<Self, E> {
+        input.list(U::parse)
+    }
+}
+
+impl<'a, T> Parser<'a, T> {
+    pub fn parse<U : Parse<T, E>, E : JlnError>(&mut self) -> Result<U, E> {
+        U::parse(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Digit(u32);
+
+    impl Parse<char, ()> for Digit {
+        fn parse(input : &mut Parser<'_, char>) -> Result<Self, ()> {
+            input.satisfy(|c : &char| c.is_ascii_digit()).map(|c| Digit(c.to_digit(10).unwrap()))
+        }
+    }
+
+    #[test]
+    fn should_parse_through_trait() {
+        let input = "12x".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        assert_eq!(buffer.parse::<Digit, ()>(), Ok(Digit(1)));
+        assert_eq!(Digit::parse(&mut buffer), Ok(Digit(2)));
+        assert_eq!(buffer.parse::<Option<Digit>, ()>(), Ok(None));
+    }
+
+    #[test]
+    fn should_parse_containers() {
+        let input = "345".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        let first : Box<Digit> = buffer.parse::<_, ()>().unwrap();
+        let rest : Vec<Digit> = buffer.parse::<_, ()>().unwrap();
+
+        assert_eq!(*first, Digit(3));
+        assert_eq!(rest, vec![Digit(4), Digit(5)]);
+    }
+}
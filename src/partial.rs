@@ -0,0 +1,128 @@
+
+use crate::{JlnError, Parser};
+
+pub struct Progress<S> {
+    best : Option<(usize, S)>,
+}
+
+impl<S> Progress<S> {
+    pub fn record(&mut self, index : usize, value : S) {
+        if self.best.as_ref().is_none_or(|(best, _)| index >= *best) {
+            self.best = Some((index, value));
+        }
+    }
+
+    pub fn best(&self) -> Option<&S> {
+        self.best.as_ref().map(|(_, value)| value)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Partial<S, E> {
+    pub value : Option<S>,
+    pub index : usize,
+    pub error : Option<E>,
+}
+
+impl<S, E> Partial<S, E> {
+    pub fn is_complete(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+impl<'a, T> Parser<'a, T> {
+    pub fn partial<S, E : JlnError, F : FnOnce(&mut Parser<'a, T>, &mut Progress<S>) -> Result<S, E>>(&mut self, f : F) -> Partial<S, E> {
+        let start = self.index;
+        let mut progress = Progress { best: None };
+        match self.attempt(|input| f(input, &mut progress)) {
+            Ok(value) => Partial { value: Some(value), index: self.index, error: None },
+            Err(e) => match progress.best {
+                Some((index, value)) => Partial { value: Some(value), index, error: Some(e) },
+                None => Partial { value: None, index: start, error: Some(e) },
+            },
+        }
+    }
+
+    pub fn parse_partial<S, E : JlnError, F : FnOnce(&mut Parser<'a, T>) -> Result<S, E>>(&self, f : F) -> Result<(S, Parser<'a, T>), E> {
+        let mut rest = self.clone();
+        let value = rest.with_rollback(f)?;
+        Ok((value, rest))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn command(input : &mut Parser<char>, progress : &mut Progress<Vec<String>>) -> Result<Vec<String>, ()> {
+        let mut words = vec![];
+        loop {
+            let word = input.list(|input| match *input.get()? {
+                c if c.is_alphabetic() => Ok(c),
+                _ => Err(()),
+            })?;
+            if word.is_empty() {
+                return Err(());
+            }
+            words.push(word.into_iter().collect());
+            progress.record(input.index(), words.clone());
+            if input.end() {
+                return Ok(words);
+            }
+            input.expect(&' ')?;
+        }
+    }
+
+    #[test]
+    fn should_return_complete_value() {
+        let input = "git commit".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        let result = buffer.partial(command);
+
+        assert!(result.is_complete());
+        assert_eq!(result.value, Some(vec!["git".to_string(), "commit".to_string()]));
+        assert_eq!(result.index, 10);
+        assert_eq!(buffer.index(), 10);
+    }
+
+    #[test]
+    fn should_return_longest_successful_prefix() {
+        let input = "git commit -".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        let result = buffer.partial(command);
+
+        assert!(!result.is_complete());
+        assert_eq!(result.value, Some(vec!["git".to_string(), "commit".to_string()]));
+        assert_eq!(result.index, 10);
+        assert_eq!(buffer.index(), 0);
+    }
+
+    #[test]
+    fn should_return_no_value_without_progress() {
+        let input = "-".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        let result = buffer.partial(command);
+
+        assert_eq!(result, Partial { value: None, index: 0, error: Some(()) });
+    }
+
+    #[test]
+    fn should_return_value_and_remaining_parser() {
+        let input = "ab;12".chars().collect::<Vec<_>>();
+        let buffer = Parser::new(&input);
+
+        let (header, mut rest) = buffer.parse_partial(|input| {
+            let header = input.tag_str::<()>("ab")?.len();
+            input.expect::<()>(&';')?;
+            Ok::<_, ()>(header)
+        }).unwrap();
+
+        assert_eq!(header, 2);
+        assert_eq!(buffer.index(), 0);
+        assert_eq!(rest.integer::<u32, ()>(), Ok(12));
+        assert!(buffer.parse_partial(|input| input.expect::<()>(&'x').copied()).is_err());
+    }
+}
@@ -0,0 +1,190 @@
+
+use std::cell::RefCell;
+
+use regex::Regex;
+use regex::bytes::Regex as BytesRegex;
+use regex_automata::Anchored;
+use regex_automata::hybrid::dfa::{Cache, DFA};
+use regex_automata::util::start;
+
+use crate::{ErrorKind, JlnError, Parser, Span};
+
+pub struct Pattern {
+    regex : Regex,
+    dfa : Option<(DFA, RefCell<Cache>)>,
+}
+
+pub struct BytesPattern {
+    regex : BytesRegex,
+}
+
+fn anchored(pattern : &str) -> String {
+    format!(r"\A(?:{})", pattern)
+}
+
+impl Pattern {
+    pub fn new(pattern : &str) -> Result<Pattern, regex::Error> {
+        let regex = Regex::new(&anchored(pattern))?;
+        let dfa = DFA::new(pattern).ok().map(|dfa| {
+            let cache = dfa.create_cache();
+            (dfa, RefCell::new(cache))
+        });
+        Ok(Pattern { regex, dfa })
+    }
+
+    fn match_len(&self, chars : &[char]) -> Option<Option<usize>> {
+        let (dfa, cache) = self.dfa.as_ref()?;
+        let cache = &mut *cache.borrow_mut();
+        let mut sid = dfa.start_state(cache, &start::Config::new().anchored(Anchored::Yes)).ok()?;
+        let mut last = None;
+        let mut buffer = [0; 4];
+        for (n, c) in chars.iter().enumerate() {
+            for (i, b) in c.encode_utf8(&mut buffer).bytes().enumerate() {
+                sid = dfa.next_state(cache, sid, b).ok()?;
+                if sid.is_match() && i == 0 {
+                    last = Some(n);
+                }
+                if sid.is_dead() {
+                    return Some(last);
+                }
+                if sid.is_quit() {
+                    return None;
+                }
+            }
+        }
+        sid = dfa.next_eoi_state(cache, sid).ok()?;
+        if sid.is_quit() {
+            return None;
+        }
+        if sid.is_match() {
+            last = Some(chars.len());
+        }
+        Some(last)
+    }
+
+    fn captures(&self, chars : &[char], start : usize) -> Option<Vec<Option<Span>>> {
+        let mut text = String::new();
+        let mut offsets = vec![];
+        for c in chars {
+            offsets.push(text.len());
+            text.push(*c);
+        }
+        offsets.push(text.len());
+        let captures = self.regex.captures(&text)?;
+        let offset = |byte : usize| start + offsets.partition_point(|x| *x < byte);
+        Some(captures.iter().map(|x| x.map(|x| Span::new(offset(x.start()), offset(x.end())))).collect())
+    }
+}
+
+impl BytesPattern {
+    pub fn new(pattern : &str) -> Result<BytesPattern, regex::Error> {
+        Ok(BytesPattern { regex: BytesRegex::new(&anchored(pattern))? })
+    }
+}
+
+impl<'a> Parser<'a, u8> {
+    pub fn regex<E : JlnError>(&mut self, pattern : &BytesPattern) -> Result<Vec<Option<Span>>, E> {
+        let start = self.next_significant();
+        let captures = match pattern.regex.captures(&self.input[start..self.bound()]) {
+            Some(captures) => captures,
+            None => { return Err(E::from_kind(ErrorKind::Unexpected, start)); },
+        };
+        let spans = captures.iter().map(|x| x.map(|x| Span::new(start + x.start(), start + x.end()))).collect::<Vec<_>>();
+        self.consume_regex(spans)
+    }
+}
+
+impl<'a> Parser<'a, char> {
+    pub fn regex<E : JlnError>(&mut self, pattern : &Pattern) -> Result<Vec<Option<Span>>, E> {
+        let start = self.next_significant();
+        let rest = &self.input[start..self.bound()];
+        let spans = match pattern.match_len(rest) {
+            Some(None) => None,
+            Some(Some(len)) => match pattern.captures(&rest[..(len + 1).min(rest.len())], start) {
+                Some(spans) if spans[0] == Some(Span::new(start, start + len)) => Some(spans),
+                _ => pattern.captures(rest, start),
+            },
+            None => pattern.captures(rest, start),
+        };
+        match spans {
+            Some(spans) => self.consume_regex(spans),
+            None => Err(E::from_kind(ErrorKind::Unexpected, start)),
+        }
+    }
+}
+
+impl<'a, T> Parser<'a, T> {
+    fn consume_regex<E : JlnError>(&mut self, spans : Vec<Option<Span>>) -> Result<Vec<Option<Span>>, E> {
+        let end = spans[0].map(|x| x.end).unwrap_or(self.index);
+        self.with_rollback(|input| {
+            input.consume_trivia();
+            input.no_trivia(|input| {
+                while input.index < end {
+                    input.get::<E>()?;
+                }
+                Ok(spans)
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_match_bytes_at_current_position() {
+        let input = b"key=42;".to_vec();
+        let mut buffer = Parser::new(&input);
+        let regex = BytesPattern::new(r"(\w+)=(\d+)").unwrap();
+
+        let spans = buffer.regex::<()>(&regex).unwrap();
+
+        assert_eq!(spans, vec![Some(Span::new(0, 6)), Some(Span::new(0, 3)), Some(Span::new(4, 6))]);
+        assert_eq!(buffer.index(), 6);
+    }
+
+    #[test]
+    fn should_not_match_later_in_input() {
+        let input = b";key".to_vec();
+        let mut buffer = Parser::new(&input);
+        let regex = BytesPattern::new(r"\w+").unwrap();
+
+        assert!(buffer.regex::<()>(&regex).is_err());
+        assert_eq!(buffer.index(), 0);
+    }
+
+    #[test]
+    fn should_match_chars_with_char_spans() {
+        let input = " héllo wörld".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        buffer.set_trivia(Some(|c| *c == ' '));
+        let regex = Pattern::new(r"(\w)(\w+)").unwrap();
+
+        assert_eq!(buffer.regex::<()>(&regex), Ok(vec![Some(Span::new(1, 6)), Some(Span::new(1, 2)), Some(Span::new(2, 6))]));
+        assert_eq!(buffer.regex::<()>(&regex).map(|x| x[0]), Ok(Some(Span::new(7, 12))));
+        assert!(buffer.end());
+    }
+
+    #[test]
+    fn should_anchor_search_and_keep_leftmost_first_choice() {
+        let input = "abcd abc".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        buffer.set_trivia(Some(|c| *c == ' '));
+        let pattern = Pattern::new(r"abcd|a|abc\b").unwrap();
+
+        assert_eq!(buffer.regex::<()>(&pattern).map(|x| x[0]), Ok(Some(Span::new(0, 4))));
+        assert_eq!(buffer.regex::<()>(&pattern).map(|x| x[0]), Ok(Some(Span::new(5, 6))));
+        assert!(buffer.regex::<()>(&Pattern::new("z").unwrap()).is_err());
+        assert_eq!(buffer.index(), 6);
+        assert_eq!(Pattern::new("a+").unwrap().match_len(&['a', 'a', 'b', 'a']), Some(Some(2)));
+        assert_eq!(Pattern::new("b").unwrap().match_len(&['a', 'b']), Some(None));
+
+        let words = "word ".repeat(2000).chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&words);
+        let word = Pattern::new(r"\w+ ").unwrap();
+        let count = buffer.list(|input| input.regex::<()>(&word)).unwrap().len();
+        assert_eq!(count, 2000);
+        assert_eq!(Pattern::new(r"(?u:\b)é\b").unwrap().captures(&['é', '!'], 3), Some(vec![Some(Span::new(3, 4))]));
+    }
+}
@@ -0,0 +1,167 @@
+
+use crate::{JlnError, Parser, Span};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lexed<K> {
+    tokens : Vec<K>,
+    spans : Vec<Span>,
+    end : usize,
+}
+
+impl<K> Lexed<K> {
+    pub(crate) fn new(tokens : Vec<K>, spans : Vec<Span>, end : usize) -> Self {
+        Lexed { tokens, spans, end }
+    }
+
+    pub fn tokens(&self) -> &[K] {
+        &self.tokens
+    }
+
+    pub fn spans(&self) -> &[Span] {
+        &self.spans
+    }
+
+    pub fn parser(&self) -> Parser<'_, K> {
+        Parser::new(&self.tokens)
+    }
+
+    pub fn span(&self, index : usize) -> Span {
+        match self.spans.get(index) {
+            Some(span) => *span,
+            None => Span::new(self.end, self.end),
+        }
+    }
+
+    pub fn source_span(&self, tokens : Span) -> Span {
+        if tokens.is_empty() {
+            let start = self.span(tokens.start).start;
+            return Span::new(start, start);
+        }
+        Span::new(self.span(tokens.start).start, self.span(tokens.end - 1).end)
+    }
+
+    pub fn into_parser(self) -> Parser<'static, K> {
+        self.tokens.into()
+    }
+
+    pub fn into_parts(self) -> (Vec<K>, Vec<Span>) {
+        (self.tokens, self.spans)
+    }
+}
+
+pub struct Lexemes<'p, 'a, T, F> {
+    input : &'p mut Parser<'a, T>,
+    lexer : F,
+    done : bool,
+}
+
+impl<'p, 'a, T, K, E : JlnError, F : FnMut(&mut Parser<'a, T>) -> Result<K, E>> Iterator for Lexemes<'p, 'a, T, F> {
+    type Item = Result<(K, Span), E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        self.input.consume_trivia();
+        if self.input.end() {
+            self.done = true;
+            return None;
+        }
+        let result = self.input.with_rollback(|input| input.spanned(&mut self.lexer));
+        self.done = result.is_err();
+        Some(result)
+    }
+}
+
+impl<'a, T> Parser<'a, T> {
+    pub fn lexemes<K, E : JlnError, F : FnMut(&mut Parser<'a, T>) -> Result<K, E>>(&mut self, lexer : F) -> Lexemes<'_, 'a, T, F> {
+        Lexemes { input: self, lexer, done: false }
+    }
+
+    pub fn lex<K, E : JlnError, F : FnMut(&mut Parser<'a, T>) -> Result<K, E>>(&mut self, lexer : F) -> Result<Lexed<K>, E> {
+        let mut tokens = vec![];
+        let mut spans = vec![];
+        for lexeme in self.lexemes(lexer) {
+            let (token, span) = lexeme?;
+            tokens.push(token);
+            spans.push(span);
+        }
+        Ok(Lexed::new(tokens, spans, self.index))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{ident_continue, ident_start};
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Tok {
+        Num(u32),
+        Name(String),
+        Plus,
+    }
+
+    fn lexer(input : &mut Parser<char>) -> Result<Tok, ()> {
+        if input.expect::<()>(&'+').is_ok() {
+            return Ok(Tok::Plus);
+        }
+        if let Some(n) = input.option(|input| input.integer())? {
+            return Ok(Tok::Num(n));
+        }
+        input.identifier(ident_start, ident_continue).map(Tok::Name)
+    }
+
+    fn sum(input : &mut Parser<Tok>) -> Result<u32, ()> {
+        let mut total = 0;
+        loop {
+            match input.get::<()>()? {
+                Tok::Num(n) => { total += n; },
+                _ => { return Err(()); },
+            }
+            if input.end() {
+                return Ok(total);
+            }
+            input.expect(&Tok::Plus)?;
+        }
+    }
+
+    #[test]
+    fn should_lex_then_parse_tokens() {
+        let input = " 12 + 30 ".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        buffer.set_trivia(Some(|c| c.is_whitespace()));
+
+        let lexed = buffer.lex(lexer).unwrap();
+
+        assert_eq!(lexed.tokens(), &[Tok::Num(12), Tok::Plus, Tok::Num(30)]);
+        assert_eq!(sum(&mut lexed.parser()), Ok(42));
+    }
+
+    #[test]
+    fn should_map_token_spans_to_source() {
+        let input = "ab + 7".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        buffer.set_trivia(Some(|c| c.is_whitespace()));
+
+        let lexed = buffer.lex(lexer).unwrap();
+        let mut tokens = lexed.parser();
+
+        assert!(sum(&mut tokens).is_err());
+        assert_eq!(lexed.span(0), Span::new(0, 2));
+        assert_eq!(lexed.source_span(Span::new(1, 3)), Span::new(3, 6));
+        assert_eq!(lexed.span(3), Span::new(6, 6));
+    }
+
+    #[test]
+    fn should_stop_lazy_stream_at_error() {
+        let input = "a ? b".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        buffer.set_trivia(Some(|c| c.is_whitespace()));
+
+        let lexemes = buffer.lexemes(lexer).collect::<Vec<_>>();
+
+        assert_eq!(lexemes, vec![Ok((Tok::Name(String::from("a")), Span::new(0, 1))), Err(())]);
+        assert_eq!(buffer.index(), 2);
+    }
+}
@@ -0,0 +1,28 @@
+
+pub use crate::{Alternative, ErrorKind, JlnError, Parser};
+pub use crate::{BoxedRule, Integer, Parse, Rule, Sequence, TextToken};
+pub use crate::{Deferred, Diagnostic, Recovered, Span, StringStyle};
+pub use crate::{Assoc, KeywordTable, OperatorTable, Word};
+pub use crate::{ident_continue, ident_start};
+pub use crate::{assert_parse_err, assert_parses, rule};
+#[cfg(feature = "macros")]
+pub use crate::grammar;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    rule! {
+        fn pair(input : &mut Parser<char>) -> Result<(char, u32), ()> {
+            input.seq((|input : &mut Parser<char>| input.get().copied(), |input : &mut Parser<char>| input.integer()))
+        }
+    }
+
+    #[test]
+    fn should_parse_with_prelude_imports() {
+        let input = "x42".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        assert_eq!(pair(&mut buffer), Ok(('x', 42)));
+    }
+}
@@ -0,0 +1,130 @@
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use crate::Parser;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RuleProfile {
+    pub calls : usize,
+    pub inclusive : Duration,
+    pub exclusive : Duration,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Profile {
+    pub rules : HashMap<&'static str, RuleProfile>,
+    pub stacks : HashMap<Vec<&'static str>, Duration>,
+}
+
+impl Profile {
+    pub fn folded(&self) -> String {
+        let mut stacks = self.stacks.iter().collect::<Vec<_>>();
+        stacks.sort();
+        let mut text = String::new();
+        for (stack, time) in stacks {
+            let _ = writeln!(text, "{} {}", stack.join(";"), time.as_micros());
+        }
+        text
+    }
+}
+
+struct Frame {
+    rule : &'static str,
+    start : Instant,
+    children : Duration,
+}
+
+pub(crate) struct Profiler {
+    profile : Profile,
+    frames : Vec<Frame>,
+}
+
+pub(crate) type ProfileLog = Rc<RefCell<Profiler>>;
+
+impl<'a, T> Parser<'a, T> {
+    pub fn enable_profile(&mut self) {
+        if self.profile.is_none() {
+            self.profile = Some(Rc::new(RefCell::new(Profiler { profile: Profile::default(), frames: vec![] })));
+        }
+    }
+
+    pub fn disable_profile(&mut self) {
+        self.profile = None;
+    }
+
+    pub fn profile(&self) -> Option<Profile> {
+        self.profile.as_ref().map(|x| x.borrow().profile.clone())
+    }
+
+    pub(crate) fn profile_enter(&self, rule : &'static str) {
+        if let Some(profiler) = &self.profile {
+            profiler.borrow_mut().frames.push(Frame { rule, start: Instant::now(), children: Duration::ZERO });
+        }
+    }
+
+    pub(crate) fn profile_exit(&self) {
+        let Some(profiler) = &self.profile else { return; };
+        let mut profiler = profiler.borrow_mut();
+        let stack = profiler.frames.iter().map(|x| x.rule).collect::<Vec<_>>();
+        let Some(frame) = profiler.frames.pop() else { return; };
+        let elapsed = frame.start.elapsed();
+        let exclusive = elapsed.saturating_sub(frame.children);
+        if let Some(parent) = profiler.frames.last_mut() {
+            parent.children += elapsed;
+        }
+        let rule = profiler.profile.rules.entry(frame.rule).or_default();
+        rule.calls += 1;
+        rule.inclusive += elapsed;
+        rule.exclusive += exclusive;
+        *profiler.profile.stacks.entry(stack).or_default() += exclusive;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::TError;
+
+    fn letter(input : &mut Parser<char>) -> Result<char, TError> {
+        input.context("letter", |input| input.satisfy(|c : &char| c.is_alphabetic()).copied())
+    }
+
+    fn word(input : &mut Parser<char>) -> Result<String, TError> {
+        input.context("word", |input| Ok(input.list(letter)?.into_iter().collect()))
+    }
+
+    #[test]
+    fn should_profile_named_rules() {
+        let input = "ab".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        buffer.enable_profile();
+
+        word(&mut buffer).map_err(|_| ()).unwrap();
+
+        let profile = buffer.profile().unwrap();
+        assert_eq!(profile.rules["letter"].calls, 3);
+        assert_eq!(profile.rules["word"].calls, 1);
+        assert!(profile.rules["word"].inclusive >= profile.rules["letter"].inclusive);
+        assert!(profile.rules["word"].inclusive >= profile.rules["word"].exclusive);
+        assert_eq!(profile.stacks.len(), 2);
+        assert!(profile.stacks.contains_key(&vec!["word", "letter"]));
+
+        let folded = profile.folded();
+        let lines = folded.lines().map(|x| x.rsplit_once(' ').unwrap().0).collect::<Vec<_>>();
+        assert_eq!(lines, vec!["word", "word;letter"]);
+    }
+
+    #[test]
+    fn should_not_profile_by_default() {
+        let input = "a".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        letter(&mut buffer).map_err(|_| ()).unwrap();
+
+        assert_eq!(buffer.profile(), None);
+    }
+}
@@ -0,0 +1,147 @@
+
+use std::rc::Rc;
+
+use crate::{JlnError, Parser};
+
+pub(crate) struct Pushed<T> {
+    token : T,
+    next : Pushback<T>,
+}
+
+pub(crate) type Pushback<T> = Option<Rc<Pushed<T>>>;
+
+impl<'a, T> Parser<'a, T> {
+    pub fn unread(&mut self, token : T) {
+        let next = self.pushback.take();
+        self.pushback = Some(Rc::new(Pushed { token, next }));
+    }
+
+    pub fn pushed_back(&self) -> usize {
+        let mut count = 0;
+        let mut top = &self.pushback;
+        while let Some(pushed) = top {
+            count += 1;
+            top = &pushed.next;
+        }
+        count
+    }
+
+    pub(crate) fn peek_pushback(&self) -> Option<&T> {
+        self.pushback.as_ref().map(|x| &x.token)
+    }
+
+    pub(crate) fn pop_pushback<E : JlnError>(&mut self) -> Result<&T, E> {
+        self.burn()?;
+        let top = self.pushback.take().ok_or_else(|| self.exhausted())?;
+        self.pushback = top.next.clone();
+        Ok(&self.popped.insert(top).token)
+    }
+
+    pub(crate) fn splice(&mut self, tokens : Vec<T>) -> &[T] {
+        self.spliced = tokens;
+        &self.spliced
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Tok {
+        Name,
+        Lt,
+        Gt,
+        Shr,
+    }
+
+    fn close(input : &mut Parser<Tok>) -> Result<(), ()> {
+        match input.get::<()>()? {
+            Tok::Gt => Ok(()),
+            Tok::Shr => {
+                input.unread(Tok::Gt);
+                Ok(())
+            },
+            _ => Err(()),
+        }
+    }
+
+    fn generic(input : &mut Parser<Tok>) -> Result<usize, ()> {
+        input.expect(&Tok::Name)?;
+        if input.option(|input| input.expect::<()>(&Tok::Lt).copied())?.is_none() {
+            return Ok(0);
+        }
+        let depth = generic(input)?;
+        close(input)?;
+        Ok(depth + 1)
+    }
+
+    #[test]
+    fn should_split_token_with_pushback() {
+        let input = vec![Tok::Name, Tok::Lt, Tok::Name, Tok::Lt, Tok::Name, Tok::Shr];
+        let mut buffer = Parser::new(&input);
+
+        assert_eq!(generic(&mut buffer), Ok(2));
+        assert!(buffer.end());
+        assert_eq!(buffer.pushed_back(), 0);
+    }
+
+    #[test]
+    fn should_restore_pushback_on_rollback() {
+        let input = vec![Tok::Shr, Tok::Name];
+        let mut buffer = Parser::new(&input);
+        buffer.unread(Tok::Lt);
+
+        let result = buffer.with_rollback(|input| {
+            input.get::<()>()?;
+            input.unread(Tok::Gt);
+            input.expect::<()>(&Tok::Name).copied()
+        });
+
+        assert!(result.is_err());
+        assert_eq!(buffer.pushed_back(), 1);
+        assert!(!buffer.end());
+        assert_eq!(buffer.get::<()>(), Ok(&Tok::Lt));
+        assert_eq!(buffer.index(), 0);
+        assert_eq!(buffer.get::<()>(), Ok(&Tok::Shr));
+    }
+
+    #[test]
+    fn should_match_tags_against_pushed_back_tokens() {
+        let input = b"=;".to_vec();
+        let mut buffer = Parser::new(&input);
+        buffer.unread(b'>');
+
+        assert_eq!(buffer.tag::<()>(b">"), Ok(&b">"[..]));
+        assert_eq!(buffer.pushed_back(), 0);
+        assert_eq!(buffer.index(), 0);
+
+        buffer.unread(b'>');
+        buffer.unread(b'>');
+        assert_eq!(buffer.tag::<()>(b">>;"), Err(()));
+        assert_eq!(buffer.pushed_back(), 2);
+        assert_eq!(buffer.tag::<()>(b">>="), Ok(&b">>="[..]));
+        assert_eq!(buffer.index(), 1);
+    }
+
+    #[test]
+    fn should_take_pushed_back_tokens_before_input() {
+        let input = vec![Tok::Name, Tok::Name, Tok::Gt];
+        let mut buffer = Parser::new(&input);
+        buffer.unread(Tok::Name);
+
+        assert_eq!(buffer.take_while::<(), _>(|x| *x == Tok::Name), Ok(&[Tok::Name, Tok::Name, Tok::Name][..]));
+        assert_eq!(buffer.index(), 2);
+
+        buffer.unread(Tok::Gt);
+        buffer.unread(Tok::Lt);
+        assert_eq!(buffer.take_until::<()>(&Tok::Gt), Ok(&[Tok::Lt][..]));
+        assert_eq!(buffer.pushed_back(), 1);
+        assert_eq!(buffer.take_until::<()>(&Tok::Shr), Err(()));
+        assert_eq!(buffer.pushed_back(), 1);
+        assert_eq!(buffer.take_while::<(), _>(|x| *x == Tok::Lt), Ok(&[][..]));
+        assert_eq!(buffer.get::<()>(), Ok(&Tok::Gt));
+        assert_eq!(buffer.get::<()>(), Ok(&Tok::Gt));
+        assert!(buffer.end());
+    }
+}
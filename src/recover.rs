@@ -0,0 +1,86 @@
+
+use crate::{JlnError, Parser};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Recovered<S, E> {
+    Present(S),
+    Missing { error : E, index : usize },
+}
+
+impl<S, E> Recovered<S, E> {
+    pub fn is_missing(&self) -> bool {
+        matches!(self, Recovered::Missing { .. })
+    }
+
+    pub fn present(&self) -> Option<&S> {
+        match self {
+            Recovered::Present(value) => Some(value),
+            Recovered::Missing { .. } => None,
+        }
+    }
+
+    pub fn into_result(self) -> Result<S, E> {
+        match self {
+            Recovered::Present(value) => Ok(value),
+            Recovered::Missing { error, .. } => Err(error),
+        }
+    }
+}
+
+impl<'a, T> Parser<'a, T> {
+    pub fn or_missing<S, E : JlnError, F : FnOnce(&mut Parser<'a, T>) -> Result<S, E>>(&mut self, f : F) -> Result<Recovered<S, E>, E> {
+        match self.attempt(f) {
+            Ok(value) => Ok(Recovered::Present(value)),
+            Err(e) if self.fatal(&e) => Err(e),
+            Err(error) => {
+                let index = self.next_significant();
+                self.emit_error("missing");
+                Ok(Recovered::Missing { error, index })
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::TError;
+
+    fn digit(input : &mut Parser<char>) -> Result<u32, TError> {
+        input.satisfy(|c : &char| c.is_ascii_digit()).map(|c| c.to_digit(10).unwrap())
+    }
+
+    type Operand = Recovered<u32, TError>;
+
+    fn sum(input : &mut Parser<char>) -> Result<(Operand, Operand), TError> {
+        let left = input.or_missing(digit)?;
+        input.expect(&'+')?;
+        let right = input.or_missing(digit)?;
+        Ok((left, right))
+    }
+
+    #[test]
+    fn should_produce_placeholder_for_missing_operand() {
+        let input = "1+".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        let (left, right) = sum(&mut buffer).unwrap();
+
+        assert_eq!(left.present(), Some(&1));
+        assert!(right.is_missing());
+        assert!(matches!(right, Recovered::Missing { index: 2, .. }));
+        assert!(buffer.end());
+    }
+
+    #[test]
+    fn should_propagate_fatal_errors() {
+        let input = "x".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        let result = buffer.or_missing(|_| Err::<(), _>(TError(true)));
+
+        assert!(result.is_err());
+        assert_eq!(buffer.or_missing(digit).map(|x| x.is_missing()).ok(), Some(true));
+        assert_eq!(buffer.index(), 0);
+    }
+}
@@ -0,0 +1,155 @@
+
+use std::sync::Arc;
+
+use crate::{Input, JlnError, Parser, Span};
+
+enum Shared<'a, T> {
+    Ref(&'a [T]),
+    Arc(Arc<[T]>),
+}
+
+impl<'a, T> Clone for Shared<'a, T> {
+    fn clone(&self) -> Self {
+        match self {
+            Shared::Ref(x) => Shared::Ref(x),
+            Shared::Arc(x) => Shared::Arc(Arc::clone(x)),
+        }
+    }
+}
+
+pub struct Region<'a, T> {
+    input : Shared<'a, T>,
+    span : Span,
+    trivia : Option<fn(&T) -> bool>,
+}
+
+impl<'a, T> Clone for Region<'a, T> {
+    fn clone(&self) -> Self {
+        Region { input: self.input.clone(), span: self.span, trivia: self.trivia }
+    }
+}
+
+impl<'a, T> Region<'a, T> {
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn len(&self) -> usize {
+        self.span.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.span.is_empty()
+    }
+
+    pub fn split_at(&self, index : usize) -> (Region<'a, T>, Region<'a, T>) {
+        let mid = index.clamp(self.span.start, self.span.end);
+        let mut left = self.clone();
+        let mut right = self.clone();
+        left.span.end = mid;
+        right.span.start = mid;
+        (left, right)
+    }
+
+    pub fn parser(&self) -> Parser<'_, T> {
+        let input = match &self.input {
+            Shared::Ref(x) => x,
+            Shared::Arc(x) => &x[..],
+        };
+        let mut parser = Parser::new(input);
+        parser.index = self.span.start;
+        parser.limit = Some(self.span.end);
+        parser.trivia = self.trivia;
+        parser
+    }
+
+    pub fn parse<S, E : JlnError, F : FnOnce(&mut Parser<'_, T>) -> Result<S, E>>(&self, f : F) -> Result<S, E> {
+        f(&mut self.parser())
+    }
+}
+
+impl<'a, T : Clone> Parser<'a, T> {
+    pub fn region(&self, span : Span) -> Region<'a, T> {
+        let input = match &self.input {
+            Input::Ref(x) => Shared::Ref(x),
+            Input::Arc(x) => Shared::Arc(Arc::clone(x)),
+            Input::Rc(_) | Input::Owned(_) => Shared::Arc(Arc::clone(self.shared.get_or_init(|| Arc::from(&self.input[..])))),
+        };
+        let end = span.end.min(self.input.len());
+        Region { input, span: Span::new(span.start.min(end), end), trivia: self.trivia }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::thread;
+
+    use super::*;
+    use crate::test::TError;
+    use crate::Config;
+
+    fn assert_send_sync<X : Send + Sync>() { }
+
+    #[test]
+    fn should_be_send_and_sync() {
+        assert_send_sync::<Region<'static, u8>>();
+        assert_send_sync::<Region<'static, char>>();
+        assert_send_sync::<Span>();
+        assert_send_sync::<crate::ErrorKind>();
+        assert_send_sync::<Config>();
+        assert_send_sync::<crate::Diagnostic>();
+        assert_send_sync::<crate::Deferred<crate::Diagnostic>>();
+        assert_send_sync::<crate::Warning>();
+        #[cfg(feature = "serde")]
+        assert_send_sync::<crate::DeError>();
+        #[cfg(feature = "tokio")]
+        assert_send_sync::<crate::CodecError<crate::ErrorKind>>();
+    }
+
+    fn sum(input : &mut Parser<u8>) -> Result<u32, TError> {
+        let items = input.list(|input| input.get::<TError>().map(|x| *x as u32))?;
+        Ok(items.into_iter().sum())
+    }
+
+    #[test]
+    fn should_parse_regions_on_threads() {
+        let input : Arc<[u8]> = (1..=100).collect::<Vec<u8>>().into();
+        let buffer = Parser::from(input);
+        let (left, right) = buffer.region(Span::new(0, 100)).split_at(50);
+
+        let (a, b) = thread::scope(|scope| {
+            let a = scope.spawn(|| left.parse(sum).map_err(|_| ()));
+            let b = scope.spawn(|| right.parse(sum).map_err(|_| ()));
+            (a.join().unwrap(), b.join().unwrap())
+        });
+
+        assert_eq!(a, Ok(1275));
+        assert_eq!(b, Ok(3775));
+        assert_eq!(left.span(), Span::new(0, 50));
+    }
+
+    #[test]
+    fn should_clamp_regions_to_input() {
+        let input = vec![1u8, 2, 3];
+        let buffer = Parser::new(&input);
+        let region = buffer.region(Span::new(2, 10));
+
+        assert_eq!(region.span(), Span::new(2, 3));
+        assert_eq!(region.parse(sum).map_err(|_| ()), Ok(3));
+        assert!(buffer.region(Span::new(5, 9)).is_empty());
+    }
+
+    #[test]
+    fn should_share_one_copy_of_rc_input_between_regions() {
+        let buffer = Parser::from(vec![1u8, 2, 3, 4]);
+        let left = buffer.region(Span::new(0, 2));
+        let right = buffer.clone().region(Span::new(2, 4));
+
+        match (&left.input, &right.input) {
+            (Shared::Arc(x), Shared::Arc(y)) => { assert!(Arc::ptr_eq(x, y)); },
+            _ => { panic!("regions of rc input should share an arc"); },
+        }
+        assert_eq!(left.parse(sum).map_err(|_| ()), Ok(3));
+        assert_eq!(right.parse(sum).map_err(|_| ()), Ok(7));
+    }
+}
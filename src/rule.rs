@@ -0,0 +1,277 @@
+
+use std::marker::PhantomData;
+use std::panic::Location;
+
+use crate::{Furthest, JlnError, Parser};
+
+pub trait Rule<T, S, E : JlnError> {
+    fn run(&mut self, input : &mut Parser<'_, T>) -> Result<S, E>;
+
+    fn map<R, M : FnMut(S) -> R>(self, f : M) -> Map<Self, M, S> where Self : Sized {
+        Map { rule: self, f, value: PhantomData }
+    }
+
+    fn then<R, B : Rule<T, R, E>>(self, next : B) -> Then<Self, B> where Self : Sized {
+        Then { first: self, second: next }
+    }
+
+    #[track_caller]
+    fn or<B : Rule<T, S, E>>(self, other : B) -> Or<Self, B> where Self : Sized {
+        Or { first: self, second: other, location: Location::caller() }
+    }
+
+    fn repeated(self) -> Repeated<Self> where Self : Sized {
+        Repeated { rule: self }
+    }
+
+    fn optional(self) -> Optional<Self> where Self : Sized {
+        Optional { rule: self }
+    }
+
+    fn boxed<'r>(self) -> BoxedRule<'r, T, S, E> where Self : Sized + 'r {
+        Box::new(self)
+    }
+}
+
+pub type BoxedRule<'r, T, S, E> = Box<dyn Rule<T, S, E> + 'r>;
+
+impl<T, S, E : JlnError> Rule<T, S, E> for BoxedRule<'_, T, S, E> {
+    fn run(&mut self, input : &mut Parser<'_, T>) -> Result<S, E> {
+        (**self).run(input)
+    }
+}
+
+impl<T, S, E : JlnError, F : FnMut(&mut Parser<'_, T>) -> Result<S, E>> Rule<T, S, E> for F {
+    fn run(&mut self, input : &mut Parser<'_, T>) -> Result<S, E> {
+        self(input)
+    }
+}
+
+pub struct Map<A, M, S> {
+    rule : A,
+    f : M,
+    value : PhantomData<fn() -> S>,
+}
+
+impl<T, S, R, E : JlnError, A : Rule<T, S, E>, M : FnMut(S) -> R> Rule<T, R, E> for Map<A, M, S> {
+    fn run(&mut self, input : &mut Parser<'_, T>) -> Result<R, E> {
+        self.rule.run(input).map(&mut self.f)
+    }
+}
+
+pub struct Then<A, B> {
+    first : A,
+    second : B,
+}
+
+impl<T, S, R, E : JlnError, A : Rule<T, S, E>, B : Rule<T, R, E>> Rule<T, (S, R), E> for Then<A, B> {
+    fn run(&mut self, input : &mut Parser<'_, T>) -> Result<(S, R), E> {
+        input.with_rollback(|input| {
+            let a = self.first.run(input)?;
+            let b = self.second.run(input)?;
+            Ok((a, b))
+        })
+    }
+}
+
+pub struct Or<A, B> {
+    first : A,
+    second : B,
+    location : &'static Location<'static>,
+}
+
+impl<T, S, E : JlnError, A : Rule<T, S, E>, B : Rule<T, S, E>> Rule<T, S, E> for Or<A, B> {
+    fn run(&mut self, input : &mut Parser<'_, T>) -> Result<S, E> {
+        let mut furthest = Furthest::<E, 2>::new();
+        if let Some(v) = input.alternative_at(&mut furthest, self.location, |input| self.first.run(input))? {
+            return Ok(v);
+        }
+        match input.alternative_at(&mut furthest, self.location, |input| self.second.run(input))? {
+            Some(v) => Ok(v),
+            None => Err(furthest.into_error()),
+        }
+    }
+}
+
+pub struct Repeated<A> {
+    rule : A,
+}
+
+impl<T, S, E : JlnError, A : Rule<T, S, E>> Rule<T, Vec<S>, E> for Repeated<A> {
+    fn run(&mut self, input : &mut Parser<'_, T>) -> Result<Vec<S>, E> {
+        input.list(|input| self.rule.run(input))
+    }
+}
+
+pub struct Optional<A> {
+    rule : A,
+}
+
+impl<T, S, E : JlnError, A : Rule<T, S, E>> Rule<T, Option<S>, E> for Optional<A> {
+    fn run(&mut self, input : &mut Parser<'_, T>) -> Result<Option<S>, E> {
+        input.option(|input| self.rule.run(input))
+    }
+}
+
+pub trait Sequence<T, S, E : JlnError> {
+    fn run_seq(&mut self, input : &mut Parser<'_, T>) -> Result<S, E>;
+}
+
+macro_rules! sequence {
+    ($($rule:ident : $value:ident),+) => {
+        impl<T, E : JlnError, $($value,)+ $($rule : Rule<T, $value, E>,)+> Sequence<T, ($($value,)+), E> for ($($rule,)+) {
+            #[allow(non_snake_case)]
+            fn run_seq(&mut self, input : &mut Parser<'_, T>) -> Result<($($value,)+), E> {
+                let ($($rule,)+) = self;
+                input.with_rollback(|input| Ok(($($rule.run(input)?,)+)))
+            }
+        }
+    };
+}
+
+sequence!(A : SA);
+sequence!(A : SA, B : SB);
+sequence!(A : SA, B : SB, C : SC);
+sequence!(A : SA, B : SB, C : SC, D : SD);
+sequence!(A : SA, B : SB, C : SC, D : SD, F : SF);
+sequence!(A : SA, B : SB, C : SC, D : SD, F : SF, G : SG);
+sequence!(A : SA, B : SB, C : SC, D : SD, F : SF, G : SG, H : SH);
+sequence!(A : SA, B : SB, C : SC, D : SD, F : SF, G : SG, H : SH, I : SI);
+
+impl<'a, T> Parser<'a, T> {
+    pub fn seq<S, E : JlnError, Q : Sequence<T, S, E>>(&mut self, mut rules : Q) -> Result<S, E> {
+        rules.run_seq(self)
+    }
+
+    pub fn list_dyn<S, E : JlnError>(&mut self, rule : &mut dyn Rule<T, S, E>) -> Result<Vec<S>, E> {
+        self.list(|input| rule.run(input))
+    }
+
+    #[track_caller]
+    pub fn or_dyn<S, E : JlnError>(&mut self, rules : &mut [BoxedRule<'_, T, S, E>]) -> Result<S, E> {
+        let location = Location::caller();
+        let branches = rules.len();
+        let mut furthest = Furthest::<E, 4>::new();
+        for (branch, rule) in rules.iter_mut().enumerate() {
+            self.count_alternative(location, branch, branches);
+            if let Some(v) = self.alternative(&mut furthest, |input| rule.run(input))? {
+                self.cover_alternative(location, branch, branches);
+                return Ok(v);
+            }
+        }
+        Err(furthest.into_error())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Diagnostic;
+
+    fn digit(input : &mut Parser<char>) -> Result<u32, ()> {
+        input.satisfy(|c : &char| c.is_ascii_digit()).map(|c| c.to_digit(10).unwrap())
+    }
+
+    fn sign(input : &mut Parser<char>) -> Result<char, ()> {
+        input.satisfy(|c : &char| *c == '-' || *c == '+').copied()
+    }
+
+    #[test]
+    fn should_run_boxed_rules() {
+        let input = "-1+2x".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        let mut rules = [digit.map(|d| d as i64).boxed(), sign.map(|c| if c == '-' { -1 } else { 0 }).boxed()];
+
+        let mut item = |input : &mut Parser<char>| input.or_dyn(&mut rules);
+        let items = buffer.list_dyn(&mut item);
+
+        assert_eq!(items, Ok(vec![-1, 1, 0, 2]));
+        assert!(buffer.or_dyn(&mut rules).is_err());
+        assert_eq!(buffer.index(), 4);
+
+        let mut pair = digit.boxed().then(sign.boxed());
+        assert!(pair.run(&mut buffer).is_err());
+    }
+
+    #[test]
+    fn should_compose_rules() {
+        let input = "-12x".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        let mut number = sign.optional()
+            .then(digit.repeated())
+            .map(|(sign, digits)| {
+                let n = digits.into_iter().fold(0i64, |acc, d| acc * 10 + d as i64);
+                if sign == Some('-') { -n } else { n }
+            });
+
+        assert_eq!(number.run(&mut buffer), Ok(-12));
+        assert_eq!(buffer.index(), 3);
+    }
+
+    #[test]
+    fn should_try_second_rule_after_failure() {
+        let input = "+".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        let mut rule = digit.map(|_| '0').or(sign);
+
+        assert_eq!(rule.run(&mut buffer), Ok('+'));
+        assert!(rule.run(&mut buffer).is_err());
+    }
+
+    #[test]
+    fn should_report_furthest_failing_rules() {
+        fn ab(input : &mut Parser<char>) -> Result<char, Diagnostic> {
+            input.expect::<Diagnostic>(&'a')?;
+            input.expect::<Diagnostic>(&'b').copied()
+        }
+
+        fn x(input : &mut Parser<char>) -> Result<char, Diagnostic> {
+            input.expect::<Diagnostic>(&'x').copied()
+        }
+
+        let input = "a!".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        let mut rules = [x.boxed(), ab.boxed()];
+        let alone = ab(&mut Parser::new(&input)).unwrap_err().snapshot();
+
+        assert_eq!(x.or(ab).run(&mut buffer).unwrap_err().snapshot(), alone);
+        assert_eq!(buffer.or_dyn(&mut rules).unwrap_err().snapshot(), alone);
+        assert_eq!(buffer.index(), 0);
+    }
+
+    #[test]
+    fn should_parse_tuple_sequence() {
+        let input = "+7-".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        assert_eq!(buffer.seq((sign, digit)), Ok(('+', 7)));
+        assert!(buffer.seq((sign, digit)).is_err());
+        assert_eq!(buffer.index(), 2);
+    }
+
+    #[test]
+    fn should_roll_back_failed_sequence() {
+        let input = "5-".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        assert!(digit.then(digit).run(&mut buffer).is_err());
+        assert_eq!(buffer.index(), 0);
+    }
+
+    #[test]
+    fn should_cover_branches_of_or_rules() {
+        let input = "-1".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        buffer.enable_coverage();
+        let mut item = digit.or(sign.map(|_| 0));
+
+        assert_eq!(item.run(&mut buffer), Ok(0));
+        assert_eq!(item.run(&mut buffer), Ok(1));
+
+        let coverage = buffer.coverage().unwrap();
+        assert_eq!(coverage.alternatives.values().collect::<Vec<_>>(), vec![&vec![1, 1]]);
+        assert_eq!(coverage.alternatives.keys().next().unwrap().file(), file!());
+    }
+}
@@ -0,0 +1,63 @@
+
+use memchr::{memchr, memchr2, memchr3, memmem};
+
+use crate::{JlnError, Parser};
+
+impl<'a> Parser<'a, u8> {
+    pub fn take_until_any<E : JlnError>(&mut self, bytes : &[u8]) -> Result<&[u8], E> {
+        self.consume_trivia();
+        let start = self.index;
+        let haystack = &self.input[start..self.bound()];
+        let found = match *bytes {
+            [] => None,
+            [a] => memchr(a, haystack),
+            [a, b] => memchr2(a, b, haystack),
+            [a, b, c] => memchr3(a, b, c, haystack),
+            _ => haystack.iter().position(|x| bytes.contains(x)),
+        };
+        match found {
+            Some(length) => { self.consume_to(start + length)?; },
+            None => { return Err(self.exhausted()); },
+        }
+        Ok(&self.input[start..self.index])
+    }
+
+    pub fn take_until_tag<E : JlnError>(&mut self, tag : &[u8]) -> Result<&[u8], E> {
+        self.consume_trivia();
+        let start = self.index;
+        match memmem::find(&self.input[start..self.bound()], tag) {
+            Some(length) => { self.consume_to(start + length)?; },
+            None => { return Err(self.exhausted()); },
+        }
+        Ok(&self.input[start..self.index])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::TError;
+
+    #[test]
+    fn should_scan_to_any_byte() {
+        let input = b"key = \"value\"\n".to_vec();
+        let mut buffer = Parser::new(&input);
+
+        assert_eq!(buffer.take_until_any::<TError>(b" =").map_err(|_| ()), Ok(&b"key"[..]));
+        buffer.take_until_any::<TError>(b"\"").map_err(|_| ()).unwrap();
+        buffer.get::<TError>().map_err(|_| ()).unwrap();
+        assert_eq!(buffer.take_until_any::<TError>(b"\"\\\n").map_err(|_| ()), Ok(&b"value"[..]));
+        assert!(buffer.take_until_any::<TError>(b"!?;,").is_err());
+        assert_eq!(buffer.index(), 12);
+    }
+
+    #[test]
+    fn should_scan_to_tag() {
+        let input = b"<!-- note -->rest".to_vec();
+        let mut buffer = Parser::new(&input);
+
+        assert_eq!(buffer.take_until_tag::<TError>(b"-->").map_err(|_| ()), Ok(&b"<!-- note "[..]));
+        assert_eq!(buffer.index(), 10);
+        assert!(buffer.take_until_tag::<TError>(b"<!--").is_err());
+    }
+}
@@ -0,0 +1,163 @@
+
+use crate::{ErrorKind, JlnError, Parser, Span, StringStyle, TextToken};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SexpValue {
+    Symbol(String),
+    String(String),
+    Integer(i64),
+    Float(f64),
+    List(Vec<Sexp>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sexp {
+    pub value : SexpValue,
+    pub span : Span,
+}
+
+impl Sexp {
+    pub fn symbol(&self) -> Option<&str> {
+        match &self.value {
+            SexpValue::Symbol(name) => Some(name),
+            _ => None,
+        }
+    }
+
+    pub fn list(&self) -> Option<&[Sexp]> {
+        match &self.value {
+            SexpValue::List(items) => Some(items),
+            _ => None,
+        }
+    }
+}
+
+fn delimiter(c : char) -> bool {
+    c.is_whitespace() || matches!(c, '(' | ')' | '"' | ';' | '\'')
+}
+
+fn number(text : &str) -> Option<SexpValue> {
+    let digits = text.strip_prefix(['-', '+']).unwrap_or(text);
+    if !digits.starts_with(|c : char| c.is_ascii_digit()) {
+        return None;
+    }
+    match text.parse() {
+        Ok(n) => Some(SexpValue::Integer(n)),
+        Err(_) => text.parse().ok().map(SexpValue::Float),
+    }
+}
+
+impl<'a, T : TextToken> Parser<'a, T> {
+    pub fn sexp<E : JlnError>(&mut self) -> Result<Sexp, E> {
+        self.with_rollback(|input| input.no_trivia(|input| {
+            input.sexp_blank()?;
+            input.sexp_item()
+        }))
+    }
+
+    pub fn sexps<E : JlnError>(&mut self) -> Result<Vec<Sexp>, E> {
+        self.with_rollback(|input| input.no_trivia(|input| {
+            let mut items = vec![];
+            loop {
+                input.sexp_blank()?;
+                if input.end() {
+                    return Ok(items);
+                }
+                items.push(input.sexp_item()?);
+            }
+        }))
+    }
+
+    fn sexp_item<E : JlnError>(&mut self) -> Result<Sexp, E> {
+        let mut input = self.enter_rule()?;
+        let start = input.index;
+        let value = match input.peek::<E>()?.to_char() {
+            '(' => input.node("list", |input| {
+                input.get::<E>()?;
+                let mut items = vec![];
+                loop {
+                    input.sexp_blank()?;
+                    if input.end() {
+                        return Err(input.raise(ErrorKind::Unterminated, start));
+                    }
+                    if input.peek::<E>()?.to_char() == ')' {
+                        input.get::<E>()?;
+                        return Ok(SexpValue::List(items));
+                    }
+                    items.push(input.sexp_item()?);
+                }
+            })?,
+            '\'' => input.node("quote", |input| {
+                input.get::<E>()?;
+                let quote = Sexp { value: SexpValue::Symbol(String::from("quote")), span: Span::new(start, start + 1) };
+                input.sexp_blank()?;
+                Ok(SexpValue::List(vec![quote, input.sexp_item()?]))
+            })?,
+            '"' => input.node("string", |input| input.string_literal::<E>(&StringStyle::default()).map(|(value, _)| SexpValue::String(value)))?,
+            ')' => { return Err(E::from_kind(ErrorKind::Unexpected, start)); },
+            _ => input.node("atom", |input| {
+                let text = input.take_while::<E, _>(|t| !delimiter(t.to_char()))?.iter().map(|t| t.to_char()).collect::<String>();
+                Ok(number(&text).unwrap_or(SexpValue::Symbol(text)))
+            })?,
+        };
+        Ok(Sexp { value, span: Span::new(start, input.index) })
+    }
+
+    fn sexp_blank<E : JlnError>(&mut self) -> Result<(), E> {
+        loop {
+            self.take_while::<E, _>(|t| t.to_char().is_whitespace())?;
+            if !self.peek::<E>().is_ok_and(|t| t.to_char() == ';') {
+                return Ok(());
+            }
+            self.take_while::<E, _>(|t| t.to_char() != '\n')?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Diagnostic;
+
+    fn parse(text : &str) -> Result<Vec<Sexp>, String> {
+        let input = text.chars().collect::<Vec<_>>();
+        Parser::new(&input).sexps::<Diagnostic>().map_err(|e| e.snapshot())
+    }
+
+    #[test]
+    fn should_read_nested_forms_with_spans() {
+        let forms = parse("; header\n(define (sq x) (* x x)) 'sym \"a\\nb\" -12 1.5e3 +inf").unwrap();
+
+        assert_eq!(forms.len(), 6);
+        let define = forms[0].list().unwrap();
+        assert_eq!(define[0].symbol(), Some("define"));
+        assert_eq!(define[1].span, Span::new(17, 23));
+        assert_eq!(forms[0].span, Span::new(9, 32));
+        assert_eq!(forms[1].list().unwrap().iter().map(|x| x.symbol()).collect::<Vec<_>>(), vec![Some("quote"), Some("sym")]);
+        assert_eq!(forms[2].value, SexpValue::String("a\nb".into()));
+        assert_eq!(forms[3].value, SexpValue::Integer(-12));
+        assert_eq!(forms[4].value, SexpValue::Float(1500.0));
+        assert_eq!(forms[5].symbol(), Some("+inf"));
+    }
+
+    #[test]
+    fn should_report_unbalanced_lists() {
+        assert_eq!(parse("(a (b c)"), Err("at 0: Unterminated\n".into()));
+        assert_eq!(parse("a)"), Err("at 1: Unexpected\n".into()));
+    }
+
+    #[test]
+    fn should_limit_nesting_and_capture_cst() {
+        let input = "((((x))))".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        buffer.set_max_depth(Some(3));
+        assert!(buffer.sexp::<()>().is_err());
+
+        let mut buffer = Parser::new(&input[2..7]);
+        let builder = buffer.capture_cst();
+        buffer.sexp::<()>().unwrap();
+        let tree = buffer.finish_cst(builder, "root");
+        assert_eq!(tree.nodes().next().unwrap().kind, "list");
+        assert_eq!(tree.tokens().into_iter().collect::<String>(), "((x))");
+    }
+}
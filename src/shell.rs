@@ -0,0 +1,136 @@
+
+use crate::{ErrorKind, JlnError, Parser, Span, TextToken};
+
+impl<'a, T : TextToken> Parser<'a, T> {
+    pub fn shell_words<E : JlnError>(&mut self) -> Result<Vec<(String, Span)>, E> {
+        self.with_rollback(|input| input.no_trivia(|input| {
+            let mut words = vec![];
+            loop {
+                input.shell_blank()?;
+                if input.end() {
+                    return Ok(words);
+                }
+                words.push(input.shell_word()?);
+            }
+        }))
+    }
+
+    pub fn shell_word<E : JlnError>(&mut self) -> Result<(String, Span), E> {
+        self.with_rollback(|input| input.no_trivia(|input| {
+            input.shell_blank()?;
+            let start = input.index;
+            let mut word = String::new();
+            while let Ok(t) = input.peek::<E>() {
+                let at = input.index;
+                match t.to_char() {
+                    ' ' | '\t' | '\n' | '\r' => { break; },
+                    '\'' => {
+                        input.get::<E>()?;
+                        loop {
+                            match input.shell_char(at)? {
+                                '\'' => { break; },
+                                c => { word.push(c); },
+                            }
+                        }
+                    },
+                    '"' => {
+                        input.get::<E>()?;
+                        loop {
+                            match input.shell_char(at)? {
+                                '"' => { break; },
+                                '\\' => {
+                                    match input.shell_char(at)? {
+                                        '\n' => { },
+                                        c @ ('$' | '`' | '"' | '\\') => { word.push(c); },
+                                        c => {
+                                            word.push('\\');
+                                            word.push(c);
+                                        },
+                                    }
+                                },
+                                c => { word.push(c); },
+                            }
+                        }
+                    },
+                    '\\' => {
+                        input.get::<E>()?;
+                        match input.shell_char(at)? {
+                            '\n' => { },
+                            c => { word.push(c); },
+                        }
+                    },
+                    c => {
+                        input.get::<E>()?;
+                        word.push(c);
+                    },
+                }
+            }
+            if input.index == start {
+                return Err(E::from_kind(ErrorKind::Unexpected, start));
+            }
+            Ok((word, Span::new(start, input.index)))
+        }))
+    }
+
+    fn shell_blank<E : JlnError>(&mut self) -> Result<(), E> {
+        loop {
+            match self.peek::<E>().map(|t| t.to_char()) {
+                Ok(' ' | '\t' | '\n' | '\r') => { self.get::<E>()?; },
+                Ok('\\') if self.index + 1 < self.bound() && self.input[self.index + 1].to_char() == '\n' => {
+                    self.get::<E>()?;
+                    self.get::<E>()?;
+                },
+                Ok('#') => {
+                    while self.peek::<E>().is_ok_and(|t| t.to_char() != '\n') {
+                        self.get::<E>()?;
+                    }
+                },
+                _ => { return Ok(()); },
+            }
+        }
+    }
+
+    fn shell_char<E : JlnError>(&mut self, start : usize) -> Result<char, E> {
+        if self.end() {
+            return Err(self.raise(ErrorKind::Unterminated, start));
+        }
+        Ok(self.get::<E>()?.to_char())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Diagnostic;
+
+    fn words(text : &str) -> Result<Vec<String>, String> {
+        let input = text.chars().collect::<Vec<_>>();
+        let words = Parser::new(&input).shell_words::<Diagnostic>().map_err(|e| e.snapshot())?;
+        Ok(words.into_iter().map(|(word, _)| word).collect())
+    }
+
+    #[test]
+    fn should_split_quoted_and_escaped_words() {
+        assert_eq!(words(r#"echo 'a b'"c\"d\x" e\ f ''"#), Ok(vec!["echo".into(), "a bc\"d\\x".into(), "e f".into(), "".into()]));
+        assert_eq!(words("run \\\n  --fast # skip this\nnext#tag"), Ok(vec!["run".into(), "--fast".into(), "next#tag".into()]));
+        assert_eq!(words("  # only a comment"), Ok(vec![]));
+    }
+
+    #[test]
+    fn should_span_words_including_quotes() {
+        let input = " ls  \"my dir\"".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        assert_eq!(buffer.shell_word::<()>(), Ok((String::from("ls"), Span::new(1, 3))));
+        assert_eq!(buffer.shell_word::<()>(), Ok((String::from("my dir"), Span::new(5, 13))));
+        assert!(buffer.shell_word::<()>().is_err());
+        assert_eq!(buffer.index(), 13);
+    }
+
+    #[test]
+    fn should_reject_unterminated_quotes() {
+        assert_eq!(words("a 'b c"), Err("at 2: Unterminated\n".into()));
+        assert_eq!(words("a \"b\\\""), Err("at 2: Unterminated\n".into()));
+        assert_eq!(words("a\\"), Err("at 1: Unterminated\n".into()));
+    }
+}
@@ -0,0 +1,51 @@
+
+use crate::{JlnError, Parser};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Span {
+    pub start : usize,
+    pub end : usize,
+}
+
+impl Span {
+    pub fn new(start : usize, end : usize) -> Self {
+        Span { start, end }
+    }
+
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+impl<'a, T> Parser<'a, T> {
+    pub fn spanned<S, E : JlnError, F : FnOnce(&mut Parser<'a, T>) -> Result<S, E>>(&mut self, f : F) -> Result<(S, Span), E> {
+        let start = self.next_significant();
+        let value = f(self)?;
+        Ok((value, Span::new(start, self.index.max(start))))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_span_consumed_tokens_without_leading_trivia() {
+        let input = "  ab c".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        buffer.set_trivia(Some(|c| *c == ' '));
+
+        let result = buffer.spanned(|input| {
+            input.get::<()>()?;
+            input.get::<()>()?;
+            Ok::<(), ()>(())
+        });
+
+        assert_eq!(result, Ok(((), Span::new(2, 4))));
+        assert_eq!(Span::new(2, 4).len(), 2);
+    }
+}
@@ -0,0 +1,124 @@
+
+use std::any::Any;
+use std::rc::Rc;
+
+use crate::{ErrorKind, JlnError, Parser};
+
+impl<'a, T> Parser<'a, T> {
+    pub fn set_state<U : Clone + 'static>(&mut self, state : U) {
+        self.state = Some(Rc::new(state));
+    }
+
+    pub fn clear_state(&mut self) {
+        self.state = None;
+    }
+
+    pub fn state<U : 'static>(&self) -> Option<&U> {
+        self.state.as_ref().and_then(|x| x.downcast_ref())
+    }
+
+    pub fn state_mut<U : Clone + 'static>(&mut self) -> Option<&mut U> {
+        let state = self.state.as_mut()?;
+        if !state.is::<U>() {
+            return None;
+        }
+        if Rc::get_mut(state).is_none() {
+            let copy = state.downcast_ref::<U>()?.clone();
+            *state = Rc::new(copy);
+        }
+        Rc::get_mut(state).and_then(|x| x.downcast_mut())
+    }
+
+    pub fn pred_state<U : 'static, E : JlnError, P : FnOnce(&U, &Parser<'a, T>) -> bool>(&mut self, pred : P) -> Result<(), E> {
+        let state = self.state.clone();
+        match state.as_deref().and_then(|x : &dyn Any| x.downcast_ref::<U>()) {
+            Some(state) if pred(state, self) => Ok(()),
+            _ => Err(E::from_kind(ErrorKind::Unexpected, self.next_significant())),
+        }
+    }
+
+    pub fn guard<U : 'static, S, E : JlnError, P : FnOnce(&U) -> bool, F : FnOnce(&mut Parser<'a, T>) -> Result<S, E>>(&mut self, pred : P, f : F) -> Result<S, E> {
+        self.pred_state::<U, E, _>(|state, _| pred(state))?;
+        self.attempt(f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    type Types = HashSet<String>;
+
+    fn name(input : &mut Parser<char>) -> Result<String, ()> {
+        Ok(input.list(|input| input.satisfy::<(), _>(|c| c.is_alphabetic()).copied())?.into_iter().collect())
+    }
+
+    fn typedef(input : &mut Parser<char>) -> Result<String, ()> {
+        input.tag_str::<()>("typedef ")?;
+        let name = name(input)?;
+        input.state_mut::<Types>().unwrap().insert(name.clone());
+        Ok(name)
+    }
+
+    fn declaration(input : &mut Parser<char>) -> Result<(String, String), ()> {
+        let ty = name(input)?;
+        input.pred_state(|types : &Types, _| types.contains(&ty))?;
+        input.expect(&' ')?;
+        Ok((ty, name(input)?))
+    }
+
+    #[test]
+    fn should_use_state_in_predicate() {
+        let input = "typedef T;T x".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        buffer.set_state(Types::new());
+
+        assert_eq!(typedef(&mut buffer), Ok(String::from("T")));
+        buffer.expect::<()>(&';').unwrap();
+        assert_eq!(declaration(&mut buffer), Ok((String::from("T"), String::from("x"))));
+    }
+
+    #[test]
+    fn should_roll_back_state_with_failed_branch() {
+        let input = "typedef U".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        buffer.set_state(Types::new());
+
+        let result = buffer.with_rollback(|input| {
+            typedef(input)?;
+            input.expect::<()>(&';')?;
+            Ok::<(), ()>(())
+        });
+
+        assert!(result.is_err());
+        assert!(buffer.state::<Types>().unwrap().is_empty());
+    }
+
+    #[test]
+    fn should_guard_rule_on_state() {
+        let input = "break".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        let keyword = |input : &mut Parser<char>| input.guard(|loops : &usize| *loops > 0, |input| input.tag_str::<()>("break").map(|x| x.len()));
+
+        buffer.set_state(0usize);
+        assert!(keyword(&mut buffer).is_err());
+        assert_eq!(buffer.index(), 0);
+        *buffer.state_mut::<usize>().unwrap() += 1;
+        assert!(keyword(&mut buffer).is_ok());
+        assert!(buffer.end());
+    }
+
+    #[test]
+    fn should_fail_predicate_without_matching_state() {
+        let input = "a".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        assert!(buffer.pred_state::<Types, (), _>(|_, _| true).is_err());
+        buffer.set_state(0usize);
+        assert!(buffer.state_mut::<Types>().is_none());
+        *buffer.state_mut::<usize>().unwrap() += 1;
+        assert_eq!(buffer.state::<usize>(), Some(&1));
+    }
+}
@@ -0,0 +1,114 @@
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::panic::Location;
+use std::rc::Rc;
+
+use crate::Parser;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub tokens : usize,
+    pub backtracks : usize,
+    pub max_depth : usize,
+    pub alternatives : HashMap<&'static Location<'static>, Vec<usize>>,
+}
+
+pub(crate) type StatsLog = Rc<RefCell<Stats>>;
+
+impl<'a, T> Parser<'a, T> {
+    pub fn enable_stats(&mut self) {
+        if self.stats.is_none() {
+            self.stats = Some(Rc::new(RefCell::new(Stats::default())));
+        }
+    }
+
+    pub fn disable_stats(&mut self) {
+        self.stats = None;
+    }
+
+    pub fn stats(&self) -> Option<Stats> {
+        self.stats.as_ref().map(|x| x.borrow().clone())
+    }
+
+    pub(crate) fn count_token(&self) {
+        if let Some(stats) = &self.stats {
+            stats.borrow_mut().tokens += 1;
+        }
+    }
+
+    pub(crate) fn count_backtrack(&self) {
+        if let Some(stats) = &self.stats {
+            stats.borrow_mut().backtracks += 1;
+        }
+    }
+
+    pub(crate) fn count_depth(&self) {
+        if let Some(stats) = &self.stats {
+            let mut stats = stats.borrow_mut();
+            stats.max_depth = stats.max_depth.max(self.depth);
+        }
+    }
+
+    pub(crate) fn count_alternative(&self, location : &'static Location<'static>, branch : usize, branches : usize) {
+        if let Some(stats) = &self.stats {
+            let mut stats = stats.borrow_mut();
+            let counts = stats.alternatives.entry(location).or_insert_with(|| vec![0; branches]);
+            if let Some(count) = counts.get_mut(branch) {
+                *count += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::TError;
+
+    fn letter(input : &mut Parser<char>) -> Result<char, TError> {
+        input.satisfy(|c : &char| c.is_alphabetic()).copied()
+    }
+
+    fn digit(input : &mut Parser<char>) -> Result<char, TError> {
+        input.satisfy(|c : &char| c.is_ascii_digit()).copied()
+    }
+
+    fn group(input : &mut Parser<char>) -> Result<char, TError> {
+        let mut input = input.enter_rule()?;
+        input.expect(&'(')?;
+        let value = item(&mut input)?;
+        input.expect(&')')?;
+        Ok(value)
+    }
+
+    fn item(input : &mut Parser<char>) -> Result<char, TError> {
+        input.or([letter, digit, group])
+    }
+
+    #[test]
+    fn should_collect_parse_stats() {
+        let input = "a1((b))".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        buffer.enable_stats();
+
+        assert_eq!(buffer.list(item).map_err(|_| ()), Ok(vec!['a', '1', 'b']));
+
+        let stats = buffer.stats().unwrap();
+        assert_eq!(stats.tokens, 7);
+        assert_eq!(stats.max_depth, 2);
+        assert_eq!(stats.backtracks, 9);
+        assert_eq!(stats.alternatives.len(), 1);
+        assert_eq!(stats.alternatives.values().next(), Some(&vec![6, 4, 3]));
+    }
+
+    #[test]
+    fn should_not_collect_stats_by_default() {
+        let input = "a".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        item(&mut buffer).map_err(|_| ()).unwrap();
+
+        assert_eq!(buffer.stats(), None);
+    }
+}
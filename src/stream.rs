@@ -0,0 +1,228 @@
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use crate::{ErrorKind, JlnError, Parser, ParserBuilder};
+
+type Configure<T> = Box<dyn for<'b> Fn(ParserBuilder<'b, T>) -> ParserBuilder<'b, T>>;
+
+pub struct StreamBuffer<T> {
+    buffer : Vec<T>,
+    cursor : usize,
+    released : usize,
+    configure : Configure<T>,
+}
+
+impl<T> Default for StreamBuffer<T> {
+    fn default() -> Self {
+        StreamBuffer::new()
+    }
+}
+
+impl<T> StreamBuffer<T> {
+    pub fn new() -> Self {
+        StreamBuffer::with_config(|builder| builder)
+    }
+
+    pub fn with_config<C : for<'b> Fn(ParserBuilder<'b, T>) -> ParserBuilder<'b, T> + 'static>(configure : C) -> Self {
+        StreamBuffer { buffer: vec![], cursor: 0, released: 0, configure: Box::new(configure) }
+    }
+
+    pub fn feed<I : IntoIterator<Item = T>>(&mut self, tokens : I) {
+        self.buffer.extend(tokens);
+    }
+
+    pub fn buffered(&self) -> usize {
+        self.buffer.len()
+    }
+
+    pub fn position(&self) -> usize {
+        self.released + self.cursor
+    }
+
+    pub fn committed(&self) -> usize {
+        self.released
+    }
+
+    pub fn rewind(&mut self, position : usize) -> bool {
+        if position < self.released || position > self.position() {
+            return false;
+        }
+        self.cursor = position - self.released;
+        true
+    }
+
+    pub fn commit(&mut self) {
+        self.buffer.drain(..self.cursor);
+        self.released += self.cursor;
+        self.cursor = 0;
+    }
+
+    pub fn next<S, E : JlnError, F : FnOnce(&mut Parser<'_, T>) -> Result<S, E>>(&mut self, f : F) -> Result<Option<S>, E> {
+        self.run(f, true)
+    }
+
+    pub fn finish<S, E : JlnError, F : FnOnce(&mut Parser<'_, T>) -> Result<S, E>>(&mut self, f : F) -> Result<Option<S>, E> {
+        if self.cursor >= self.buffer.len() {
+            return Ok(None);
+        }
+        self.run(f, false)
+    }
+
+    fn run<S, E : JlnError, F : FnOnce(&mut Parser<'_, T>) -> Result<S, E>>(&mut self, f : F, streaming : bool) -> Result<Option<S>, E> {
+        let mut parser = (self.configure)(Parser::builder(&self.buffer[self.cursor..])).build();
+        parser.set_streaming(streaming);
+        match f(&mut parser) {
+            Ok(_) if parser.index() == 0 => Err(E::custom(Some("empty frame"), self.position())),
+            Ok(value) => {
+                self.cursor += parser.index();
+                Ok(Some(value))
+            },
+            Err(_) if parser.incomplete() => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl<'a, T> Parser<'a, T> {
+    pub fn set_streaming(&mut self, streaming : bool) {
+        self.streaming = streaming.then(|| Rc::new(Cell::new(false)));
+    }
+
+    pub fn streaming(&self) -> bool {
+        self.streaming.is_some()
+    }
+
+    pub fn incomplete(&self) -> bool {
+        self.streaming.as_ref().is_some_and(|x| x.get())
+    }
+
+    pub(crate) fn awaiting_input(&self) -> bool {
+        self.streaming.is_some() && self.limit.is_none()
+    }
+
+    pub(crate) fn exhausted<E : JlnError>(&self) -> E {
+        match &self.streaming {
+            Some(incomplete) if self.awaiting_input() => {
+                incomplete.set(true);
+                self.raise(ErrorKind::Incomplete, self.input.len())
+            },
+            _ => E::eof_at(self.next_significant()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::TError;
+
+    fn frame(input : &mut Parser<u8>) -> Result<Vec<u8>, TError> {
+        input.length_value(|input| input.get().copied(), |input| input.list(|input| input.get().copied()))
+    }
+
+    #[test]
+    fn should_report_incomplete_input() {
+        let input = vec![3, 1, 2];
+        let mut buffer = Parser::new(&input);
+        buffer.set_streaming(true);
+
+        let result = frame(&mut buffer);
+
+        assert!(result.unwrap_err().is_fatal());
+        assert!(buffer.incomplete());
+        assert!(!buffer.end());
+    }
+
+    #[test]
+    fn should_treat_bounded_end_as_real_end() {
+        let input = vec![2, 1, 2];
+        let mut buffer = Parser::new(&input);
+        buffer.set_streaming(true);
+
+        assert_eq!(frame(&mut buffer).map_err(|_| ()), Ok(vec![1, 2]));
+        assert!(!buffer.incomplete());
+    }
+
+    fn line(input : &mut Parser<u8>) -> Result<Vec<u8>, TError> {
+        let line = input.list(|input| input.satisfy(|b : &u8| *b != b'\n').copied())?;
+        input.expect(&b'\n')?;
+        Ok(line)
+    }
+
+    #[test]
+    fn should_release_committed_prefix() {
+        let mut buffer = StreamBuffer::new();
+        buffer.feed(b"ab\ncd".iter().copied());
+
+        assert_eq!(buffer.next(line).map_err(|_| ()), Ok(Some(b"ab".to_vec())));
+        assert_eq!(buffer.next(line).map_err(|_| ()), Ok(None));
+        buffer.commit();
+        assert_eq!(buffer.buffered(), 2);
+        assert_eq!(buffer.committed(), 3);
+
+        buffer.feed(b"\n".iter().copied());
+        assert_eq!(buffer.next(line).map_err(|_| ()), Ok(Some(b"cd".to_vec())));
+        buffer.commit();
+        assert_eq!(buffer.buffered(), 0);
+        assert_eq!(buffer.position(), 6);
+    }
+
+    #[test]
+    fn should_rewind_only_after_commit_point() {
+        let mut buffer = StreamBuffer::new();
+        buffer.feed(b"a\nb\nc".iter().copied());
+
+        buffer.next(line).unwrap();
+        buffer.commit();
+        buffer.next(line).unwrap();
+
+        assert!(!buffer.rewind(0));
+        assert!(buffer.rewind(2));
+        assert_eq!(buffer.next(line).map_err(|_| ()), Ok(Some(b"b".to_vec())));
+        assert_eq!(buffer.finish(line).map_err(|_| ()), Err(()));
+    }
+
+    #[test]
+    fn should_see_end_of_input_without_streaming() {
+        let input = vec![3, 1, 2];
+        let mut buffer = Parser::new(&input);
+
+        assert!(frame(&mut buffer).is_err());
+        assert!(!buffer.incomplete());
+        assert!(!buffer.streaming());
+    }
+
+    #[test]
+    fn should_reject_items_that_consume_nothing() {
+        let mut buffer = StreamBuffer::new();
+        buffer.feed(b"ab".iter().copied());
+
+        assert!(buffer.next(|input| input.list(|input| input.expect::<TError>(&b'x').copied())).is_err());
+        assert_eq!(buffer.position(), 0);
+    }
+
+    #[test]
+    fn should_stop_alternatives_at_incomplete_input() {
+        let input = vec![b'a'];
+        let mut buffer = Parser::new(&input);
+        buffer.set_streaming(true);
+
+        let result = buffer.or([
+            |input : &mut Parser<u8>| input.with_rollback(|input| input.tag::<()>(b"ab").map(|_| 0)),
+            |input : &mut Parser<u8>| input.get::<()>().map(|_| 1),
+        ]);
+
+        assert_eq!(result, Err(()));
+        assert!(buffer.incomplete());
+        assert_eq!(buffer.index(), 0);
+    }
+
+    #[test]
+    fn should_keep_builder_settings_for_each_item() {
+        let mut buffer = StreamBuffer::with_config(|builder| builder.trivia(|b : &u8| *b == b' '));
+        buffer.feed(b" a b".iter().copied());
+
+        assert_eq!(buffer.next(|input| Ok::<_, TError>((*input.get()?, *input.get()?))).map_err(|_| ()), Ok(Some((b'a', b'b'))));
+    }
+}
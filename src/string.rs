@@ -0,0 +1,230 @@
+
+use crate::{ErrorKind, JlnError, Parser, Span, TextToken};
+
+#[derive(Debug, Clone)]
+pub struct StringStyle {
+    quote : char,
+    escape : Option<char>,
+    escapes : Vec<(char, char)>,
+    hex : bool,
+    unicode : bool,
+    utf16 : bool,
+}
+
+impl Default for StringStyle {
+    fn default() -> Self {
+        StringStyle::new('"')
+    }
+}
+
+impl StringStyle {
+    pub fn new(quote : char) -> Self {
+        let escapes = vec![('n', '\n'), ('r', '\r'), ('t', '\t'), ('0', '\0'), ('\\', '\\'), ('"', '"'), ('\'', '\'')];
+        StringStyle { quote, escape: Some('\\'), escapes, hex: true, unicode: true, utf16: false }
+    }
+
+    pub fn raw(quote : char) -> Self {
+        StringStyle { quote, escape: None, escapes: vec![], hex: false, unicode: false, utf16: false }
+    }
+
+    pub fn json() -> Self {
+        let escapes = vec![('n', '\n'), ('r', '\r'), ('t', '\t'), ('b', '\u{8}'), ('f', '\u{c}'), ('\\', '\\'), ('"', '"'), ('/', '/')];
+        StringStyle { quote: '"', escape: Some('\\'), escapes, hex: false, unicode: false, utf16: true }
+    }
+
+    pub fn set_escape(&mut self, escape : Option<char>) {
+        self.escape = escape;
+    }
+
+    pub fn insert_escape(&mut self, code : char, value : char) {
+        self.remove_escape(code);
+        self.escapes.push((code, value));
+    }
+
+    pub fn remove_escape(&mut self, code : char) {
+        self.escapes.retain(|(c, _)| *c != code);
+    }
+
+    pub fn set_hex(&mut self, hex : bool) {
+        self.hex = hex;
+    }
+
+    pub fn set_unicode(&mut self, unicode : bool) {
+        self.unicode = unicode;
+    }
+
+    pub fn set_utf16(&mut self, utf16 : bool) {
+        self.utf16 = utf16;
+    }
+}
+
+impl<'a, T : TextToken> Parser<'a, T> {
+    pub fn string_literal<E : JlnError>(&mut self, style : &StringStyle) -> Result<(String, Span), E> {
+        self.with_rollback(|input| {
+            input.consume_trivia();
+            let start = input.index;
+            input.no_trivia(|input| {
+                input.satisfy::<E, _>(|t| t.to_char() == style.quote)?;
+                let mut value = String::new();
+                loop {
+                    let c = input.string_char(start)?;
+                    if c == style.quote {
+                        break;
+                    }
+                    else if Some(c) == style.escape {
+                        value.push(input.escape(style, start)?);
+                    }
+                    else {
+                        value.push(c);
+                    }
+                }
+                Ok((value, Span::new(start, input.index)))
+            })
+        })
+    }
+
+    fn string_char<E : JlnError>(&mut self, start : usize) -> Result<char, E> {
+        if self.end() {
+            return Err(self.raise(ErrorKind::Unterminated, start));
+        }
+        T::decode(self)
+    }
+
+    fn escape<E : JlnError>(&mut self, style : &StringStyle, start : usize) -> Result<char, E> {
+        let at = self.index - 1;
+        let invalid = || E::from_kind(ErrorKind::InvalidEscape, at);
+        match self.string_char(start)? {
+            'x' if style.hex => {
+                let mut code = 0;
+                for _ in 0..2 {
+                    code = code * 16 + self.string_char(start)?.to_digit(16).ok_or_else(invalid)?;
+                }
+                char::from_u32(code).ok_or_else(invalid)
+            },
+            'u' if style.utf16 => {
+                let high = self.code_unit(start, at)?;
+                if !(0xD800..0xDC00).contains(&high) {
+                    return char::from_u32(high).ok_or_else(invalid);
+                }
+                if self.string_char(start)? != '\\' || self.string_char(start)? != 'u' {
+                    return Err(invalid());
+                }
+                let low = self.code_unit(start, at)?;
+                if !(0xDC00..0xE000).contains(&low) {
+                    return Err(invalid());
+                }
+                char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)).ok_or_else(invalid)
+            },
+            'u' if style.unicode => {
+                if self.string_char(start)? != '{' {
+                    return Err(invalid());
+                }
+                let mut code = 0u32;
+                let mut digits = 0;
+                loop {
+                    match self.string_char(start)? {
+                        '}' if digits > 0 => { break; },
+                        c => {
+                            let digit = c.to_digit(16).ok_or_else(invalid)?;
+                            digits += 1;
+                            if digits > 6 {
+                                return Err(invalid());
+                            }
+                            code = code * 16 + digit;
+                        },
+                    }
+                }
+                char::from_u32(code).ok_or_else(invalid)
+            },
+            c => style.escapes.iter().find(|(code, _)| *code == c).map(|(_, value)| *value).ok_or_else(invalid),
+        }
+    }
+
+    fn code_unit<E : JlnError>(&mut self, start : usize, at : usize) -> Result<u32, E> {
+        let mut code = 0;
+        for _ in 0..4 {
+            code = code * 16 + self.string_char(start)?.to_digit(16).ok_or_else(|| E::from_kind(ErrorKind::InvalidEscape, at))?;
+        }
+        Ok(code)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct KindError(Option<ErrorKind>, usize);
+
+    impl JlnError for KindError {
+        fn is_fatal(&self) -> bool { self.0.as_ref().is_some_and(|x| x.is_fatal()) }
+        fn eof() -> Self { KindError(None, 0) }
+        fn aggregate(_errors : Vec<Self>) -> Self { KindError(None, 0) }
+        fn from_kind(kind : ErrorKind, index : usize) -> Self { KindError(Some(kind), index) }
+    }
+
+    #[test]
+    fn should_decode_escapes() {
+        let input = r#""a\n\"\x41\u{1F600}" x"#.chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        let (value, span) = buffer.string_literal::<()>(&StringStyle::default()).unwrap();
+
+        assert_eq!(value, "a\n\"A\u{1F600}");
+        assert_eq!(span, Span::new(0, 20));
+        assert_eq!(buffer.index(), 20);
+    }
+
+    #[test]
+    fn should_parse_bytes_with_custom_quote() {
+        let input = br"'it''s' rest".to_vec();
+        let mut buffer = Parser::new(&input);
+        let style = StringStyle::raw('\'');
+
+        assert_eq!(buffer.string_literal::<()>(&style), Ok((String::from("it"), Span::new(0, 4))));
+    }
+
+    #[test]
+    fn should_decode_utf8_strings_on_bytes() {
+        let input = "\"héllo\" x".as_bytes().to_vec();
+        let mut buffer = Parser::new(&input);
+
+        assert_eq!(buffer.string_literal::<()>(&StringStyle::default()), Ok((String::from("héllo"), Span::new(0, 8))));
+        assert_eq!(Parser::new(&b"\"\xff\""[..]).string_literal::<KindError>(&StringStyle::default()), Err(KindError(Some(ErrorKind::Custom { label: Some("utf8") }), 1)));
+    }
+
+    #[test]
+    fn should_report_unterminated_string_as_fatal() {
+        let input = r#""abc\"#.chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        let result = buffer.string_literal::<KindError>(&StringStyle::default());
+
+        assert_eq!(result, Err(KindError(Some(ErrorKind::Unterminated), 0)));
+        assert!(result.unwrap_err().is_fatal());
+        assert_eq!(buffer.index(), 0);
+    }
+
+    #[test]
+    fn should_decode_json_escapes() {
+        let input = r#""\u0041\/\ud83d\ude00\b" "\ud83d""#.chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        let style = StringStyle::json();
+
+        assert_eq!(buffer.string_literal::<KindError>(&style).map(|x| x.0), Ok(String::from("A/\u{1F600}\u{8}")));
+        buffer.get::<KindError>().unwrap();
+        assert_eq!(buffer.string_literal::<KindError>(&style), Err(KindError(Some(ErrorKind::InvalidEscape), 26)));
+    }
+
+    #[test]
+    fn should_report_invalid_escape() {
+        let input = r#""a\q""#.chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        assert_eq!(buffer.string_literal::<KindError>(&StringStyle::default()), Err(KindError(Some(ErrorKind::InvalidEscape), 2)));
+
+        let mut style = StringStyle::default();
+        style.insert_escape('q', '?');
+        assert_eq!(buffer.string_literal::<KindError>(&style).map(|x| x.0), Ok(String::from("a?")));
+    }
+}
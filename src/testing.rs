@@ -0,0 +1,83 @@
+
+use std::fmt::Debug;
+use std::ops::Range;
+
+use proptest::collection::vec;
+use proptest::prelude::*;
+use proptest::sample::select;
+use proptest::test_runner::TestCaseError;
+
+use crate::{JlnError, Parser};
+
+pub fn tokens<S : Strategy>(token : S, len : Range<usize>) -> impl Strategy<Value = Vec<S::Value>> {
+    vec(token, len)
+}
+
+pub fn fragments<T : Clone + Debug + 'static>(fragments : Vec<Vec<T>>, len : Range<usize>) -> impl Strategy<Value = Vec<T>> {
+    vec(select(fragments), len).prop_map(|x| x.concat())
+}
+
+pub fn roundtrip<T, S, E, F, R>(input : &[T], mut parse : F, mut render : R) -> Result<S, TestCaseError>
+    where S : PartialEq + Debug,
+          E : JlnError + Debug,
+          F : FnMut(&mut Parser<'_, T>) -> Result<S, E>,
+          R : FnMut(&S) -> Vec<T>,
+{
+    let first = complete(input, &mut parse)?;
+    let rendered = render(&first);
+    let second = complete(&rendered, &mut parse)?;
+    if first != second {
+        return Err(TestCaseError::fail(format!("roundtrip changed value: {:?} became {:?}", first, second)));
+    }
+    Ok(first)
+}
+
+fn complete<T, S, E : JlnError + Debug, F : FnMut(&mut Parser<'_, T>) -> Result<S, E>>(input : &[T], parse : &mut F) -> Result<S, TestCaseError> {
+    let mut parser = Parser::new(input);
+    let value = parse(&mut parser).map_err(|e| TestCaseError::fail(format!("parse failed: {:?}", e)))?;
+    if !parser.end() {
+        return Err(TestCaseError::fail(format!("input not fully consumed: stopped at {} of {}", parser.index(), input.len())));
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn list(input : &mut Parser<char>) -> Result<Vec<u32>, ()> {
+        let mut items = vec![input.integer()?];
+        items.extend(input.list(|input| {
+            input.expect::<()>(&',')?;
+            input.integer::<u32, ()>()
+        })?);
+        Ok(items)
+    }
+
+    fn render(items : &[u32]) -> Vec<char> {
+        items.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(",").chars().collect()
+    }
+
+    proptest! {
+        #[test]
+        fn should_roundtrip_generated_lists(input in fragments(vec![vec!['1'], vec!['0', '7'], vec!['4', '2']], 1..5)) {
+            let mut text = input.clone();
+            text.push(',');
+            text.push('9');
+            roundtrip(&text, list, |x| render(x))?;
+        }
+
+        #[test]
+        fn should_generate_token_streams(input in tokens(0u8..3, 0..8)) {
+            prop_assert!(input.len() < 8);
+            prop_assert!(input.iter().all(|x| *x < 3));
+        }
+    }
+
+    #[test]
+    fn should_report_unconsumed_input() {
+        let text = "1,2;".chars().collect::<Vec<_>>();
+
+        assert!(roundtrip(&text, list, |x| render(x)).is_err());
+    }
+}
@@ -0,0 +1,147 @@
+
+use std::fmt::Display;
+
+use crate::{DecodeMode, ErrorKind, JlnError, Parser};
+
+pub trait TextToken : Copy + PartialEq {
+    fn to_char(self) -> char;
+    fn eq_no_case(self, other : Self) -> bool;
+    fn units(text : &str) -> impl Iterator<Item = Self> + '_;
+    fn decode<E : JlnError>(input : &mut Parser<'_, Self>) -> Result<char, E>;
+}
+
+impl TextToken for char {
+    fn to_char(self) -> char {
+        self
+    }
+
+    fn eq_no_case(self, other : char) -> bool {
+        self == other || self.to_lowercase().eq(other.to_lowercase())
+    }
+
+    fn units(text : &str) -> impl Iterator<Item = char> + '_ {
+        text.chars()
+    }
+
+    fn decode<E : JlnError>(input : &mut Parser<'_, char>) -> Result<char, E> {
+        input.get().copied()
+    }
+}
+
+impl TextToken for u8 {
+    fn to_char(self) -> char {
+        self as char
+    }
+
+    fn eq_no_case(self, other : u8) -> bool {
+        self.eq_ignore_ascii_case(&other)
+    }
+
+    fn units(text : &str) -> impl Iterator<Item = u8> + '_ {
+        text.bytes()
+    }
+
+    fn decode<E : JlnError>(input : &mut Parser<'_, u8>) -> Result<char, E> {
+        input.utf8_char(DecodeMode::Strict)
+    }
+}
+
+fn expected<E : JlnError, D : Display>(error : E, text : D) -> E {
+    if error.is_fatal() {
+        error
+    }
+    else {
+        error.expected(&format!("`{}`", text))
+    }
+}
+
+impl<'a, T : TextToken> Parser<'a, T> {
+    pub fn expect_no_case<E : JlnError>(&mut self, expected : char) -> Result<T, E> {
+        let mut buffer = [0; 4];
+        let mut units = T::units(expected.encode_utf8(&mut buffer));
+        let unit = units.next().filter(|_| units.next().is_none());
+        match self.peek::<E>() {
+            Ok(t) if unit.is_some_and(|x| t.eq_no_case(x)) => self.get().copied(),
+            Ok(_) => Err(self::expected(E::from_kind(ErrorKind::Unexpected, self.next_significant()), expected)),
+            Err(e) => Err(self::expected(e, expected)),
+        }
+    }
+
+    pub fn tag_str<E : JlnError>(&mut self, tag : &str) -> Result<&[T], E> {
+        self.text_tag(tag, |a, b| a == b)
+    }
+
+    pub fn tag_no_case<E : JlnError>(&mut self, tag : &str) -> Result<&[T], E> {
+        self.text_tag(tag, T::eq_no_case)
+    }
+
+    fn text_tag<E : JlnError>(&mut self, tag : &str, eq : fn(T, T) -> bool) -> Result<&[T], E> {
+        let start = self.next_significant();
+        self.with_rollback(|input| {
+            input.consume_trivia();
+            input.no_trivia(|input| {
+                for expected in T::units(tag) {
+                    input.satisfy(|t : &T| eq(*t, expected))?;
+                }
+                Ok(())
+            })
+        }).map_err(|e| expected(e, tag))?;
+        Ok(&self.input[start..self.index])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_match_tag_without_case_for_chars() {
+        let input = "SeLeCt *".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        assert!(buffer.tag_no_case::<()>("insert").is_err());
+        assert_eq!(buffer.index(), 0);
+        assert_eq!(buffer.tag_no_case::<()>("select"), Ok(&input[..6]));
+        assert_eq!(buffer.index(), 6);
+    }
+
+    #[test]
+    fn should_match_tag_without_case_for_bytes() {
+        let input = b"Content-Length: 5".to_vec();
+        let mut buffer = Parser::new(&input);
+
+        assert_eq!(buffer.tag_no_case::<()>("content-length"), Ok(&b"Content-Length"[..]));
+        assert_eq!(buffer.expect_no_case::<()>(':'), Ok(b':'));
+    }
+
+    #[test]
+    fn should_expect_without_case() {
+        let input = "Xy".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        assert_eq!(buffer.expect_no_case::<()>('x'), Ok('X'));
+        assert!(buffer.expect_no_case::<()>('z').is_err());
+        assert_eq!(buffer.expect_no_case::<()>('Y'), Ok('y'));
+    }
+
+    #[test]
+    fn should_match_utf8_tags_on_bytes() {
+        let input = "héllo".as_bytes().to_vec();
+        let mut buffer = Parser::new(&input);
+
+        assert_eq!(buffer.tag_str::<()>("hé"), Ok("hé".as_bytes()));
+        assert!(Parser::new(&[0xC9]).tag_no_case::<()>("é").is_err());
+        assert!(Parser::new("É".as_bytes()).tag_no_case::<()>("é").is_err());
+        assert_eq!(Parser::new("LLé".as_bytes()).tag_no_case::<()>("llé"), Ok("LLé".as_bytes()));
+        assert!(Parser::new(&[0xC9]).expect_no_case::<()>('é').is_err());
+    }
+
+    #[test]
+    fn should_match_tag_str_with_case() {
+        let input = "Ab".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        assert!(buffer.tag_str::<()>("ab").is_err());
+        assert!(buffer.tag_str::<()>("Ab").is_ok());
+    }
+}
@@ -0,0 +1,51 @@
+
+use crate::{Parser, Stop};
+
+pub struct Tokens<'p, 'a, T> {
+    input : &'p mut Parser<'a, T>,
+}
+
+impl<'p, 'a, T : Clone> Iterator for Tokens<'p, 'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.input.get::<Stop>().ok().cloned()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.input.bound().saturating_sub(self.input.index)))
+    }
+}
+
+impl<'a, T> Parser<'a, T> {
+    pub fn tokens(&mut self) -> Tokens<'_, 'a, T> {
+        Tokens { input: self }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_drain_remaining_tokens() {
+        let input = vec![1, 2, 3, 4];
+        let mut buffer = Parser::new(&input);
+        buffer.get::<()>().unwrap();
+
+        let rest = buffer.tokens().collect::<Vec<_>>();
+
+        assert_eq!(rest, vec![2, 3, 4]);
+        assert!(buffer.end());
+    }
+
+    #[test]
+    fn should_resume_parsing_after_partial_iteration() {
+        let input = " a b c".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        buffer.set_trivia(Some(|c| *c == ' '));
+
+        assert_eq!(buffer.tokens().take(2).collect::<String>(), "ab");
+        assert_eq!(buffer.expect::<()>(&'c'), Ok(&'c'));
+    }
+}
@@ -0,0 +1,250 @@
+
+use std::cell::RefCell;
+use std::fmt::{self, Display, Formatter};
+use std::rc::Rc;
+
+use crate::Parser;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+    Branch { index : usize, branch : usize },
+    Rollback { from : usize, to : usize },
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Trace {
+    pub decisions : Vec<Decision>,
+}
+
+impl Trace {
+    pub fn decode(text : &str) -> Option<Trace> {
+        let decisions = text.split_whitespace().map(|entry| {
+            let (kind, rest) = entry.split_at_checked(1)?;
+            let (a, b) = rest.split_once('.')?;
+            let (a, b) = (a.parse().ok()?, b.parse().ok()?);
+            match kind {
+                "b" => Some(Decision::Branch { index: a, branch: b }),
+                "r" => Some(Decision::Rollback { from: a, to: b }),
+                _ => None,
+            }
+        }).collect::<Option<Vec<_>>>()?;
+        Some(Trace { decisions })
+    }
+}
+
+impl Display for Trace {
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result {
+        for (i, decision) in self.decisions.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            match decision {
+                Decision::Branch { index, branch } => write!(f, "b{}.{}", index, branch)?,
+                Decision::Rollback { from, to } => write!(f, "r{}.{}", from, to)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+enum Mode {
+    Record,
+    Replay { position : usize, suspended : usize, diverged : Option<usize> },
+}
+
+pub(crate) struct Tracer {
+    decisions : Vec<Decision>,
+    mode : Mode,
+}
+
+pub(crate) type TraceLog = Rc<RefCell<Tracer>>;
+
+pub(crate) enum TraceSlot {
+    Untraced,
+    Recorded(usize),
+    Suspended,
+}
+
+impl<'a, T> Parser<'a, T> {
+    pub fn record_trace(&mut self) {
+        self.trace = Some(Rc::new(RefCell::new(Tracer { decisions: vec![], mode: Mode::Record })));
+    }
+
+    pub fn replay_trace(&mut self, trace : Trace) {
+        let mode = Mode::Replay { position: 0, suspended: 0, diverged: None };
+        self.trace = Some(Rc::new(RefCell::new(Tracer { decisions: trace.decisions, mode })));
+    }
+
+    pub fn clear_trace(&mut self) {
+        self.trace = None;
+    }
+
+    pub fn trace(&self) -> Option<Trace> {
+        match &self.trace {
+            Some(tracer) if matches!(tracer.borrow().mode, Mode::Record) => Some(Trace { decisions: tracer.borrow().decisions.clone() }),
+            _ => None,
+        }
+    }
+
+    pub fn replay_divergence(&self) -> Option<usize> {
+        match &self.trace {
+            Some(tracer) => match tracer.borrow().mode {
+                Mode::Replay { diverged, .. } => diverged,
+                Mode::Record => None,
+            },
+            None => None,
+        }
+    }
+
+    pub(crate) fn trace_mark(&self) -> Option<usize> {
+        match &self.trace {
+            Some(tracer) if matches!(tracer.borrow().mode, Mode::Record) => Some(tracer.borrow().decisions.len()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn trace_rollback(&self, mark : Option<usize>, from : usize, to : usize) {
+        let Some(tracer) = &self.trace else { return; };
+        let mut tracer = tracer.borrow_mut();
+        let Tracer { decisions, mode } = &mut *tracer;
+        match mode {
+            Mode::Record => {
+                decisions.truncate(mark.unwrap_or(decisions.len()));
+                decisions.push(Decision::Rollback { from, to });
+            },
+            Mode::Replay { position, suspended : 0, diverged : diverged @ None } => {
+                if decisions.get(*position) == Some(&Decision::Rollback { from, to }) {
+                    *position += 1;
+                }
+                else {
+                    *diverged = Some(*position);
+                }
+            },
+            Mode::Replay { .. } => { },
+        }
+    }
+
+    pub(crate) fn replay_branch(&self, branches : usize) -> Option<usize> {
+        let tracer = self.trace.as_ref()?;
+        let mut tracer = tracer.borrow_mut();
+        let Tracer { decisions, mode } = &mut *tracer;
+        let Mode::Replay { position, suspended : 0, diverged : diverged @ None } = mode else { return None; };
+        match decisions.get(*position) {
+            Some(Decision::Rollback { .. }) => None,
+            Some(Decision::Branch { index, branch }) if *index == self.index && *branch < branches
+                && decisions[*position + 1..].iter().take(*branch).filter(|x| matches!(x, Decision::Rollback { .. })).count() == *branch => {
+                let branch = *branch;
+                *position += 1 + branch;
+                Some(branch)
+            },
+            _ => {
+                *diverged = Some(*position);
+                None
+            },
+        }
+    }
+
+    pub(crate) fn trace_branch_start(&self) -> TraceSlot {
+        let Some(tracer) = &self.trace else { return TraceSlot::Untraced; };
+        let mut tracer = tracer.borrow_mut();
+        let Tracer { decisions, mode } = &mut *tracer;
+        match mode {
+            Mode::Record => {
+                decisions.push(Decision::Branch { index: self.index, branch: usize::MAX });
+                TraceSlot::Recorded(decisions.len() - 1)
+            },
+            Mode::Replay { position, suspended, diverged : None } if *suspended > 0 || matches!(decisions.get(*position), Some(Decision::Rollback { .. })) => {
+                *suspended += 1;
+                TraceSlot::Suspended
+            },
+            Mode::Replay { .. } => TraceSlot::Untraced,
+        }
+    }
+
+    pub(crate) fn trace_branch_end(&self, slot : TraceSlot, branch : Option<usize>) {
+        let Some(tracer) = &self.trace else { return; };
+        let mut tracer = tracer.borrow_mut();
+        let Tracer { decisions, mode } = &mut *tracer;
+        match (slot, mode) {
+            (TraceSlot::Recorded(slot), Mode::Record) => match branch {
+                Some(branch) => {
+                    if let Some(Decision::Branch { branch : entry, .. }) = decisions.get_mut(slot) {
+                        *entry = branch;
+                    }
+                },
+                None => { decisions.truncate(slot); },
+            },
+            (TraceSlot::Suspended, Mode::Replay { suspended, .. }) => { *suspended -= 1; },
+            _ => { },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::test::TError;
+
+    thread_local! {
+        static CALLS : Cell<usize> = const { Cell::new(0) };
+    }
+
+    fn digit(input : &mut Parser<char>) -> Result<char, TError> {
+        CALLS.with(|x| x.set(x.get() + 1));
+        input.satisfy(|c : &char| c.is_ascii_digit()).copied()
+    }
+
+    fn letter(input : &mut Parser<char>) -> Result<char, TError> {
+        CALLS.with(|x| x.set(x.get() + 1));
+        input.satisfy(|c : &char| c.is_alphabetic()).copied()
+    }
+
+    fn pair(input : &mut Parser<char>) -> Result<char, TError> {
+        input.expect(&'(')?;
+        let value = input.or([digit, letter])?;
+        input.expect(&')')?;
+        Ok(value)
+    }
+
+    fn item(input : &mut Parser<char>) -> Result<char, TError> {
+        input.or([digit, pair, letter])
+    }
+
+    #[test]
+    fn should_record_and_replay_decisions() {
+        let input = "1(a)b".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        buffer.record_trace();
+        assert_eq!(buffer.list(item).map_err(|_| ()), Ok(vec!['1', 'a', 'b']));
+        let trace = buffer.trace().unwrap();
+
+        assert_eq!(trace.to_string(), "b0.0 b1.1 r1.1 b2.1 r2.2 b4.2 r4.4 r4.4 r5.5");
+        assert_eq!(Trace::decode(&trace.to_string()), Some(trace.clone()));
+
+        CALLS.with(|x| x.set(0));
+        let mut replay = Parser::new(&input);
+        replay.replay_trace(trace);
+        assert_eq!(replay.list(item).map_err(|_| ()), Ok(vec!['1', 'a', 'b']));
+        assert_eq!(replay.replay_divergence(), None);
+        assert_eq!(CALLS.with(|x| x.get()), 5);
+    }
+
+    #[test]
+    fn should_report_divergence_and_fall_back() {
+        let input = "1(a)b".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        buffer.record_trace();
+        buffer.list(item).map_err(|_| ()).unwrap();
+        let trace = buffer.trace().unwrap();
+
+        let other = "1(2)b".chars().collect::<Vec<_>>();
+        let mut replay = Parser::new(&other);
+        replay.replay_trace(trace);
+
+        assert_eq!(replay.list(item).map_err(|_| ()), Ok(vec!['1', '2', 'b']));
+        assert_eq!(replay.replay_divergence(), Some(5));
+        assert_eq!(Trace::decode("b1.x"), None);
+    }
+}
@@ -0,0 +1,105 @@
+
+use std::ops::{Deref, DerefMut};
+
+use crate::{Checkpoint, Parser};
+
+pub struct Transaction<'p, 'a, T> {
+    parser : &'p mut Parser<'a, T>,
+    saved : Option<Checkpoint<T>>,
+    mark : Option<usize>,
+}
+
+impl<'p, 'a, T> Transaction<'p, 'a, T> {
+    pub fn commit(mut self) {
+        self.saved = None;
+        self.parser.settle(self.mark, true);
+    }
+}
+
+impl<'p, 'a, T> Deref for Transaction<'p, 'a, T> {
+    type Target = Parser<'a, T>;
+
+    fn deref(&self) -> &Self::Target {
+        self.parser
+    }
+}
+
+impl<'p, 'a, T> DerefMut for Transaction<'p, 'a, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.parser
+    }
+}
+
+impl<'p, 'a, T> Drop for Transaction<'p, 'a, T> {
+    fn drop(&mut self) {
+        if let Some(saved) = self.saved.take() {
+            self.parser.restore(saved);
+            self.parser.settle(self.mark, false);
+        }
+    }
+}
+
+impl<'a, T> Parser<'a, T> {
+    pub fn transaction(&mut self) -> Transaction<'_, 'a, T> {
+        let saved = self.checkpoint();
+        let mark = self.speculate();
+        Transaction { parser: self, saved: Some(saved), mark }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use crate::Event;
+
+    use super::*;
+
+    #[test]
+    fn should_roll_back_on_drop() {
+        let input = vec![1, 2, 3];
+        let mut buffer = Parser::new(&input);
+
+        {
+            let mut tx = buffer.transaction();
+            tx.get::<()>().unwrap();
+            tx.get::<()>().unwrap();
+            assert_eq!(tx.index(), 2);
+        }
+
+        assert_eq!(buffer.index(), 0);
+    }
+
+    #[test]
+    fn should_keep_progress_on_commit() {
+        let input = vec![1, 2, 3];
+        let mut buffer = Parser::new(&input);
+        let events = Rc::new(RefCell::new(vec![]));
+        let sink = Rc::clone(&events);
+        buffer.set_event_sink(move |event| sink.borrow_mut().push(event));
+
+        let mut tx = buffer.transaction();
+        tx.get::<()>().unwrap();
+        assert_eq!(tx.peek::<()>(), Ok(&2));
+        tx.commit();
+
+        assert_eq!(buffer.index(), 1);
+        assert_eq!(*events.borrow(), vec![Event::Token { index: 0 }]);
+    }
+
+    #[test]
+    fn should_discard_events_on_abort() {
+        let input = vec![1];
+        let mut buffer = Parser::new(&input);
+        let events = Rc::new(RefCell::new(vec![]));
+        let sink = Rc::clone(&events);
+        buffer.set_event_sink(move |event| sink.borrow_mut().push(event));
+
+        let mut tx = buffer.transaction();
+        tx.get::<()>().unwrap();
+        drop(tx);
+
+        assert!(events.borrow().is_empty());
+    }
+}
@@ -0,0 +1,86 @@
+
+use crate::{ErrorKind, JlnError, Parser};
+
+impl<'a, T> Parser<'a, T> {
+    pub fn set_trivia(&mut self, trivia : Option<fn(&T) -> bool>) {
+        self.trivia = trivia;
+    }
+
+    pub fn no_trivia<S, E : JlnError, F : FnOnce(&mut Parser<'a, T>) -> Result<S, E>>(&mut self, f : F) -> Result<S, E> {
+        let previous = self.skip_trivia;
+        self.skip_trivia = false;
+        let result = f(self);
+        self.skip_trivia = previous;
+        result
+    }
+
+    pub fn satisfy<E : JlnError, P : FnOnce(&T) -> bool>(&mut self, pred : P) -> Result<&T, E> {
+        match self.peek::<E>()? {
+            t if pred(t) => self.get(),
+            _ => Err(E::from_kind(ErrorKind::Unexpected, self.next_significant())),
+        }
+    }
+
+    pub(crate) fn next_significant(&self) -> usize {
+        match self.trivia {
+            Some(trivia) if self.skip_trivia => {
+                self.index + self.input[self.index.min(self.bound())..self.bound()].iter().take_while(|x| trivia(x)).count()
+            },
+            _ => self.index,
+        }
+    }
+
+    pub(crate) fn consume_trivia(&mut self) {
+        let target = self.next_significant();
+        while self.index < target {
+            self.advance();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn whitespace(c : &char) -> bool {
+        c.is_whitespace()
+    }
+
+    #[test]
+    fn should_skip_trivia_before_get_and_expect() {
+        let input = " a  b ".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        buffer.set_trivia(Some(whitespace));
+
+        assert_eq!(buffer.peek::<()>(), Ok(&'a'));
+        assert_eq!(buffer.get::<()>(), Ok(&'a'));
+        assert_eq!(buffer.expect::<()>(&'b'), Ok(&'b'));
+        assert_eq!(buffer.index(), 5);
+        assert!(buffer.end());
+    }
+
+    #[test]
+    fn should_satisfy_predicate() {
+        let input = " 1x".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        buffer.set_trivia(Some(whitespace));
+
+        assert_eq!(buffer.satisfy::<(), _>(|c| c.is_ascii_digit()), Ok(&'1'));
+        assert!(buffer.satisfy::<(), _>(|c| c.is_ascii_digit()).is_err());
+        assert_eq!(buffer.index(), 2);
+    }
+
+    #[test]
+    fn should_disable_trivia_temporarily() {
+        let input = "\" a \"".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        buffer.set_trivia(Some(whitespace));
+
+        buffer.expect::<()>(&'"').unwrap();
+        let body = buffer.no_trivia(|buffer| buffer.list(|buffer| buffer.satisfy::<(), _>(|c| *c != '"').copied())).unwrap();
+        buffer.expect::<()>(&'"').unwrap();
+
+        assert_eq!(body, vec![' ', 'a', ' ']);
+        assert!(buffer.end());
+    }
+}
@@ -0,0 +1,69 @@
+
+use unicode_properties::{GeneralCategory, GeneralCategoryGroup, UnicodeGeneralCategory};
+
+use crate::{JlnError, Parser};
+
+pub fn xid_start(c : char) -> bool {
+    unicode_ident::is_xid_start(c)
+}
+
+pub fn xid_continue(c : char) -> bool {
+    unicode_ident::is_xid_continue(c)
+}
+
+impl<'a> Parser<'a, char> {
+    pub fn category<E : JlnError>(&mut self, category : GeneralCategory) -> Result<char, E> {
+        self.satisfy(|c : &char| c.general_category() == category).copied()
+    }
+
+    pub fn category_group<E : JlnError>(&mut self, group : GeneralCategoryGroup) -> Result<char, E> {
+        self.satisfy(|c : &char| c.general_category_group() == group).copied()
+    }
+
+    pub fn xid_start<E : JlnError>(&mut self) -> Result<char, E> {
+        self.satisfy(|c : &char| xid_start(*c)).copied()
+    }
+
+    pub fn xid_continue<E : JlnError>(&mut self) -> Result<char, E> {
+        self.satisfy(|c : &char| xid_continue(*c)).copied()
+    }
+
+    pub fn whitespace<E : JlnError>(&mut self) -> Result<char, E> {
+        self.satisfy(|c : &char| c.is_whitespace()).copied()
+    }
+
+    pub fn numeric<E : JlnError>(&mut self) -> Result<char, E> {
+        self.satisfy(|c : &char| c.is_numeric()).copied()
+    }
+
+    pub fn unicode_identifier<E : JlnError>(&mut self) -> Result<String, E> {
+        self.identifier(|c| c == '_' || xid_start(c), xid_continue)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_match_general_categories() {
+        let input = "Aé1½".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        assert_eq!(buffer.category::<()>(GeneralCategory::UppercaseLetter), Ok('A'));
+        assert!(buffer.category::<()>(GeneralCategory::UppercaseLetter).is_err());
+        assert_eq!(buffer.category_group::<()>(GeneralCategoryGroup::Letter), Ok('é'));
+        assert_eq!(buffer.numeric::<()>(), Ok('1'));
+        assert_eq!(buffer.category::<()>(GeneralCategory::OtherNumber), Ok('½'));
+    }
+
+    #[test]
+    fn should_parse_unicode_identifiers() {
+        let input = "_délka2 ∑".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+
+        assert_eq!(buffer.unicode_identifier::<()>(), Ok(String::from("_délka2")));
+        assert_eq!(buffer.whitespace::<()>(), Ok(' '));
+        assert!(buffer.xid_start::<()>().is_err());
+    }
+}
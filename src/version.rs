@@ -0,0 +1,136 @@
+
+use std::fmt;
+
+use crate::{ErrorKind, JlnError, Parser, TextToken};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Version {
+    pub major : u64,
+    pub minor : u64,
+    pub patch : u64,
+    pub pre : Vec<String>,
+    pub build : Vec<String>,
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f : &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if !self.pre.is_empty() {
+            write!(f, "-{}", self.pre.join("."))?;
+        }
+        if !self.build.is_empty() {
+            write!(f, "+{}", self.build.join("."))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "semver")]
+impl Version {
+    pub fn to_semver(&self) -> Option<semver::Version> {
+        Some(semver::Version {
+            major: self.major,
+            minor: self.minor,
+            patch: self.patch,
+            pre: semver::Prerelease::new(&self.pre.join(".")).ok()?,
+            build: semver::BuildMetadata::new(&self.build.join(".")).ok()?,
+        })
+    }
+}
+
+impl<'a, T : TextToken> Parser<'a, T> {
+    pub fn semver<E : JlnError>(&mut self) -> Result<Version, E> {
+        self.with_rollback(|input| {
+            input.consume_trivia();
+            input.no_trivia(|input| {
+                let major = input.version_number()?;
+                input.expect_no_case::<E>('.')?;
+                let minor = input.version_number()?;
+                input.expect_no_case::<E>('.')?;
+                let patch = input.version_number()?;
+                let pre = match input.option(|input| input.expect_no_case::<E>('-'))? {
+                    Some(_) => input.version_identifiers(true)?,
+                    None => vec![],
+                };
+                let build = match input.option(|input| input.expect_no_case::<E>('+'))? {
+                    Some(_) => input.version_identifiers(false)?,
+                    None => vec![],
+                };
+                Ok(Version { major, minor, patch, pre, build })
+            })
+        })
+    }
+
+    #[cfg(feature = "semver")]
+    pub fn semver_version<E : JlnError>(&mut self) -> Result<semver::Version, E> {
+        let start = self.next_significant();
+        self.with_rollback(|input| input.semver::<E>()?.to_semver().ok_or_else(|| E::custom(Some("semver"), start)))
+    }
+
+    fn version_number<E : JlnError>(&mut self) -> Result<u64, E> {
+        let start = self.index;
+        let digits = self.take_while::<E, _>(|t| t.to_char().is_ascii_digit())?.iter().map(|t| t.to_char()).collect::<String>();
+        if digits.is_empty() || (digits.len() > 1 && digits.starts_with('0')) {
+            return Err(E::from_kind(ErrorKind::InvalidNumber, start));
+        }
+        digits.parse().map_err(|_| E::from_kind(ErrorKind::Overflow, start))
+    }
+
+    fn version_identifiers<E : JlnError>(&mut self, numeric : bool) -> Result<Vec<String>, E> {
+        let mut identifiers = vec![];
+        loop {
+            let start = self.index;
+            let identifier = self.take_while::<E, _>(|t| t.to_char().is_ascii_alphanumeric() || t.to_char() == '-')?.iter().map(|t| t.to_char()).collect::<String>();
+            if identifier.is_empty() {
+                return Err(E::from_kind(ErrorKind::Unexpected, start));
+            }
+            if numeric && identifier.len() > 1 && identifier.starts_with('0') && identifier.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(E::from_kind(ErrorKind::InvalidNumber, start));
+            }
+            identifiers.push(identifier);
+            if self.option(|input| input.expect_no_case::<E>('.'))?.is_none() {
+                return Ok(identifiers);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Diagnostic;
+
+    fn parse(text : &str) -> Result<Version, String> {
+        let input = text.as_bytes().to_vec();
+        Parser::new(&input).semver::<Diagnostic>().map_err(|e| e.snapshot())
+    }
+
+    #[test]
+    fn should_parse_full_versions() {
+        let version = parse("1.20.3-rc.1.x-y+build.007").unwrap();
+
+        assert_eq!((version.major, version.minor, version.patch), (1, 20, 3));
+        assert_eq!(version.pre, vec!["rc", "1", "x-y"]);
+        assert_eq!(version.build, vec!["build", "007"]);
+        assert_eq!(version.to_string(), "1.20.3-rc.1.x-y+build.007");
+        assert_eq!(parse("0.0.0").unwrap(), Version::default());
+    }
+
+    #[test]
+    fn should_point_at_invalid_component() {
+        assert_eq!(parse("1.02.3"), Err("at 2: InvalidNumber\n".into()));
+        assert_eq!(parse("1.2.3-alpha.01"), Err("at 12: InvalidNumber\n".into()));
+        assert_eq!(parse("1.2.3-a..b"), Err("at 8: Unexpected\n".into()));
+        assert_eq!(parse("1.2.99999999999999999999"), Err("at 4: Overflow\n".into()));
+        assert_eq!(parse("1.2"), Err("at 3: expected `.`\n".into()));
+    }
+
+    #[cfg(feature = "semver")]
+    #[test]
+    fn should_convert_to_semver_crate() {
+        let input = b"2.1.0-beta+sha".to_vec();
+        let version = Parser::new(&input).semver_version::<()>().unwrap();
+
+        assert_eq!(version, semver::Version::parse("2.1.0-beta+sha").unwrap());
+    }
+}
@@ -0,0 +1,93 @@
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::{JlnError, Parser, Span};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Warning {
+    pub note : &'static str,
+    pub span : Span,
+}
+
+pub(crate) type WarningLog = Rc<RefCell<Vec<Warning>>>;
+
+impl<'a, T> Parser<'a, T> {
+    pub fn enable_warnings(&mut self) {
+        if self.warnings.is_none() {
+            self.warnings = Some(Rc::new(RefCell::new(vec![])));
+        }
+    }
+
+    pub fn disable_warnings(&mut self) {
+        self.warnings = None;
+    }
+
+    pub fn warnings(&self) -> Vec<Warning> {
+        self.warnings.as_ref().map(|x| x.borrow().clone()).unwrap_or_default()
+    }
+
+    pub fn warn(&self, note : &'static str, span : Span) {
+        if let Some(warnings) = &self.warnings {
+            warnings.borrow_mut().push(Warning { note, span });
+        }
+    }
+
+    pub fn deprecated<S, E : JlnError, F : FnOnce(&mut Parser<'a, T>) -> Result<S, E>>(&mut self, f : F, note : &'static str) -> Result<S, E> {
+        let (value, span) = self.spanned(f)?;
+        self.warn(note, span);
+        Ok(value)
+    }
+
+    pub(crate) fn warning_mark(&self) -> usize {
+        self.warnings.as_ref().map_or(0, |x| x.borrow().len())
+    }
+
+    pub(crate) fn warnings_since(&self, mark : usize) -> Vec<Warning> {
+        self.warnings.as_ref().map(|x| x.borrow()[mark..].to_vec()).unwrap_or_default()
+    }
+
+    pub(crate) fn discard_warnings(&self, mark : usize) {
+        if let Some(warnings) = &self.warnings {
+            warnings.borrow_mut().truncate(mark);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn assignment(input : &mut Parser<char>) -> Result<char, ()> {
+        let op = |input : &mut Parser<char>| input.deprecated(|input| input.tag_str::<()>("<-").map(|_| '='), "use `=` instead of `<-`");
+        input.or([op, |input| input.expect::<()>(&'=').copied()])
+    }
+
+    #[test]
+    fn should_record_deprecated_syntax() {
+        let input = "a<-b=c".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::builder(&input[..]).warnings().build();
+        buffer.get::<()>().unwrap();
+
+        assert_eq!(assignment(&mut buffer), Ok('='));
+        buffer.get::<()>().unwrap();
+        assert_eq!(assignment(&mut buffer), Ok('='));
+        assert_eq!(buffer.warnings(), vec![Warning { note: "use `=` instead of `<-`", span: Span::new(1, 3) }]);
+    }
+
+    #[test]
+    fn should_discard_warnings_from_failed_branch() {
+        let input = "<-x".chars().collect::<Vec<_>>();
+        let mut buffer = Parser::new(&input);
+        buffer.enable_warnings();
+
+        let result = buffer.with_rollback(|input| {
+            assignment(input)?;
+            input.expect::<()>(&'y').copied()
+        });
+
+        assert!(result.is_err());
+        assert!(buffer.warnings().is_empty());
+        assert_eq!(Parser::new(&input).deprecated(|input| input.get::<()>().copied(), "x").map(|_| ()), Ok(()));
+    }
+}